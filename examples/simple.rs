@@ -24,10 +24,10 @@ fn main() {
     let parsed = cli.parse();
 
     for subcommand in parsed.subcommands {
-        if subcommand.inner == &add {
-            println!("Adding package {:?}..", subcommand.arguments[0]);
-        } else if subcommand.inner == &rem {
-            println!("Removing package {:?}..", subcommand.arguments[0]);
+        if subcommand == add {
+            println!("Adding package {}..", subcommand.arguments[0].data);
+        } else if subcommand == rem {
+            println!("Removing package {}..", subcommand.arguments[0].data);
         }
     }
 }
@@ -1,32 +1,122 @@
 //! Contains [Argument]-related items, see specific documentation for more information
 
-use super::utils::writeln_term;
-use crate::io::Input;
-use crate::HELP_DEFAULT;
+use super::utils::{split_escaped, wrap, writeln_term, DefinitionId, Predicate, Shared};
+use crate::io::{Data, FromData, Input};
+use crate::messages::{message, Key};
+use crate::CLI_TABBING;
 
+use std::any::Any;
+use std::env;
 use std::fmt;
 use std::io::Write;
+use std::ops::{Bound, RangeBounds};
+use std::sync::{Arc, Mutex};
+
+/// Type-erased validator closure stored inside of an [Argument], see
+/// [Argument::validator] for more information
+type Validator<'a> = dyn Fn(&Data) -> Result<Arc<dyn Any + Send + Sync>, String> + Send + Sync + 'a;
+
+/// Type-erased sink closure stored inside of an [Argument], see [Argument::bind]
+/// for more information
+type Sink<'a> = dyn Fn(&Data) + Send + Sync + 'a;
+
+/// Closure giving the possible values of an [Argument] whilst parsing, see
+/// [Argument::choices_from] for more information
+type Choices<'a> = dyn Fn() -> Vec<String> + Send + Sync + 'a;
 
 /// An argument attached to the cli, allowing passing of user data to the top-level
 /// cli or subcommands
-#[derive(Debug, PartialEq, Clone)]
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
 pub struct Argument<'a> {
+    /// Stable identifier of this argument, see [Argument::id]
+    pub(crate) id: DefinitionId,
+
     /// Optional help message
-    help: Option<&'a str>,
+    pub(crate) help: Option<&'a str>,
 
-    /// Many [CallType]s corrosponding to this argument
-    calls: Vec<CallType>,
+    /// Optional extended help shown for `--help`, see [Argument::long_help]
+    pub(crate) long_help: Option<&'a str>,
+
+    /// Short, single-char calls corrosponding to this argument, e.g. `-v`
+    pub(crate) shorts: Vec<char>,
+
+    /// Long, multi-char calls corrosponding to this argument, e.g. `--verbose`
+    pub(crate) longs: Vec<String>,
 
     /// [Input] type allowed for this argument
-    input: Input,
+    pub(crate) input: Input,
 
-    /// Required argument for given root cli or [Subcommand](crate::Subcommand).
-    /// If this argument is not present whilst the cli parses, it will provide an
-    /// apt error
-    ///
-    /// To change the default behaviour of `false` (not required), simply modify
-    /// this value before it's time to parse.
-    required: bool,
+    /// Required argument for given root cli or [Subcommand](crate::Subcommand),
+    /// see [Argument::required]
+    pub(crate) required: bool,
+
+    /// If the value for this argument must be attached using `=`, e.g.
+    /// `--level=3`, meaning `--level 3` will be rejected whilst parsing
+    pub(crate) require_equals: bool,
+
+    /// If the value for this argument is optional, meaning it may be present
+    /// without one (`--color`) or with one attached using `=` (`--color=never`)
+    pub(crate) optional_value: bool,
+
+    /// If `,` is accepted as the decimal separator for [Input::Float] values,
+    /// e.g. `0,5` as commonly written in many european locales
+    pub(crate) decimal_comma: bool,
+
+    /// If values starting with `-` are allowed to be passed as the next input,
+    /// see [Argument::allow_hyphen_values]
+    pub(crate) allow_hyphen_values: bool,
+
+    /// If every following value is captured into one, see [Argument::greedy]
+    pub(crate) greedy: bool,
+
+    /// Delimiter which splits a single value into many, see
+    /// [Argument::value_delimiter]
+    pub(crate) value_delimiter: Option<char>,
+
+    /// If this argument may be given after any subcommand below where it's
+    /// attached, see [Argument::global]
+    pub(crate) global: bool,
+
+    /// Minimum and (if bounded) maximum amount of values allowed, see
+    /// [Argument::arity]
+    pub(crate) arity: Option<(usize, Option<usize>)>,
+
+    /// Environment variable which is read for this argument's value if it's
+    /// missing from the inputs given to the cli
+    pub(crate) env: Option<&'a str>,
+
+    /// Optional closure giving possible values whilst parsing, see
+    /// [Argument::choices_from]
+    pub(crate) choices: Option<Shared<Choices<'a>>>,
+
+    /// Optional validator which checks data once parsed, see [Argument::validator]
+    pub(crate) validator: Option<Shared<Validator<'a>>>,
+
+    /// Optional help section this argument is grouped into, see [Argument::section]
+    pub(crate) section: Option<&'a str>,
+
+    /// If this argument may also be given in a negated form, e.g. `--no-color`,
+    /// see [Argument::negatable]
+    pub(crate) negatable: bool,
+
+    /// What happens when this argument is given more than once, see
+    /// [Argument::repeats]
+    pub(crate) repeats: RepeatPolicy,
+
+    /// Optional limit on how many times this argument may be given, see
+    /// [Argument::max_occurrences]
+    pub(crate) max_occurrences: Option<usize>,
+
+    /// If repeated values are removed once parsed, see [Argument::unique]
+    pub(crate) unique: bool,
+
+    /// Optional predicate deciding if this argument is available, see
+    /// [Argument::available_if]
+    pub(crate) available: Option<Shared<Predicate<'a>>>,
+
+    /// Optional sink given this argument's data once parsed, see
+    /// [Argument::bind]
+    pub(crate) sink: Option<Shared<Sink<'a>>>,
 }
 
 impl<'a> Argument<'a> {
@@ -37,28 +127,491 @@ impl<'a> Argument<'a> {
         long_calls: impl IntoIterator<Item = &'a str>,
         input: impl Into<Input>,
     ) -> Self {
-        let mut calls: Vec<CallType> = short_calls
-            .into_iter()
-            .map(|call| CallType::Short(call))
-            .collect();
-        calls.append(
-            &mut long_calls
-                .into_iter()
-                .map(|call| CallType::Long(call.to_string()))
-                .collect::<Vec<CallType>>(),
-        );
-
         Self {
+            id: DefinitionId::new(),
             help: help.into(),
-            calls,
+            long_help: None,
+            shorts: short_calls.into_iter().collect(),
+            longs: long_calls
+                .into_iter()
+                .map(|call| call.to_string())
+                .collect(),
             input: input.into(),
             required: false,
+            require_equals: false,
+            optional_value: false,
+            decimal_comma: false,
+            allow_hyphen_values: false,
+            greedy: false,
+            value_delimiter: None,
+            global: false,
+            arity: None,
+            env: None,
+            choices: None,
+            validator: None,
+            section: None,
+            negatable: false,
+            repeats: RepeatPolicy::default(),
+            max_occurrences: None,
+            unique: false,
+            available: None,
+            sink: None,
+        }
+    }
+
+    /// Creates many new [Argument]s at once from `(short, long, input, help)`
+    /// tuples, useful for declaring lots of similar arguments without repeating
+    /// [Argument::new] for each one
+    ///
+    /// The arguments are returned owned so they may be attached afterwards using
+    /// [CliMake::add_args](crate::CliMake::add_args), e.g. `cli.add_args(&args)`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use climake::prelude::*;
+    ///
+    /// let args = Argument::many(vec![
+    ///     ('v', "verbose", Input::None, "Verbose mode"),
+    ///     ('q', "quiet", Input::None, "Quiet mode"),
+    /// ]);
+    ///
+    /// let mut cli = CliMake::new("example", vec![], vec![], None, None);
+    /// cli.add_args(&args);
+    /// ```
+    pub fn many(
+        definitions: impl IntoIterator<Item = (char, &'a str, Input, &'a str)>,
+    ) -> Vec<Self> {
+        definitions
+            .into_iter()
+            .map(|(short, long, input, help)| Self::new(help, vec![short], vec![long], input))
+            .collect()
+    }
+
+    /// Sets if this argument is required for the cli or
+    /// [Subcommand](crate::Subcommand) it's attached to, chainable
+    ///
+    /// Arguments aren't required by default. Every required argument missing
+    /// once the invoked level has been parsed is reported together as a
+    /// [ParseError::MissingArguments](crate::ParseError::MissingArguments),
+    /// unless it could be read from its [Argument::env] instead.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use climake::prelude::*;
+    ///
+    /// let mut output = Argument::new("Output file", vec!['o'], vec![], Input::Path);
+    /// output.required(true);
+    /// let cli = CliMake::new("tool", vec![&output], vec![], None, None);
+    ///
+    /// let inputs = vec!["tool".to_string()];
+    /// let error = cli.try_parse_custom(inputs.into_iter()).unwrap_err();
+    /// assert_eq!(error.to_string(), "Missing required argument -o");
+    /// ```
+    pub fn required(&mut self, required: bool) -> &mut Self {
+        self.required = required;
+        self
+    }
+
+    /// Sets if the value for this argument must be attached using `=` (e.g.
+    /// `--level=3`) instead of being passed as the next input, chainable
+    ///
+    /// This is useful for disambiguating arguments where the next input could
+    /// otherwise be mistaken as a value.
+    pub fn require_equals(&mut self, require_equals: bool) -> &mut Self {
+        self.require_equals = require_equals;
+        self
+    }
+
+    /// Sets if the value for this argument is optional, chainable
+    ///
+    /// Arguments with an optional value may be passed on their own (`--color`)
+    /// or with a value attached using `=` (`--color=never`), which is reported
+    /// as a [Data::Flag](crate::io::Data::Flag) once parsed. As the next input
+    /// could be mistaken as a value, optional values may only be attached with `=`.
+    pub fn optional_value(&mut self, optional_value: bool) -> &mut Self {
+        self.optional_value = optional_value;
+        self
+    }
+
+    /// Sets if `,` is accepted as the decimal separator for [Input::Float] values
+    /// (e.g. `0,5` as well as `0.5`), chainable
+    ///
+    /// This is never guessed from the user's locale, so it must be explicitly
+    /// enabled for arguments which european users may pass numbers into.
+    pub fn decimal_comma(&mut self, decimal_comma: bool) -> &mut Self {
+        self.decimal_comma = decimal_comma;
+        self
+    }
+
+    /// Sets if values starting with `-` (e.g. `-5` or `-x`) may be passed as the
+    /// next input after this argument, rather than being treated as a call,
+    /// chainable
+    ///
+    /// Negative numbers are always accepted for [Input::Float] arguments, so this
+    /// is only needed for other inputs, such as text which may be negative. As
+    /// [Input::Paths] arguments take every value they can, these will take every
+    /// following input up until a subcommand once this is set.
+    pub fn allow_hyphen_values(&mut self, allow_hyphen_values: bool) -> &mut Self {
+        self.allow_hyphen_values = allow_hyphen_values;
+        self
+    }
+
+    /// Sets if this argument captures every following input up until the next
+    /// call or subcommand, joining them with spaces into a single value,
+    /// chainable
+    ///
+    /// This is meant for [Input::Text] arguments such as
+    /// `--message this is my commit message`, which would otherwise need quoting.
+    /// A `--` ends the capture early, with any inputs after it left in
+    /// [ParsedCli::trailing](crate::parsed::ParsedCli::trailing) as usual. Once
+    /// [Argument::allow_hyphen_values] is also set, calls are captured too so
+    /// only a `--` or subcommand ends the capture.
+    pub fn greedy(&mut self, greedy: bool) -> &mut Self {
+        self.greedy = greedy;
+        self
+    }
+
+    /// Sets a delimiter which splits each value given to this argument into many
+    /// (e.g. `,` for `--features a,b,c`), chainable
+    ///
+    /// Values are then collected in the same way as [RepeatPolicy::Append], into
+    /// a [Data::Paths] for path inputs, [Data::Floats] for numbers or
+    /// [Data::Texts] for others, even if only a single value was given. The
+    /// delimiter may be escaped with a backslash to keep it inside of a value,
+    /// e.g. `a\,b`. This has no effect on arguments with an
+    /// [Argument::optional_value].
+    pub fn value_delimiter(&mut self, delimiter: char) -> &mut Self {
+        self.value_delimiter = Some(delimiter);
+        self
+    }
+
+    /// Sets if this argument is global, meaning it may also be given after any
+    /// subcommand below the cli or subcommand it's attached to, chainable
+    ///
+    /// This is useful for arguments such as `--verbose` or `--config`, and works
+    /// like [CliMake::bubble_arguments](crate::CliMake::bubble_arguments) does for
+    /// every argument. Once parsed, global arguments are found at the level
+    /// they're attached to as well as in every parsed subcommand below it.
+    pub fn global(&mut self, global: bool) -> &mut Self {
+        self.global = global;
+        self
+    }
+
+    /// Sets the amount of values this argument takes, chainable
+    ///
+    /// This may be exact (`2..=2`), a range (`1..=3`) or unbounded (`1..`), with
+    /// as many values as allowed being taken from the following inputs and too
+    /// few being rejected whilst parsing. Values are then collected in the same
+    /// way as [RepeatPolicy::Append] unless only a single value is allowed. By
+    /// default, [Input::Paths] arguments take every value they can and others
+    /// take exactly one.
+    pub fn arity(&mut self, range: impl RangeBounds<usize>) -> &mut Self {
+        let min = match range.start_bound() {
+            Bound::Included(min) => *min,
+            Bound::Excluded(min) => min + 1,
+            Bound::Unbounded => 0,
+        };
+        let max = match range.end_bound() {
+            Bound::Included(max) => Some(*max),
+            Bound::Excluded(max) => Some(max.saturating_sub(1)),
+            Bound::Unbounded => None,
+        };
+
+        self.arity = Some((min, max));
+        self
+    }
+
+    /// Describes the amount of values allowed by this argument's
+    /// [Argument::arity] for messages, e.g. `2`, `1 to 3` or `at least 1`
+    pub(crate) fn arity_msg(&self) -> String {
+        match self.arity {
+            Some((min, Some(max))) if min == max => min.to_string(),
+            Some((min, Some(max))) => format!("{} to {}", min, max),
+            Some((min, None)) => format!("at least {}", min),
+            None => "1".to_string(),
+        }
+    }
+
+    /// Checks if this argument's data is collected from many values, due to a
+    /// [Argument::value_delimiter] or an [Argument::arity] allowing more than one
+    fn is_multi_valued(&self) -> bool {
+        self.value_delimiter.is_some() || self.arity.is_some_and(|(_, max)| max != Some(1))
+    }
+
+    /// Sets an environment variable to read this argument's value from if it's
+    /// missing from the inputs given to the cli, chainable
+    ///
+    /// The environment is only read for arguments which are actually missing once
+    /// parsed, so a variable being set never matters if the argument was given.
+    pub fn env(&mut self, var: &'a str) -> &mut Self {
+        self.env = Some(var);
+        self
+    }
+
+    /// Sets a closure giving the possible values of this argument, which is
+    /// called whilst parsing rather than when the cli is made, chainable
+    ///
+    /// This is for values which can't be known up front such as the names of
+    /// profiles on disk, unlike the static list of an [Input::Choice]. Values
+    /// which aren't possible are rejected with the closest possible value
+    /// suggested, in the same way as choices.
+    pub fn choices_from(
+        &mut self,
+        choices: impl Fn() -> Vec<String> + Send + Sync + 'a,
+    ) -> &mut Self {
+        self.choices = Some(Shared(Arc::new(choices)));
+        self
+    }
+
+    /// Gets the possible values of this argument whilst parsing, from either its
+    /// [Argument::choices_from] closure or its [Input::Choice], if any
+    pub(crate) fn possible_values(&self) -> Option<Vec<String>> {
+        match (&self.choices, &self.input) {
+            (Some(choices), _) => Some((choices.0)()),
+            (None, Input::Choice(choices)) => Some(choices.clone()),
+            (None, _) => None,
         }
     }
 
+    /// Sets a validator for this argument, chainable
+    ///
+    /// The validator is given this argument's [Data] once it's been parsed and
+    /// may reject it with an error message to show to the user. Anything created
+    /// whilst validating (e.g. a compiled pattern or opened file) can be returned
+    /// to be stashed alongside the data, so it doesn't need to be created twice;
+    /// see [ParsedArgument::get_custom](crate::parsed::ParsedArgument::get_custom)
+    /// for getting it back.
+    pub fn validator<T: Send + Sync + 'static>(
+        &mut self,
+        validator: impl Fn(&Data) -> Result<T, String> + Send + Sync + 'a,
+    ) -> &mut Self {
+        self.validator = Some(Shared(Arc::new(move |data: &Data| {
+            validator(data).map(|custom| Arc::new(custom) as Arc<dyn Any + Send + Sync>)
+        })));
+        self
+    }
+
+    /// Sets the help section this argument is grouped into, chainable
+    ///
+    /// Once a cli or subcommand has more arguments than its
+    /// [CliMake::section_threshold](crate::CliMake::section_threshold), arguments
+    /// in a section are collapsed in help and may instead be shown using
+    /// `--help <section>`, keeping large clis navigable.
+    pub fn section(&mut self, section: &'a str) -> &mut Self {
+        self.section = Some(section);
+        self
+    }
+
+    /// Sets extended help shown below the usual help message for `--help`,
+    /// chainable
+    ///
+    /// The short `-h` call only shows the usual one-line help, so this is for
+    /// details which would clutter it, such as the accepted formats of a value.
+    /// Paragraphs are separated by blank lines and each is wrapped on its own.
+    pub fn long_help(&mut self, long_help: &'a str) -> &mut Self {
+        self.long_help = Some(long_help);
+        self
+    }
+
+    /// Sets if this argument may also be given in a negated form for each of its
+    /// long calls, e.g. `--no-color` as well as `--color`, chainable
+    ///
+    /// Negatable arguments are reported as a [Data::Bool] once parsed, being
+    /// `false` if the negated form was given, and have both forms shown on one
+    /// line in help as `--[no-]color`. Only arguments with an [Input::None] may
+    /// be negated, as any value given wouldn't make sense alongside the negation.
+    pub fn negatable(&mut self, negatable: bool) -> &mut Self {
+        self.negatable = negatable;
+        self
+    }
+
+    /// Sets what happens when this argument is given more than once (e.g.
+    /// `-I one -I two`) at the same level, chainable. This defaults to
+    /// [RepeatPolicy::Each]
+    pub fn repeats(&mut self, policy: RepeatPolicy) -> &mut Self {
+        self.repeats = policy;
+        self
+    }
+
+    /// Sets the most times this argument may be given at the same level,
+    /// chainable
+    ///
+    /// This bounds repeatable flags such as `-vvvv`, which are counted using
+    /// [ParsedArgument::occurrences](crate::parsed::ParsedArgument::occurrences),
+    /// as well as arguments whose values are collected with [RepeatPolicy::Append].
+    /// Giving it more often is rejected with an error containing the amount
+    /// found, and the limit is shown in the extended help for `--help`.
+    pub fn max_occurrences(&mut self, max: usize) -> &mut Self {
+        self.max_occurrences = Some(max);
+        self
+    }
+
+    /// Sets if repeated values given to this argument are removed once parsed,
+    /// keeping the first of each in order, chainable
+    ///
+    /// This only affects arguments whose data is made of many values, such as
+    /// those with an [Argument::arity] or [RepeatPolicy::Append], and is useful
+    /// for generated command lines which may repeat the same value thousands of
+    /// times. The amount removed is given in
+    /// [ParsedArgument::duplicates](crate::parsed::ParsedArgument::duplicates).
+    pub fn unique(&mut self, unique: bool) -> &mut Self {
+        self.unique = unique;
+        self
+    }
+
+    /// Sets a sink which is given this argument's [Data] whilst parsing,
+    /// chainable
+    ///
+    /// This allows small programs to fill in their config as the cli is parsed
+    /// rather than inspecting the [ParsedCli](crate::parsed::ParsedCli) at all.
+    /// Sinks are only called once parsing has succeeded, in the order arguments
+    /// were given, and are called for every occurrence of an argument. Setting a
+    /// new sink replaces the last one. See [Argument::bind_to] for filling in a
+    /// single value directly.
+    pub fn bind(&mut self, sink: impl FnMut(&Data) + Send + 'a) -> &mut Self {
+        let sink = Mutex::new(sink);
+        self.sink = Some(Shared(Arc::new(move |data: &Data| {
+            if let Ok(mut sink) = sink.try_lock() {
+                (*sink)(data)
+            }
+        })));
+        self
+    }
+
+    /// Binds this argument to a `destination` which is set to its [Data]
+    /// converted using [FromData] whilst parsing, chainable
+    ///
+    /// The destination is left as-is if the data can't be converted, so it may
+    /// hold a default beforehand. See [Argument::bind] for more information.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use climake::prelude::*;
+    ///
+    /// let mut port: u16 = 80;
+    ///
+    /// {
+    ///     let mut port_arg = Argument::new("Port", vec!['p'], vec!["port"], Input::Text);
+    ///     port_arg.bind_to(&mut port);
+    ///
+    ///     let cli = CliMake::new("server", vec![&port_arg], vec![], None, None);
+    ///     cli.try_parse_str("--port 8080").unwrap();
+    /// }
+    ///
+    /// assert_eq!(port, 8080);
+    /// ```
+    pub fn bind_to<T: FromData + Send + 'a>(&mut self, destination: &'a mut T) -> &mut Self {
+        self.bind(move |data| {
+            if let Some(value) = T::from_data(data) {
+                *destination = value;
+            }
+        })
+    }
+
+    /// Sets a predicate deciding if this argument is available, chainable
+    ///
+    /// This is useful for platform or feature specific arguments, e.g.
+    /// `available_if(|| cfg!(windows))`. Unavailable arguments are hidden from
+    /// help and rejected whilst parsing with an error saying they aren't
+    /// available, rather than being reported as unknown. The predicate is checked
+    /// whenever it's needed, so it may also read the environment.
+    pub fn available_if(&mut self, predicate: impl Fn() -> bool + Send + Sync + 'a) -> &mut Self {
+        self.available = Some(Shared(Arc::new(predicate)));
+        self
+    }
+
+    /// Checks if this argument is available, see [Argument::available_if]
+    pub(crate) fn is_available(&self) -> bool {
+        self.available
+            .as_ref()
+            .map_or(true, |predicate| (predicate.0)())
+    }
+
+    /// Reads the value of this argument's [Argument::env] variable (if it has one
+    /// and it's set) and maps it into [Data] using [Argument::to_data], giving
+    /// the variable alongside for error reporting
+    pub(crate) fn read_env(&self) -> Option<(&'a str, Result<Data, String>)> {
+        let var = self.env?;
+        let value = env::var(var).ok()?;
+
+        Some((var, self.to_data(vec![value])))
+    }
+
+    /// Formats the most descriptive call for this argument for messages, which
+    /// is the first long call (e.g. `--verbose`) or otherwise the first short
+    /// call (e.g. `-v`)
+    pub(crate) fn display_call(&self) -> String {
+        match (self.longs.first(), self.shorts.first()) {
+            (Some(long), _) => format!("--{}", long),
+            (None, Some(short)) => format!("-{}", short),
+            (None, None) => String::new(),
+        }
+    }
+
+    /// Maps user-provided `values` into [Data] for this argument, applying any
+    /// value-related settings beforehand. Values which aren't allowed are
+    /// returned as an error, in the same way as [Data::new]
+    ///
+    /// Arguments set with [Argument::optional_value] are always mapped into a
+    /// [Data::Flag], containing the first value if one was given.
+    pub(crate) fn to_data(&self, mut values: Vec<String>) -> Result<Data, String> {
+        if self.greedy && !values.is_empty() {
+            values = vec![values.join(" ")];
+        }
+
+        if let Some(delimiter) = self.value_delimiter.filter(|_| !self.optional_value) {
+            values = values
+                .iter()
+                .flat_map(|value| split_escaped(value, delimiter))
+                .collect();
+        }
+
+        if let Some(choices) = &self.choices {
+            let choices = (choices.0)();
+
+            if let Some(value) = values.iter().find(|value| !choices.contains(value)) {
+                return Err(value.clone());
+            }
+        }
+
+        if self.decimal_comma && self.input == Input::Float {
+            for value in values.iter_mut() {
+                if !value.contains('.') && value.matches(',').count() == 1 {
+                    *value = value.replace(',', ".");
+                }
+            }
+        }
+
+        if self.optional_value {
+            Data::new_flag(&self.input, values.into_iter().next())
+        } else if self.is_multi_valued() && self.input != Input::None {
+            let data = values
+                .into_iter()
+                .map(|value| Data::new(&self.input, vec![value]))
+                .collect::<Result<Vec<Data>, String>>()?;
+
+            Ok(Data::append(&self.input, data))
+        } else {
+            Data::new(&self.input, values)
+        }
+    }
+
+    /// Gets the stable identifier of this argument, which is unique to where it
+    /// was created and kept whenever it's cloned
+    ///
+    /// This is given to [ParsedArgument](crate::parsed::ParsedArgument)s as well,
+    /// so parsed arguments may be matched up to their definition by identifier
+    /// alone. Identifiers are not taken into account when comparing arguments.
+    pub fn id(&self) -> usize {
+        self.id.0
+    }
+
     /// Adds a single short call, chainable
     pub fn add_scall(&mut self, short_call: impl Into<char>) -> &mut Self {
-        self.calls.push(short_call.into().into());
+        self.shorts.push(short_call.into());
         self
     }
 
@@ -72,7 +625,7 @@ impl<'a> Argument<'a> {
 
     /// Adds a single long call, chainable
     pub fn add_lcall(&mut self, long_call: impl Into<String>) -> &mut Self {
-        self.calls.push(long_call.into().into());
+        self.longs.push(long_call.into());
         self
     }
 
@@ -84,6 +637,51 @@ impl<'a> Argument<'a> {
         self
     }
 
+    /// Checks if this argument can be called using the given `call`
+    pub(crate) fn has_call(&self, call: &CallType) -> bool {
+        match call {
+            CallType::Short(c) => self.shorts.contains(c),
+            CallType::Long(string) => self.longs.contains(string) || self.is_negation(call),
+        }
+    }
+
+    /// Checks if the given `call` is the negated form of one of this argument's
+    /// long calls (e.g. `--no-color`), see [Argument::negatable]
+    pub(crate) fn is_negation(&self, call: &CallType) -> bool {
+        match call {
+            CallType::Long(string) if self.negatable && self.input == Input::None => string
+                .strip_prefix("no-")
+                .is_some_and(|positive| self.longs.iter().any(|long| long == positive)),
+            _ => false,
+        }
+    }
+
+    /// Checks if this argument can be called by the given `name`, which is either
+    /// one of its long calls without the dashes (e.g. `verbose`) or a short call
+    pub(crate) fn is_named(&self, name: &str) -> bool {
+        let mut chars = name.chars();
+
+        match (chars.next(), chars.next()) {
+            (Some(c), None) if self.shorts.contains(&c) => true,
+            _ => self.longs.iter().any(|call| call == name),
+        }
+    }
+
+    /// Checks if the lowercase `query` is contained in this argument's help
+    /// messages or any of its long calls, used for searching help
+    pub(crate) fn search(&self, query: &str) -> bool {
+        self.is_available()
+            && (self
+                .help
+                .into_iter()
+                .chain(self.long_help)
+                .any(|help| help.to_lowercase().contains(query))
+                || self
+                    .longs
+                    .iter()
+                    .any(|call| call.to_lowercase().contains(query)))
+    }
+
     /// Generates compact help message for current [Argument]
     ///
     /// This writes directly to a buffer of some kind (typically [std::io::stdout])
@@ -96,54 +694,243 @@ impl<'a> Argument<'a> {
     /// ```none
     ///   (-v, --verbose) — Verbose mode
     /// ```
-    pub(crate) fn help_name_msg(&self, buf: &mut impl Write) -> std::io::Result<()> {
-        let mut lc_buf: Vec<String> = Vec::new();
-        let mut sc_buf: Vec<char> = Vec::new();
-
-        for call in self.calls.iter() {
-            match call {
-                CallType::Long(call) => lc_buf.push(format!("--{}", call)),
-                CallType::Short(call) => sc_buf.push(*call),
-            }
+    ///
+    /// Arguments without any calls can never be given, so nothing is written for
+    /// them; these are instead reported by [CliMake::validate](crate::CliMake::validate).
+    pub(crate) fn help_name_msg(
+        &self,
+        width: usize,
+        slash: bool,
+        buf: &mut impl Write,
+    ) -> std::io::Result<()> {
+        if self.shorts.is_empty() && self.longs.is_empty() {
+            return Ok(());
+        }
+
+        let mut formatted_calls: Vec<String> = Vec::with_capacity(self.longs.len() + 1);
+        let (long_prefix, equals) = if slash { ("/", ":") } else { ("--", "=") };
+
+        if slash {
+            formatted_calls.extend(self.shorts.iter().map(|c| format!("/{}", c)));
+        } else if !self.shorts.is_empty() {
+            formatted_calls.push(format!("-{}", self.shorts.iter().collect::<String>()));
         }
 
-        let short_calls: String = if sc_buf.len() == 0 {
-            String::new()
+        let negation = if self.negatable && self.input == Input::None {
+            "[no-]"
         } else {
-            format!("-{}", sc_buf.iter().collect::<String>())
+            ""
         };
-
-        let mut formatted_calls = vec![short_calls];
-        formatted_calls.append(&mut lc_buf);
+        formatted_calls.extend(
+            self.longs
+                .iter()
+                .map(|call| format!("{}{}{}", long_prefix, negation, call)),
+        );
 
         let formatted_help = match self.help {
             Some(msg) => msg,
-            None => HELP_DEFAULT,
+            None => Key::NoHelp.template(),
+        };
+        let required_msg = match self.required {
+            true => format!("{} ", message(Key::Required, &[])),
+            false => String::new(),
+        };
+        let env_msg = match self.env {
+            Some(var) => format!("{} ", message(Key::EnvMarker, &[&var])),
+            None => String::new(),
+        };
+        let input_msg = match self.input.hint() {
+            Some(hint) if self.optional_value => format!("[{}{}] ", equals, hint),
+            Some(hint) if self.require_equals => format!("{}[{}] ", equals, hint),
+            Some(hint) => match self.value_delimiter {
+                Some(delimiter) => format!("[{}{}...] ", hint, delimiter),
+                None if self.greedy => format!("[{}...] ", hint),
+                None => self.input.to_string(),
+            },
+            None => self.input.to_string(),
         };
-        let required_msg = if self.required { "[REQUIRED] " } else { "" };
 
         writeln_term(
-            if formatted_calls.len() == 1 && formatted_calls[0] != "" {
+            if formatted_calls.len() == 1 {
                 format!(
-                    "{} {}{}— {}",
-                    formatted_calls[0], self.input, required_msg, formatted_help
+                    "{} {}{}{}— {}",
+                    formatted_calls[0], input_msg, required_msg, env_msg, formatted_help
                 )
             } else {
                 format!(
-                    "({}) {}{}— {}",
+                    "({}) {}{}{}— {}",
                     formatted_calls.join(", "),
-                    self.input,
+                    input_msg,
                     required_msg,
+                    env_msg,
                     formatted_help,
                 )
             },
+            width,
             buf,
         )
     }
+
+    /// Generates the extended help shown below this argument's entry for
+    /// `--help`, with each paragraph of [Argument::long_help] and any
+    /// [Argument::max_occurrences] limit tabbed in further and wrapped to `width`.
+    /// Nothing is written if there's no extended help
+    ///
+    /// # Example
+    ///
+    /// What this may look like below the entry:
+    ///
+    /// ```none
+    ///   --since [text] — Only show changes since a date
+    ///     Accepts dates such as 2024-05-01 or relative times such as
+    ///     3d, 2w or 1y.
+    /// ```
+    pub(crate) fn long_help_msg(&self, width: usize, buf: &mut impl Write) -> std::io::Result<()> {
+        let mut paragraphs: Vec<String> = match self.long_help {
+            Some(long_help) => long_help.split("\n\n").map(String::from).collect(),
+            None => vec![],
+        };
+        if let Some(max) = self.max_occurrences {
+            paragraphs.push(message(Key::MaxOccurrences, &[&max]));
+        }
+        let tabbing = CLI_TABBING.repeat(2);
+        let text_width = width.saturating_sub(tabbing.chars().count()).max(1);
+
+        for (ind, paragraph) in paragraphs.iter().enumerate() {
+            if ind != 0 {
+                buf.write_all("\n".as_bytes())?;
+            }
+
+            let paragraph = paragraph
+                .split_whitespace()
+                .collect::<Vec<&str>>()
+                .join(" ");
+            for line in wrap(&paragraph, text_width) {
+                buf.write_all(format!("{}{}\n", tabbing, line).as_bytes())?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Generates the help message for a list of `arguments` wrapped to `width`,
+/// collapsing arguments which are in an [Argument::section] into a short summary
+/// of each section if there are more arguments than the given `threshold`. Each
+/// argument's [Argument::long_help] is also shown if `long` is set
+///
+/// # Example
+///
+/// What this may look like once collapsed:
+///
+/// ```none
+///   (-v, --verbose) — Verbose mode
+///
+/// Argument sections (see --help <section>):
+///   network — 14 arguments
+/// ```
+pub(crate) fn arguments_msg(
+    arguments: &[&Argument],
+    threshold: usize,
+    width: usize,
+    slash: bool,
+    long: bool,
+    buf: &mut impl Write,
+) -> std::io::Result<()> {
+    if arguments.is_empty() {
+        return writeln_term(message(Key::NoArguments, &[]), width, buf);
+    }
+
+    if arguments.len() <= threshold {
+        for argument in arguments.iter() {
+            argument.help_name_msg(width, slash, buf)?;
+
+            if long {
+                argument.long_help_msg(width, buf)?;
+            }
+        }
+
+        return Ok(());
+    }
+
+    let mut sections: Vec<(&str, usize)> = vec![];
+
+    for argument in arguments.iter() {
+        match argument.section {
+            Some(section) => match sections.iter_mut().find(|(name, _)| *name == section) {
+                Some((_, amount)) => *amount += 1,
+                None => sections.push((section, 1)),
+            },
+            None => {
+                argument.help_name_msg(width, slash, buf)?;
+
+                if long {
+                    argument.long_help_msg(width, buf)?;
+                }
+            }
+        }
+    }
+
+    if !sections.is_empty() {
+        buf.write_all(format!("\n{}\n", message(Key::SectionsHeading, &[])).as_bytes())?;
+
+        for (name, amount) in sections {
+            writeln_term(message(Key::SectionSummary, &[&name, &amount]), width, buf)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Generates the help message for only the `arguments` in the given `section`,
+/// including their [Argument::long_help] as this is only shown for `--help`
+pub(crate) fn section_msg(
+    arguments: &[&Argument],
+    section: &str,
+    width: usize,
+    slash: bool,
+    buf: &mut impl Write,
+) -> std::io::Result<()> {
+    let mut found = false;
+
+    let in_section =
+        |argument: &&&Argument| argument.section == Some(section) && argument.is_available();
+
+    for argument in arguments.iter().filter(in_section) {
+        argument.help_name_msg(width, slash, buf)?;
+        argument.long_help_msg(width, buf)?;
+        found = true;
+    }
+
+    if !found {
+        writeln_term(message(Key::NoSectionArguments, &[&section]), width, buf)?;
+    }
+
+    Ok(())
+}
+
+/// Policy for an [Argument] given more than once at the same level, as set by
+/// [Argument::repeats]
+#[derive(Debug, Default, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum RepeatPolicy {
+    /// Every occurrence is kept as its own parsed argument, the default
+    #[default]
+    Each,
+
+    /// Values of every occurrence are collected into one parsed argument, as a
+    /// [Data::Paths] for path inputs, [Data::Floats] for numbers or [Data::Texts]
+    /// for other inputs taking a value, e.g. `-I one -I two` giving both paths
+    Append,
+
+    /// Only the last occurrence is kept, allowing earlier ones to be overridden
+    LastWins,
+
+    /// Giving the argument more than once is rejected with an error
+    ErrorOnDuplicate,
 }
 
 /// A single type of call for an [Argument], can be a short call or a long call
-#[derive(Debug, PartialEq, Clone)]
+#[derive(Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Clone)]
 pub(crate) enum CallType {
     /// Short, single-char call, e.g. `-h`
     Short(char),
@@ -155,7 +942,7 @@ pub(crate) enum CallType {
 impl fmt::Display for CallType {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            CallType::Short(c) => write!(f, "{}", c),
+            CallType::Short(c) => write!(f, "-{}", c),
             CallType::Long(string) => write!(f, "--{}", string),
         }
     }
@@ -192,43 +979,88 @@ mod tests {
         assert_eq!(
             Argument::new(None, vec!['a', 'b'], vec!["hi", "there"], Input::Text),
             Argument {
-                calls: vec![
-                    CallType::Short('a'),
-                    CallType::Short('b'),
-                    CallType::Long("hi".to_string()),
-                    CallType::Long("there".to_string())
-                ],
+                id: DefinitionId::new(),
+                shorts: vec!['a', 'b'],
+                longs: vec!["hi".to_string(), "there".to_string()],
                 help: None,
+                long_help: None,
                 input: Input::Text,
                 required: false,
+                require_equals: false,
+                optional_value: false,
+                decimal_comma: false,
+                allow_hyphen_values: false,
+                greedy: false,
+                value_delimiter: None,
+                global: false,
+                arity: None,
+                env: None,
+                choices: None,
+                validator: None,
+                section: None,
+                negatable: false,
+                repeats: RepeatPolicy::default(),
+                max_occurrences: None,
+                unique: false,
+                available: None,
+                sink: None,
             }
         )
     }
 
+    /// Checks that the [Argument::many] method works correctly
+    #[test]
+    fn arg_many() {
+        assert_eq!(
+            Argument::many(vec![
+                ('v', "verbose", Input::None, "Verbose"),
+                ('o', "output", Input::Path, "Output")
+            ]),
+            vec![
+                Argument::new("Verbose", vec!['v'], vec!["verbose"], Input::None),
+                Argument::new("Output", vec!['o'], vec!["output"], Input::Path)
+            ]
+        )
+    }
+
     /// Checks that the [Argument::help_name_msg] method works correctly
     #[test]
     fn name_help() -> std::io::Result<()> {
         let mut chk_vec: Vec<u8> = vec![];
 
-        Argument::new(None, vec![], vec![], Input::None).help_name_msg(&mut chk_vec)?;
+        Argument::new(None, vec![], vec![], Input::None).help_name_msg(80, false, &mut chk_vec)?;
+        assert!(chk_vec.is_empty());
+
+        Argument::new("Some simple help", vec!['a'], vec!["long"], Input::Text).help_name_msg(
+            80,
+            false,
+            &mut chk_vec,
+        )?;
         assert_eq!(
             std::str::from_utf8(chk_vec.as_slice()).unwrap(),
-            "  () — No help provided\n"
+            "  (-a, --long) [text] — Some simple help\n"
         );
         chk_vec = vec![];
 
-        Argument::new("Some simple help", vec!['a'], vec!["long"], Input::Text)
-            .help_name_msg(&mut chk_vec)?;
+        Argument::new(None, vec!['a'], vec![], Input::Text).help_name_msg(
+            80,
+            false,
+            &mut chk_vec,
+        )?;
         assert_eq!(
             std::str::from_utf8(chk_vec.as_slice()).unwrap(),
-            "  (-a, --long) [text] — Some simple help\n"
+            "  -a [text] — No help provided\n"
         );
         chk_vec = vec![];
 
-        Argument::new(None, vec!['a'], vec![], Input::Text).help_name_msg(&mut chk_vec)?;
+        Argument::new(None, vec![], vec!["long"], Input::Text).help_name_msg(
+            80,
+            false,
+            &mut chk_vec,
+        )?;
         assert_eq!(
             std::str::from_utf8(chk_vec.as_slice()).unwrap(),
-            "  -a [text] — No help provided\n"
+            "  --long [text] — No help provided\n"
         );
 
         Ok(())
@@ -241,8 +1073,8 @@ mod tests {
         let mut chk_vec: Vec<u8> = vec![];
 
         let mut arg = Argument::new("Some argument", vec!['s'], vec![], Input::None);
-        arg.required = true;
-        arg.help_name_msg(&mut chk_vec)?;
+        arg.required(true);
+        arg.help_name_msg(80, false, &mut chk_vec)?;
         assert_eq!(
             std::str::from_utf8(chk_vec.as_slice()).unwrap(),
             "  -s [REQUIRED] — Some argument\n"
@@ -250,6 +1082,271 @@ mod tests {
 
         Ok(())
     }
+
+    /// Checks that the [Argument::help_name_msg] method shows the `=` needed for
+    /// arguments set with [Argument::require_equals]
+    #[test]
+    fn name_help_require_equals() -> std::io::Result<()> {
+        let mut chk_vec: Vec<u8> = vec![];
+
+        Argument::new("Some level", vec!['l'], vec!["level"], Input::Text)
+            .require_equals(true)
+            .help_name_msg(80, false, &mut chk_vec)?;
+        assert_eq!(
+            std::str::from_utf8(chk_vec.as_slice()).unwrap(),
+            "  (-l, --level) =[text] — Some level\n"
+        );
+
+        Ok(())
+    }
+
+    /// Checks that the [Argument::help_name_msg] method shows that the value is
+    /// optional for arguments set with [Argument::optional_value]
+    #[test]
+    fn name_help_optional_value() -> std::io::Result<()> {
+        let mut chk_vec: Vec<u8> = vec![];
+
+        Argument::new("When to color", vec!['c'], vec!["color"], Input::Text)
+            .optional_value(true)
+            .help_name_msg(80, false, &mut chk_vec)?;
+        assert_eq!(
+            std::str::from_utf8(chk_vec.as_slice()).unwrap(),
+            "  (-c, --color) [=text] — When to color\n"
+        );
+
+        Ok(())
+    }
+
+    /// Checks that the [Argument::help_name_msg] method shows calls in the
+    /// windows style when asked to, as used by [CliMake::slash_help]
+    #[test]
+    fn name_help_slash() -> std::io::Result<()> {
+        let mut chk_vec: Vec<u8> = vec![];
+
+        Argument::new("Some output", vec!['o', 'O'], vec!["output"], Input::Text)
+            .require_equals(true)
+            .help_name_msg(80, true, &mut chk_vec)?;
+        assert_eq!(
+            std::str::from_utf8(chk_vec.as_slice()).unwrap(),
+            "  (/o, /O, /output) :[text] — Some output\n"
+        );
+
+        Ok(())
+    }
+
+    /// Checks that the [Argument::help_name_msg] method shows both forms of
+    /// arguments set with [Argument::negatable] on one line
+    #[test]
+    fn name_help_negatable() -> std::io::Result<()> {
+        let mut chk_vec: Vec<u8> = vec![];
+
+        Argument::new("Color output", vec![], vec!["color"], Input::None)
+            .negatable(true)
+            .help_name_msg(80, false, &mut chk_vec)?;
+        assert_eq!(
+            std::str::from_utf8(chk_vec.as_slice()).unwrap(),
+            "  --[no-]color — Color output\n"
+        );
+
+        Ok(())
+    }
+
+    /// Checks that the [Argument::to_data] method splits values by the
+    /// [Argument::value_delimiter], keeping escaped delimiters
+    #[test]
+    fn to_data_value_delimiter() -> std::io::Result<()> {
+        let mut arg = Argument::new("Features", vec![], vec!["features"], Input::Text);
+        arg.value_delimiter(',');
+
+        assert_eq!(
+            arg.to_data(vec!["a,b\\,c".to_string(), "d".to_string()]),
+            Ok(Data::Texts(vec![
+                "a".to_string(),
+                "b,c".to_string(),
+                "d".to_string()
+            ]))
+        );
+
+        let mut chk_vec: Vec<u8> = vec![];
+        arg.help_name_msg(80, false, &mut chk_vec)?;
+        assert_eq!(
+            std::str::from_utf8(chk_vec.as_slice()).unwrap(),
+            "  --features [text,...] — Features\n"
+        );
+
+        Ok(())
+    }
+
+    /// Checks that the [arguments_msg] function collapses sections only past the
+    /// threshold and that [section_msg] shows a single section
+    #[test]
+    fn sections() -> std::io::Result<()> {
+        let mut chk_vec: Vec<u8> = vec![];
+
+        let verbose = Argument::new("Verbose mode", vec!['v'], vec![], Input::None);
+        let mut proxy = Argument::new("Proxy to use", vec!['p'], vec![], Input::Text);
+        proxy.section("network");
+        let mut port = Argument::new("Port to use", vec!['o'], vec![], Input::Text);
+        port.section("network");
+        let arguments = vec![&verbose, &proxy, &port];
+
+        arguments_msg(&arguments, 3, 80, false, false, &mut chk_vec)?;
+        assert_eq!(
+            std::str::from_utf8(chk_vec.as_slice()).unwrap(),
+            "  -v — Verbose mode\n  -p [text] — Proxy to use\n  -o [text] — Port to use\n"
+        );
+        chk_vec = vec![];
+
+        arguments_msg(&arguments, 2, 80, false, false, &mut chk_vec)?;
+        assert_eq!(
+            std::str::from_utf8(chk_vec.as_slice()).unwrap(),
+            "  -v — Verbose mode\n\nArgument sections (see --help <section>):\n  network — 2 arguments\n"
+        );
+        chk_vec = vec![];
+
+        section_msg(&arguments, "network", 80, false, &mut chk_vec)?;
+        assert_eq!(
+            std::str::from_utf8(chk_vec.as_slice()).unwrap(),
+            "  -p [text] — Proxy to use\n  -o [text] — Port to use\n"
+        );
+        chk_vec = vec![];
+
+        section_msg(&arguments, "storage", 80, false, &mut chk_vec)?;
+        assert_eq!(
+            std::str::from_utf8(chk_vec.as_slice()).unwrap(),
+            "  No arguments found in section 'storage'\n"
+        );
+
+        Ok(())
+    }
+
+    /// Checks that the [Argument::search] method matches help and long calls
+    #[test]
+    fn search() {
+        let arg = Argument::new("Use a Proxy server", vec!['p'], vec!["via"], Input::Text);
+
+        assert!(arg.search("proxy"));
+        assert!(arg.search("via"));
+        assert!(!arg.search("tcp"));
+        assert!(!Argument::new(None, vec![], vec![], Input::None).search("proxy"));
+    }
+
+    /// Checks that the [Argument::read_env] method reads and maps the variable
+    #[test]
+    fn read_env() {
+        let mut arg = Argument::new("Ratio", vec!['r'], vec!["ratio"], Input::Float);
+
+        assert_eq!(arg.read_env(), None);
+
+        arg.env("CLIMAKE_TEST_READ_ENV");
+        assert_eq!(arg.read_env(), None);
+
+        env::set_var("CLIMAKE_TEST_READ_ENV", "0.5");
+        assert_eq!(
+            arg.read_env(),
+            Some(("CLIMAKE_TEST_READ_ENV", Ok(Data::Float(0.5))))
+        );
+
+        env::set_var("CLIMAKE_TEST_READ_ENV", "half");
+        assert_eq!(
+            arg.read_env(),
+            Some(("CLIMAKE_TEST_READ_ENV", Err("half".to_string())))
+        );
+    }
+
+    /// Checks that the [Argument::help_name_msg] method shows the environment
+    /// variable set with [Argument::env]
+    #[test]
+    fn name_help_env() -> std::io::Result<()> {
+        let mut chk_vec: Vec<u8> = vec![];
+
+        Argument::new("Port to use", vec!['p'], vec![], Input::Text)
+            .env("PORT")
+            .help_name_msg(80, false, &mut chk_vec)?;
+        assert_eq!(
+            std::str::from_utf8(chk_vec.as_slice()).unwrap(),
+            "  -p [text] [env: PORT] — Port to use\n"
+        );
+
+        Ok(())
+    }
+
+    /// Checks that the [Argument::long_help_msg] method wraps each paragraph of
+    /// [Argument::long_help] on its own, tabbed in below the entry
+    #[test]
+    fn long_help() -> std::io::Result<()> {
+        let mut chk_vec: Vec<u8> = vec![];
+
+        Argument::new(
+            "Only show recent changes",
+            vec![],
+            vec!["since"],
+            Input::Text,
+        )
+        .long_help("Accepts dates such as 2024-05-01\nor relative times.\n\nDefaults to all.")
+        .long_help_msg(30, &mut chk_vec)?;
+        assert_eq!(
+            std::str::from_utf8(chk_vec.as_slice()).unwrap(),
+            "    Accepts dates such as\n    2024-05-01 or relative\n    times.\n\n    Defaults to all.\n"
+        );
+
+        chk_vec = vec![];
+        Argument::new(None, vec!['v'], vec![], Input::None).long_help_msg(30, &mut chk_vec)?;
+        assert!(chk_vec.is_empty());
+
+        chk_vec = vec![];
+        Argument::new(None, vec!['v'], vec![], Input::None)
+            .long_help("More output.")
+            .max_occurrences(4)
+            .long_help_msg(30, &mut chk_vec)?;
+        assert_eq!(
+            std::str::from_utf8(chk_vec.as_slice()).unwrap(),
+            "    More output.\n\n    May be given at most 4\n    times.\n"
+        );
+
+        Ok(())
+    }
+
+    /// Checks that the [Argument::display_call] method prefers long calls
+    #[test]
+    fn display_call() {
+        assert_eq!(
+            Argument::new(None, vec!['v'], vec!["verbose"], Input::None).display_call(),
+            "--verbose"
+        );
+        assert_eq!(
+            Argument::new(None, vec!['v'], vec![], Input::None).display_call(),
+            "-v"
+        );
+    }
+
+    /// Checks that the [Argument::to_data] method only accepts a decimal comma
+    /// when [Argument::decimal_comma] is set
+    #[test]
+    fn to_data_decimal_comma() {
+        let mut arg = Argument::new("Ratio", vec!['r'], vec![], Input::Float);
+
+        assert_eq!(arg.to_data(vec!["0,5".to_string()]), Err("0,5".to_string()));
+
+        arg.decimal_comma(true);
+        assert_eq!(arg.to_data(vec!["0,5".to_string()]), Ok(Data::Float(0.5)));
+        assert_eq!(arg.to_data(vec!["0.5".to_string()]), Ok(Data::Float(0.5)));
+        assert_eq!(
+            arg.to_data(vec!["1.000,5".to_string()]),
+            Err("1.000,5".to_string())
+        );
+    }
+
+    /// Checks that the [Argument::id] method is unique yet kept by clones
+    #[test]
+    fn id() {
+        let arg = Argument::new(None, vec!['a'], vec![], Input::None);
+        let other = Argument::new(None, vec!['a'], vec![], Input::None);
+
+        assert_eq!(arg.clone().id(), arg.id());
+        assert_ne!(other.id(), arg.id());
+    }
+
     /// Checks that the [Argument::add_scall] method works correctly
     #[test]
     fn add_scall() {
@@ -1,7 +1,11 @@
 //! Contains basic implementations for [CliMake]
 
-use super::CliMake;
-use crate::{Argument, Subcommand, CLI_TABBING};
+use super::{ArgumentOrder, BuildInfo, CliMake};
+use crate::core::utils::terminal_width;
+use crate::{
+    Argument, Subcommand, CLI_TABBING, ERROR_CODE, HELP_LINT_WIDTH, HELP_MAX_WIDTH, HELP_MIN_WIDTH,
+    HELP_TEMPLATE, MAX_DEPTH, SECTION_THRESHOLD,
+};
 
 impl<'a> CliMake<'a> {
     /// Creates a new [Argument] from given passed values
@@ -18,7 +22,41 @@ impl<'a> CliMake<'a> {
             subcommands: subcommands.into(),
             description: description.into(),
             version: version.into(),
+            long_version: None,
+            build_info: BuildInfo::default(),
             tabbing: CLI_TABBING,
+            debug: false,
+            section_threshold: SECTION_THRESHOLD,
+            examples: vec![],
+            help_template: HELP_TEMPLATE,
+            before_help: None,
+            after_help: None,
+            tips: vec![],
+            help_lint_width: HELP_LINT_WIDTH,
+            bubble_arguments: false,
+            max_depth: MAX_DEPTH,
+            argument_order: ArgumentOrder::default(),
+            reproducible: false,
+            max_width: HELP_MAX_WIDTH,
+            min_width: HELP_MIN_WIDTH,
+            term_width: None,
+            quiet_errors: false,
+            error_code: ERROR_CODE,
+            stop_at_positional: false,
+            lenient: false,
+            abbreviations: false,
+            abbreviate_subcommands: false,
+            case_insensitive: false,
+            slash_calls: false,
+            slash_help: false,
+            multicall: false,
+            disable_help_flag: false,
+            disable_version_flag: false,
+            disable_help_subcommand: false,
+            renames: vec![],
+            warn_renamed: false,
+            embedded: false,
+            record_overrides: false,
         }
     }
 
@@ -53,12 +91,403 @@ impl<'a> CliMake<'a> {
         self
     }
 
+    /// Adds a single example invocation to show in help, chainable
+    ///
+    /// Examples should be written in full including the executable, e.g.
+    /// `mytool add serde`, and can be checked against the cli in tests using
+    /// [testing::verify_examples](crate::testing::verify_examples) so they never
+    /// fall out of date.
+    pub fn add_example(&mut self, example: &'a str) -> &mut Self {
+        self.examples.push(example);
+        self
+    }
+
+    /// Sets the layout of help messages for this cli and every subcommand of it,
+    /// chainable
+    ///
+    /// The `template` is shown as-is with the `{before-help}`, `{usage}`,
+    /// `{about}`, `{arguments}`, `{subcommands}` and `{after-help}` placeholders
+    /// replaced by those parts of help, each starting on their own line. Examples
+    /// added with [CliMake::add_example] are shown as part of `{after-help}`,
+    /// before any [CliMake::after_help]. This defaults to every part in that
+    /// order, so templates may reorder or leave out parts rather than
+    /// reimplementing help.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use climake::prelude::*;
+    ///
+    /// let mut cli = CliMake::new("tool", vec![], vec![], "A tool", None);
+    /// cli.help_template("{usage}{subcommands}{arguments}\nMore at example.com\n");
+    /// ```
+    pub fn help_template(&mut self, template: &'a str) -> &mut Self {
+        self.help_template = template;
+        self
+    }
+
+    /// Sets free text shown before the usage line of help, chainable
+    ///
+    /// This is useful for prologues such as a banner or a notice. Paragraphs are
+    /// separated by blank lines and each is wrapped on its own. Subcommands have
+    /// their own [Subcommand::before_help] instead.
+    pub fn before_help(&mut self, text: &'a str) -> &mut Self {
+        self.before_help = Some(text);
+        self
+    }
+
+    /// Sets free text shown at the end of help after any examples, chainable
+    ///
+    /// This is useful for epilogues such as a "see also" section, links or
+    /// copyright. Paragraphs are separated by blank lines and each is wrapped on
+    /// its own. Subcommands have their own [Subcommand::after_help] instead.
+    pub fn after_help(&mut self, text: &'a str) -> &mut Self {
+        self.after_help = Some(text);
+        self
+    }
+
+    /// Adds many old spellings of long calls alongside the canonical call they
+    /// map to, e.g. `("colour", "color")`, which are accepted transparently at
+    /// every level of the cli, chainable
+    ///
+    /// This is useful for renamed calls or regional spellings without adding
+    /// aliases to each argument, and keeps old spellings out of help. Arguments
+    /// which have an old spelling as one of their own calls are still called by
+    /// it directly. See [CliMake::warn_renamed] to warn when they're used.
+    pub fn rename_map(
+        &mut self,
+        renames: impl IntoIterator<Item = (&'a str, &'a str)>,
+    ) -> &mut Self {
+        self.renames.extend(renames);
+        self
+    }
+
+    /// Sets if using an old spelling from [CliMake::rename_map] writes a
+    /// deprecation warning to stderr naming the canonical call, chainable
+    pub fn warn_renamed(&mut self, warn: bool) -> &mut Self {
+        self.warn_renamed = warn;
+        self
+    }
+
+    /// Sets if values which are ignored as a higher-precedence source overrode
+    /// them are recorded, chainable
+    ///
+    /// Currently this is an [Argument::env] variable being set whilst the
+    /// argument was also given by the user, with each being found in
+    /// [ParsedCli::overrides](crate::parsed::ParsedCli::overrides) once parsed.
+    /// This is off by default as the environment is only read for missing
+    /// arguments otherwise.
+    pub fn record_overrides(&mut self, record: bool) -> &mut Self {
+        self.record_overrides = record;
+        self
+    }
+
+    /// Sets if climake is embedded inside of a larger program such as a GUI,
+    /// language binding or daemon, so it never prints anything on its own,
+    /// chainable
+    ///
+    /// Warnings (see [CliMake::warn_renamed]) and debugging traces are dropped
+    /// instead of being written to stderr. This should be used alongside
+    /// [CliMake::outcome_custom] or the `try_parse` methods, which never exit
+    /// the process, rather than the `parse` methods which still exit on help
+    /// or errors.
+    pub fn embedded(&mut self, embedded: bool) -> &mut Self {
+        self.embedded = embedded;
+        self
+    }
+
     /// Sets the tabbing characters for cli help, the default for this is 2 spaces,
     /// i.e. `  `.
     pub fn tabbing(&mut self, tab_chars: &'static str) -> &mut Self {
         self.tabbing = tab_chars;
         self
     }
+
+    /// Sets debug mode, tracing each decision made whilst parsing (e.g. an input
+    /// being matched as a long call or subcommand) to stderr, chainable
+    ///
+    /// This is also enabled without recompiling if the `CLIMAKE_DEBUG` environment
+    /// variable is set to `1`, which is handy for diagnosing why an input wasn't
+    /// recognised.
+    pub fn debug(&mut self, debug: bool) -> &mut Self {
+        self.debug = debug;
+        self
+    }
+
+    /// Sets the amount of arguments the cli or a subcommand may have before
+    /// arguments in an [Argument::section] are collapsed in help, chainable
+    ///
+    /// Collapsed sections are listed with a hint to use `--help <section>`. This
+    /// defaults to 20 arguments.
+    pub fn section_threshold(&mut self, threshold: usize) -> &mut Self {
+        self.section_threshold = threshold;
+        self
+    }
+
+    /// Sets the maximum length of help messages before they're flagged by
+    /// [CliMake::validate], chainable. This defaults to 60 characters
+    pub fn help_lint_width(&mut self, width: usize) -> &mut Self {
+        self.help_lint_width = width;
+        self
+    }
+
+    /// Sets if arguments given after a subcommand which only exist for a parent
+    /// of it (e.g. `mypkg add --verbose` where `--verbose` is an argument of the
+    /// root cli) are passed up to the parent, chainable
+    ///
+    /// By default these are rejected with an error telling the user where the
+    /// argument belongs, as it's ambiguous which level they meant. If a subcommand
+    /// has an argument with the same call as a parent, the subcommand's is always
+    /// used instead. This may be overridden for specific subcommands using
+    /// [Subcommand::bubble_arguments].
+    pub fn bubble_arguments(&mut self, bubble: bool) -> &mut Self {
+        self.bubble_arguments = bubble;
+        self
+    }
+
+    /// Sets the maximum depth subcommands may be nested to whilst parsing,
+    /// chainable. This defaults to 64 levels
+    ///
+    /// Inputs nesting subcommands any deeper are rejected, bounding the work done
+    /// by programs which parse untrusted input.
+    pub fn max_depth(&mut self, depth: usize) -> &mut Self {
+        self.max_depth = depth;
+        self
+    }
+
+    /// Sets the order given to parsed arguments in [ParsedCli::arguments] and
+    /// [ParsedSubcommand::arguments], chainable. This defaults to
+    /// [ArgumentOrder::Given], the order the user gave them in
+    ///
+    /// [ParsedCli::arguments]: crate::parsed::ParsedCli::arguments
+    /// [ParsedSubcommand::arguments]: crate::parsed::ParsedSubcommand::arguments
+    pub fn argument_order(&mut self, order: ArgumentOrder) -> &mut Self {
+        self.argument_order = order;
+        self
+    }
+
+    /// Sets if generated help should be byte-stable across machines and build
+    /// paths, for documentation made in reproducible-build pipelines, chainable
+    ///
+    /// Once set, the cli's name is always used in usage lines instead of the name
+    /// of the executable, versions are omitted and help is always wrapped to
+    /// [CliMake::max_width] rather than the width of the terminal.
+    pub fn reproducible_docs(&mut self, reproducible: bool) -> &mut Self {
+        self.reproducible = reproducible;
+        self
+    }
+
+    /// Sets the maximum width help is wrapped to, chainable. This defaults to
+    /// 80 characters
+    ///
+    /// Help is wrapped to the width of the terminal when known (see
+    /// [CliMake::term_width]), clamped between this and [CliMake::min_width].
+    pub fn max_width(&mut self, width: usize) -> &mut Self {
+        self.max_width = width;
+        self
+    }
+
+    /// Sets the minimum width help is wrapped to, chainable. This defaults to 20
+    /// characters, which is also a hard minimum
+    ///
+    /// Help piped into narrow panes will instead wrap at this width, so it stays
+    /// readable rather than becoming a character per line. This takes priority
+    /// over [CliMake::max_width] if they overlap.
+    pub fn min_width(&mut self, width: usize) -> &mut Self {
+        self.min_width = width;
+        self
+    }
+
+    /// Sets the width of the terminal manually instead of detecting it, which
+    /// is mainly useful for tests, chainable
+    ///
+    /// The terminal width is otherwise detected from the `COLUMNS` environment
+    /// variable, falling back to querying the platform if the `terminal-size`
+    /// feature is enabled. Either way, it's still clamped between
+    /// [CliMake::min_width] and [CliMake::max_width] and isn't used for
+    /// [CliMake::reproducible_docs].
+    pub fn term_width(&mut self, width: usize) -> &mut Self {
+        self.term_width = Some(width);
+        self
+    }
+
+    /// Sets if errors shown by [CliMake::parse] are reduced to a single line
+    /// without the usage of the cli, chainable
+    ///
+    /// This is meant for scripted invocations which capture stderr into logs,
+    /// and may be tied to a `--quiet` argument by finding it beforehand with
+    /// [CliMake::preparse]. Help is still shown in full when requested.
+    pub fn quiet_errors(&mut self, quiet: bool) -> &mut Self {
+        self.quiet_errors = quiet;
+        self
+    }
+
+    /// Sets the exit code used when [CliMake::parse] fails due to bad user input,
+    /// which is `2` by default as is conventional for usage errors, chainable
+    pub fn error_code(&mut self, code: i32) -> &mut Self {
+        self.error_code = code;
+        self
+    }
+
+    /// Sets if parsing stops at the first positional input which isn't a
+    /// subcommand, as it always does at a `--` terminator, chainable
+    ///
+    /// Every input from there onwards is left untouched in
+    /// [ParsedCli::trailing](crate::parsed::ParsedCli::trailing), which allows
+    /// wrapper programs such as `mytool run cargo build --release` to forward
+    /// arguments on without them being parsed. The `--` itself isn't kept.
+    pub fn stop_at_positional(&mut self, stop: bool) -> &mut Self {
+        self.stop_at_positional = stop;
+        self
+    }
+
+    /// Sets if unrecognised calls and stray positional inputs are collected into
+    /// [ParsedCli::unknown](crate::parsed::ParsedCli::unknown) rather than
+    /// erroring, chainable
+    ///
+    /// This is useful for plugin-style clis which forward unrecognised options on
+    /// to a child process. As it's unknown if an unrecognised call takes a value,
+    /// any value given after it as the next input is collected separately.
+    pub fn lenient(&mut self, lenient: bool) -> &mut Self {
+        self.lenient = lenient;
+        self
+    }
+
+    /// Sets if unambiguous prefixes of long calls are accepted in place of the
+    /// full call (e.g. `--verb` for `--verbose`), as many gnu-style tools do,
+    /// chainable
+    ///
+    /// Prefixes are matched against the arguments of the cli or subcommand they
+    /// were given at, with prefixes matching calls of more than one argument
+    /// being rejected alongside a list of the calls they could've meant. Calls
+    /// which match exactly are never treated as prefixes.
+    pub fn abbreviations(&mut self, abbreviations: bool) -> &mut Self {
+        self.abbreviations = abbreviations;
+        self
+    }
+
+    /// Sets if unambiguous prefixes of subcommand names are accepted in place of
+    /// the full name, chainable
+    ///
+    /// This gives power users of deep clis a faster way of invoking them without
+    /// defining aliases, e.g. `mytool p a` for `mytool post add`. Each prefix is
+    /// matched against the subcommands at its own level, with prefixes matching
+    /// more than one subcommand being rejected alongside a list of the names
+    /// they could've meant. Names which match exactly are never treated as
+    /// prefixes, and inputs taken as values of arguments are never expanded.
+    pub fn abbreviate_subcommands(&mut self, abbreviate: bool) -> &mut Self {
+        self.abbreviate_subcommands = abbreviate;
+        self
+    }
+
+    /// Sets if long calls and subcommand names are matched ignoring case (e.g.
+    /// `--Verbose` for `--verbose`), as expected by users coming from windows
+    /// shells such as powershell, chainable
+    ///
+    /// Exact matches are always preferred, so calls differing only by case still
+    /// work as defined. Short calls are never matched ignoring case, as `-v` and
+    /// `-V` commonly mean different things.
+    pub fn case_insensitive(&mut self, case_insensitive: bool) -> &mut Self {
+        self.case_insensitive = case_insensitive;
+        self
+    }
+
+    /// Sets if windows style calls are accepted as alternate spellings of short
+    /// and long calls, e.g. `/v` for `-v` or `/output:file` for `--output=file`,
+    /// chainable
+    ///
+    /// As absolute paths also start with `/`, these are only taken as calls when
+    /// they match an argument (or `/?` and `/help` for help), and never after a
+    /// `--` terminator. See [CliMake::slash_help] to show this style in help.
+    pub fn slash_calls(&mut self, slash_calls: bool) -> &mut Self {
+        self.slash_calls = slash_calls;
+        self
+    }
+
+    /// Sets if calls are shown in help in the windows style accepted by
+    /// [CliMake::slash_calls], e.g. `(/v, /verbose)`, chainable
+    pub fn slash_help(&mut self, slash_help: bool) -> &mut Self {
+        self.slash_help = slash_help;
+        self
+    }
+
+    /// Sets if the name the executable was invoked as selects a subcommand of the
+    /// same name, allowing one binary to provide many tools busybox-style,
+    /// chainable
+    ///
+    /// For example, a binary with `gzip` and `gunzip` subcommands symlinked as
+    /// `gunzip` parses `gunzip file.gz` as `mytool gunzip file.gz`, with help
+    /// showing `gunzip` as the executable. Invoked names which don't match a
+    /// subcommand are parsed as usual, so the binary can still be used directly.
+    /// Arguments of the cli itself may be given to a selected subcommand as if
+    /// [CliMake::bubble_arguments] were set, as they can't be given before it.
+    pub fn multicall(&mut self, multicall: bool) -> &mut Self {
+        self.multicall = multicall;
+        self
+    }
+
+    /// Sets if the automatic `-h`, `--help` and `--help-search` calls are
+    /// disabled, so they may be used by arguments or passed through, chainable
+    ///
+    /// This may be overridden for specific subcommands using
+    /// [Subcommand::disable_help_flag], e.g. for a pass-through `exec`. Once
+    /// disabled these are treated like any other unknown call, meaning they're
+    /// collected into [ParsedCli::unknown](crate::parsed::ParsedCli::unknown)
+    /// when [CliMake::lenient] is set. Subcommands set with
+    /// [Subcommand::help_if_empty] still show their help when given nothing.
+    pub fn disable_help_flag(&mut self, disable: bool) -> &mut Self {
+        self.disable_help_flag = disable;
+        self
+    }
+
+    /// Sets if the automatic `help` subcommand is disabled, chainable
+    ///
+    /// This subcommand is available wherever subcommands are, so that
+    /// `mytool help add img` shows the help of the nested `img` subcommand
+    /// like cargo or git. Subcommands named `help` are always matched first,
+    /// and it's also unavailable when help calls are disabled using
+    /// [CliMake::disable_help_flag] or [Subcommand::disable_help_flag].
+    pub fn disable_help_subcommand(&mut self, disable: bool) -> &mut Self {
+        self.disable_help_subcommand = disable;
+        self
+    }
+
+    /// Sets a longer, possibly multi-line version shown instead of the usual
+    /// version for `--version`, chainable
+    ///
+    /// This is typically used for details such as the commit hash and build
+    /// date, e.g. `"1.2.0\ncommit 3fb2eec\nbuilt 2024-05-01"`. The short `-V`
+    /// call always shows the usual version.
+    pub fn long_version(&mut self, long_version: impl Into<Option<&'a str>>) -> &mut Self {
+        self.long_version = long_version.into();
+        self
+    }
+
+    /// Sets if the automatic `-V` and `--version` calls are disabled, chainable
+    ///
+    /// These are only available when a version was given for the cli (or for
+    /// the subcommand being parsed), printing `name version` and exiting.
+    /// Arguments using these calls at the level being parsed are always matched
+    /// first, as are those of parents which may be given there (see
+    /// [CliMake::bubble_arguments] and [Argument::global]), so this is only
+    /// needed to treat them like any other unknown call.
+    pub fn disable_version_flag(&mut self, disable: bool) -> &mut Self {
+        self.disable_version_flag = disable;
+        self
+    }
+
+    /// Gets the width help should be wrapped to, from the terminal width if
+    /// known (and not [CliMake::reproducible_docs]) and clamped between
+    /// [CliMake::min_width] and [CliMake::max_width]
+    pub(crate) fn width(&self) -> usize {
+        Some(self.term_width)
+            .filter(|_| !self.reproducible)
+            .and_then(|term_width| term_width.or_else(terminal_width))
+            .unwrap_or(self.max_width)
+            .min(self.max_width)
+            .max(self.min_width)
+            .max(HELP_MIN_WIDTH)
+    }
 }
 
 #[cfg(test)]
@@ -88,6 +517,47 @@ mod tests {
         assert_eq!(cli.arguments, vec![&arg, &arg, &arg, &arg])
     }
 
+    /// Checks that the [CliMake::add_example] method works correctly
+    #[test]
+    fn cli_add_example() {
+        let mut cli = CliMake::new("example", vec![], vec![], "Add example check", None);
+
+        cli.add_example("example one").add_example("example two");
+
+        assert_eq!(cli.examples, vec!["example one", "example two"])
+    }
+
+    /// Checks that [CliMake::width] is clamped, even at pathological widths
+    #[test]
+    fn cli_width() {
+        let mut cli = CliMake::new("example", vec![], vec![], "Width check", None);
+
+        cli.min_width(50).max_width(50);
+        assert_eq!(cli.width(), 50);
+
+        cli.min_width(0).max_width(0);
+        assert_eq!(cli.width(), HELP_MIN_WIDTH);
+
+        cli.min_width(100).max_width(40);
+        assert_eq!(cli.width(), 100);
+    }
+
+    /// Checks that [CliMake::term_width] overrides the detected terminal width,
+    /// whilst still being clamped and ignored for reproducible docs
+    #[test]
+    fn cli_term_width() {
+        let mut cli = CliMake::new("example", vec![], vec![], "Width check", None);
+
+        cli.term_width(60);
+        assert_eq!(cli.width(), 60);
+
+        cli.term_width(200);
+        assert_eq!(cli.width(), HELP_MAX_WIDTH);
+
+        cli.term_width(60).reproducible_docs(true);
+        assert_eq!(cli.width(), HELP_MAX_WIDTH);
+    }
+
     /// Checks that the [CliMake::add_subcmds] method works correctly
     #[test]
     fn cli_add_subcmds() {
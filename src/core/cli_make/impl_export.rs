@@ -0,0 +1,277 @@
+//! Contains exporting implementations for [CliMake], generating files or snippets
+//! from the cli definition for use elsewhere
+
+use super::CliMake;
+use crate::core::utils::{fnv1a, shell_quote};
+use crate::{Argument, Subcommand};
+
+/// Writes a canonical description of everything about an `argument` which
+/// affects parsing or help to `definition`, see [CliMake::fingerprint]
+fn describe_argument(argument: &Argument, definition: &mut String) {
+    definition.push_str(&format!(
+        "argument{:?}{:?}\n",
+        (
+            argument.help,
+            argument.long_help,
+            &argument.shorts,
+            &argument.longs,
+            &argument.input,
+            argument.required,
+            argument.require_equals,
+            argument.optional_value,
+            argument.decimal_comma,
+            argument.allow_hyphen_values,
+            argument.greedy,
+            argument.value_delimiter,
+        ),
+        (
+            argument.global,
+            argument.arity,
+            argument.env,
+            argument.section,
+            argument.negatable,
+            argument.repeats,
+            argument.max_occurrences,
+            argument.unique,
+        )
+    ));
+}
+
+/// Writes a canonical description of a `subcommand` and everything below it to
+/// `definition`, see [CliMake::fingerprint]
+fn describe_subcommand(subcommand: &Subcommand, definition: &mut String) {
+    definition.push_str(&format!(
+        "subcommand{:?}[\n",
+        (
+            subcommand.name,
+            subcommand.help,
+            subcommand.long_help,
+            subcommand.before_help,
+            subcommand.after_help,
+            subcommand.version,
+            subcommand.help_if_empty,
+            subcommand.bubble_arguments,
+            subcommand.disable_help_flag,
+            subcommand.disable_version_flag,
+        )
+    ));
+
+    for argument in subcommand.arguments.iter() {
+        describe_argument(argument, definition);
+    }
+    for inner in subcommand.subcommands.iter() {
+        describe_subcommand(inner, definition);
+    }
+
+    definition.push_str("]\n");
+}
+
+impl<'a> CliMake<'a> {
+    /// Computes a fingerprint of this cli's full definition, i.e. the names,
+    /// help, calls, inputs and requirements of the cli and every argument and
+    /// subcommand throughout it, alongside the settings changing which inputs
+    /// they accept or how their help is laid out
+    ///
+    /// This allows applications to cache generated artifacts such as
+    /// completions, man pages or schemas and only regenerate them once the cli
+    /// actually changed. The fingerprint is the same on every platform and run,
+    /// and isn't affected by settings which don't change the definition itself
+    /// (such as [CliMake::debug]) or by closures such as validators, which can't
+    /// be compared.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use climake::prelude::*;
+    ///
+    /// let verbose = Argument::new("Verbose mode", vec!['v'], vec![], Input::None);
+    /// let before = CliMake::new("tool", vec![&verbose], vec![], None, None);
+    /// let mut after = before.clone();
+    ///
+    /// assert_eq!(before.fingerprint(), after.fingerprint());
+    ///
+    /// after.add_example("tool -v");
+    /// assert_ne!(before.fingerprint(), after.fingerprint());
+    /// ```
+    pub fn fingerprint(&self) -> u64 {
+        let mut definition = format!(
+            "cli{:?}{:?}{:?}[\n",
+            (
+                self.name,
+                self.description,
+                self.version,
+                self.long_version,
+                &self.examples,
+                self.before_help,
+                self.after_help,
+                self.help_template,
+                self.section_threshold,
+            ),
+            (
+                self.disable_help_flag,
+                self.disable_version_flag,
+                self.disable_help_subcommand,
+                self.bubble_arguments,
+                self.lenient,
+                self.abbreviations,
+                self.abbreviate_subcommands,
+            ),
+            (
+                self.case_insensitive,
+                self.slash_calls,
+                self.slash_help,
+                self.stop_at_positional,
+                self.multicall,
+                self.max_depth,
+                &self.renames,
+            )
+        );
+
+        for argument in self.arguments.iter() {
+            describe_argument(argument, &mut definition);
+        }
+        for subcommand in self.subcommands.iter() {
+            describe_subcommand(subcommand, &mut definition);
+        }
+
+        definition.push_str("]\n");
+        fnv1a(definition.as_bytes())
+    }
+
+    /// Generates a shell function wrapping this cli's executable which always
+    /// passes the given `defaults`, for teams standardizing how a tool is invoked
+    ///
+    /// Each default is an [Argument] of this cli alongside the value to pass to
+    /// it, if any. Long calls are preferred over short calls and values are quoted
+    /// so they're passed through as-is. The generated function works in any posix
+    /// shell, i.e. `sh`, `bash` or `zsh`. Names which can't be used for a shell
+    /// function, such as `my-tool`, are wrapped using an alias instead.
+    ///
+    /// # Example
+    ///
+    /// What this may look like:
+    ///
+    /// ```none
+    /// mytool() { command mytool --color=always "$@"; }
+    /// alias my-tool='command my-tool --color=always'
+    /// ```
+    pub fn shell_wrapper(&self, defaults: &[(&Argument<'a>, Option<&str>)]) -> String {
+        let mut invocation = format!("command {}", shell_quote(self.name));
+
+        for (argument, value) in defaults.iter() {
+            let call = argument.display_call();
+
+            match value {
+                Some(value) if call.starts_with("--") => {
+                    invocation.push_str(&format!(" {}={}", call, shell_quote(value)))
+                }
+                Some(value) => invocation.push_str(&format!(" {} {}", call, shell_quote(value))),
+                None => invocation.push_str(&format!(" {}", call)),
+            }
+        }
+
+        // posix only allows function names made of letters, digits and `_`
+        let is_identifier = !self.name.starts_with(|c: char| c.is_ascii_digit())
+            && !self.name.is_empty()
+            && self
+                .name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '_');
+
+        if is_identifier {
+            format!("{}() {{ {} \"$@\"; }}\n", self.name, invocation)
+        } else {
+            format!(
+                "alias {}={}\n",
+                shell_quote(self.name),
+                shell_quote(&invocation)
+            )
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::io::Input;
+
+    /// Checks that [CliMake::fingerprint] only changes along with the definition
+    #[test]
+    fn fingerprint() {
+        let verbose = Argument::new("Verbose mode", vec!['v'], vec![], Input::None);
+        let mut required = verbose.clone();
+        required.required(true);
+        let add = Subcommand::new("add", vec![&verbose], vec![], None);
+        let remove = Subcommand::new("remove", vec![&verbose], vec![], None);
+        let cli = CliMake::new("mytool", vec![&verbose], vec![&add], None, None);
+        let fingerprint = cli.fingerprint();
+
+        assert_eq!(
+            CliMake::new("mytool", vec![&verbose], vec![&add], None, None).fingerprint(),
+            fingerprint
+        );
+        assert_ne!(
+            CliMake::new("mytool", vec![&required], vec![&add], None, None).fingerprint(),
+            fingerprint
+        );
+        assert_ne!(
+            CliMake::new("mytool", vec![&verbose], vec![&remove], None, None).fingerprint(),
+            fingerprint
+        );
+        assert_ne!(
+            CliMake::new("mytool", vec![], vec![&add], None, None).fingerprint(),
+            fingerprint
+        );
+
+        let changed = vec![
+            cli.clone().lenient(true).clone(),
+            cli.clone().case_insensitive(true).clone(),
+            cli.clone().slash_calls(true).clone(),
+            cli.clone().slash_help(true).clone(),
+            cli.clone().stop_at_positional(true).clone(),
+            cli.clone().multicall(true).clone(),
+            cli.clone().rename_map(vec![("old", "verbose")]).clone(),
+            cli.clone().max_depth(1).clone(),
+            cli.clone().help_template("{usage}{arguments}").clone(),
+            cli.clone().section_threshold(1).clone(),
+        ];
+        for changed in changed {
+            assert_ne!(changed.fingerprint(), fingerprint);
+        }
+
+        let mut bubbling = add.clone();
+        bubbling.bubble_arguments(true);
+        assert_ne!(
+            CliMake::new("mytool", vec![&verbose], vec![&bubbling], None, None).fingerprint(),
+            fingerprint
+        );
+    }
+
+    /// Checks that the [CliMake::shell_wrapper] method works correctly
+    #[test]
+    fn shell_wrapper() {
+        let color = Argument::new("Output color", vec!['c'], vec!["color"], Input::Text);
+        let quiet = Argument::new("Quiet mode", vec!['q'], vec![], Input::None);
+        let config = Argument::new("Config file", vec!['f'], vec![], Input::Path);
+        let cli = CliMake::new("mytool", vec![&color, &quiet, &config], vec![], None, None);
+
+        assert_eq!(
+            cli.shell_wrapper(&[]),
+            "mytool() { command mytool \"$@\"; }\n"
+        );
+        assert_eq!(
+            cli.shell_wrapper(&[(&color, Some("always")), (&quiet, None)]),
+            "mytool() { command mytool --color=always -q \"$@\"; }\n"
+        );
+        assert_eq!(
+            cli.shell_wrapper(&[(&config, Some("my file's.toml"))]),
+            "mytool() { command mytool -f 'my file'\\''s.toml' \"$@\"; }\n"
+        );
+
+        let cli = CliMake::new("my-tool", vec![&color], vec![], None, None);
+        assert_eq!(
+            cli.shell_wrapper(&[(&color, Some("always"))]),
+            "alias my-tool='command my-tool --color=always'\n"
+        );
+    }
+}
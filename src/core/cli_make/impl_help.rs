@@ -1,10 +1,192 @@
 //! Contains help implementations for [CliMake]
 
 use super::CliMake;
-use crate::core::utils::writeln_term;
+use crate::core::argument::{arguments_msg, section_msg};
+use crate::core::render::{HelpRender, RenderOptions};
+use crate::core::utils::{to_plain, writeln_term};
+use crate::messages::{message, Key};
+use crate::{Argument, Subcommand};
 
-use std::io::Write;
 use std::env;
+use std::io::Write;
+
+/// Recurses through a level of the cli, writing any [Argument]s or [Subcommand]s
+/// which match the lowercase `query` under the given `path` as a heading, wrapped
+/// to `width`. This returns if anything was found in this level or any below it
+fn search_level(
+    path: String,
+    arguments: &[&Argument],
+    subcommands: &[&Subcommand],
+    query: &str,
+    width: usize,
+    slash: bool,
+    buf: &mut impl Write,
+) -> std::io::Result<bool> {
+    let found_arguments: Vec<&&Argument> = arguments.iter().filter(|a| a.search(query)).collect();
+    let found_subcommands: Vec<&&Subcommand> =
+        subcommands.iter().filter(|s| s.search(query)).collect();
+    let found = !found_arguments.is_empty() || !found_subcommands.is_empty();
+
+    if found {
+        buf.write_all(format!("\n{}:\n", path).as_bytes())?;
+
+        for argument in found_arguments {
+            argument.help_name_msg(width, slash, buf)?;
+        }
+
+        for subcommand in found_subcommands {
+            subcommand.help_name_msg(width, buf)?;
+        }
+    }
+
+    let mut found_below = false;
+
+    for subcommand in subcommands.iter().filter(|s| s.is_available()) {
+        found_below |= search_level(
+            format!("{} {}", path, subcommand.name),
+            &subcommand.arguments,
+            &subcommand.subcommands,
+            query,
+            width,
+            slash,
+            buf,
+        )?;
+    }
+
+    Ok(found || found_below)
+}
+
+/// Writes free text such as [CliMake::before_help], with each paragraph tabbed
+/// in and wrapped to `width` on its own
+fn text_msg(text: &str, width: usize, buf: &mut impl Write) -> std::io::Result<()> {
+    for (ind, paragraph) in text.split("\n\n").enumerate() {
+        if ind != 0 {
+            buf.write_all("\n".as_bytes())?;
+        }
+
+        writeln_term(
+            paragraph
+                .split_whitespace()
+                .collect::<Vec<&str>>()
+                .join(" "),
+            width,
+            buf,
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Renders help for a level of the cli, i.e. the root cli or a single
+/// subcommand, laid out using the [RenderOptions::help_template]
+///
+/// This is the single place sections are rendered, shared by [CliMake::help_msg]
+/// and [Subcommand::help_msg] so help for either never diverges. Any new
+/// sections should be added here. The `{usage}` and `{about}` placeholders
+/// differ between the two so are rendered by the `header` closure, which
+/// returns if it knew the placeholder. Unknown placeholders are left as-is.
+/// The level's `before_help` and `after_help` text are shown for the
+/// `{before-help}` and `{after-help}` placeholders.
+///
+/// # Example
+///
+/// What the sections may look like:
+///
+/// ```none
+/// Arguments:
+///   (-v, --verbose) — Verbose mode
+///
+/// Subcommands:
+///   add — Adds a package
+/// ```
+pub(crate) fn render_sections(
+    arguments: &[&Argument],
+    subcommands: &[&Subcommand],
+    options: &RenderOptions,
+    before_help: Option<&str>,
+    after_help: Option<&str>,
+    mut header: impl FnMut(&str, &mut dyn Write) -> std::io::Result<bool>,
+    buf: &mut impl Write,
+) -> std::io::Result<()> {
+    let arguments: Vec<&Argument> = arguments
+        .iter()
+        .copied()
+        .filter(|argument| argument.is_available())
+        .collect();
+    let subcommands: Vec<&Subcommand> = subcommands
+        .iter()
+        .copied()
+        .filter(|subcommand| subcommand.is_available())
+        .collect();
+    let mut template = options.help_template;
+
+    while let Some(start) = template.find('{') {
+        buf.write_all(&template.as_bytes()[..start])?;
+        template = &template[start..];
+
+        let end = match template.find('}') {
+            Some(end) => end,
+            None => break,
+        };
+        let known = match &template[1..end] {
+            "arguments" => {
+                buf.write_all(format!("\n{}\n", message(Key::ArgumentsHeading, &[])).as_bytes())?;
+                arguments_msg(
+                    &arguments,
+                    options.section_threshold,
+                    options.width,
+                    options.slash_calls,
+                    options.long_help,
+                    buf,
+                )?;
+                true
+            }
+            "subcommands" => {
+                buf.write_all(format!("\n{}\n", message(Key::SubcommandsHeading, &[])).as_bytes())?;
+
+                if subcommands.is_empty() {
+                    writeln_term(message(Key::NoSubcommands, &[]), options.width, buf)?;
+                }
+
+                for subcommand in subcommands.iter() {
+                    subcommand.help_name_msg(options.width, buf)?;
+                }
+                true
+            }
+            "before-help" => {
+                if let Some(text) = before_help {
+                    text_msg(text, options.width, buf)?;
+                    buf.write_all("\n".as_bytes())?;
+                }
+                true
+            }
+            "after-help" => {
+                if !options.examples.is_empty() {
+                    buf.write_all(
+                        format!("\n{}\n", message(Key::ExamplesHeading, &[])).as_bytes(),
+                    )?;
+
+                    for example in options.examples.iter() {
+                        writeln_term(*example, options.width, buf)?;
+                    }
+                }
+                if let Some(text) = after_help {
+                    buf.write_all("\n".as_bytes())?;
+                    text_msg(text, options.width, buf)?;
+                }
+                true
+            }
+            placeholder => header(placeholder, &mut *buf)?,
+        };
+
+        if !known {
+            buf.write_all(&template.as_bytes()[..=end])?;
+        }
+        template = &template[end + 1..];
+    }
+
+    buf.write_all(template.as_bytes())
+}
 
 impl<'a> CliMake<'a> {
     /// Generates header and streams to given [Write] buffer for displaying info
@@ -30,25 +212,41 @@ impl<'a> CliMake<'a> {
         usage_suffix: impl Into<Option<&'a str>>,
         buf: &mut impl Write,
     ) -> std::io::Result<()> {
-        let cur_exe = env::current_exe().unwrap(); // TODO: better errors
-        let cur_stem = cur_exe.file_stem().unwrap().to_str().unwrap(); // TOOD: better errors
+        self.usage_msg(usage_suffix, buf)?;
+        self.about_msg(buf)
+    }
 
-        match usage_suffix.into() {
-            Some(suffix) => {
-                buf.write_fmt(format_args!("Usage: ./{} {} [OPTIONS]\n", cur_stem, suffix))?
-            }
-            None => buf.write_fmt(format_args!("Usage: ./{} [OPTIONS]\n", cur_stem))?,
-        }
+    /// Generates the usage line of the header, see [CliMake::header_msg]
+    pub(crate) fn usage_msg(
+        &self,
+        usage_suffix: impl Into<Option<&'a str>>,
+        buf: &mut impl Write,
+    ) -> std::io::Result<()> {
+        let usage_name = self.usage_name();
+        let usage_suffix = usage_suffix
+            .into()
+            .filter(|suffix| !self.multicall || *suffix != usage_name);
+
+        let usage = match usage_suffix {
+            Some(suffix) => message(Key::SubcommandUsage, &[&usage_name, &suffix]),
+            None => message(Key::Usage, &[&usage_name]),
+        };
+        buf.write_all(format!("{}\n", usage).as_bytes())
+    }
 
-        match self.description.clone() {
+    /// Generates the description of this cli below the usage line of the
+    /// header, if it has one, see [CliMake::header_msg]
+    pub(crate) fn about_msg(&self, buf: &mut impl Write) -> std::io::Result<()> {
+        match self.description {
             Some(d) => {
-                buf.write("\n".as_bytes())?; // write formatting empty byte
+                buf.write_all("\n".as_bytes())?; // write formatting empty byte
 
                 writeln_term(
-                    match &self.version {
+                    match self.version.filter(|_| !self.reproducible) {
                         Some(v) => format!("{} v{} — {}", self.name, v, d),
                         None => format!("{} — {}", self.name, d),
                     },
+                    self.width(),
                     buf,
                 )
             }
@@ -56,6 +254,29 @@ impl<'a> CliMake<'a> {
         }
     }
 
+    /// Gets the name of the executable shown in usage lines, which is the cli's
+    /// name if [CliMake::reproducible_docs] is set or the executable can't be
+    /// found. Subcommands invoked by name when [CliMake::multicall] is set are
+    /// used instead of the executable, as symlinks to it have their own names
+    fn usage_name(&self) -> String {
+        if self.multicall && !self.reproducible {
+            let applet = env::args_os()
+                .next()
+                .and_then(|invoked| self.multicall_subcommand(invoked));
+
+            if let Some(subcommand) = applet {
+                return subcommand.name.to_string();
+            }
+        }
+
+        let cur_exe = env::current_exe().ok().filter(|_| !self.reproducible);
+
+        match cur_exe.as_ref().and_then(|exe| exe.file_stem()) {
+            Some(stem) => stem.to_string_lossy().to_string(),
+            None => self.name.to_string(),
+        }
+    }
+
     /// Displays help infomation for climake which is used inside the execution
     /// of the cli
     ///
@@ -80,28 +301,285 @@ impl<'a> CliMake<'a> {
     ///   (-v, --verbose) — Verbose mode
     /// ```
     pub(crate) fn help_msg(&self, buf: &mut impl Write) -> std::io::Result<()> {
-        self.header_msg(None, buf)?;
-
-        buf.write("\nArguments:\n".as_bytes())?;
+        self.render_full(&self.render_options(), buf)
+    }
 
-        if self.arguments.len() > 0 {
-            for argument in self.arguments.iter() {
-                argument.help_name_msg(buf)?;
-            }
-        } else {
-            buf.write("  No arguments found\n".as_bytes())?;
+    /// Gets the [RenderOptions] for help set by this cli, including its examples,
+    /// for use with [HelpRender](crate::HelpRender)
+    pub fn render_options(&self) -> RenderOptions<'_> {
+        RenderOptions {
+            section_threshold: self.section_threshold,
+            width: self.width(),
+            examples: &self.examples,
+            reproducible: self.reproducible,
+            slash_calls: self.slash_help,
+            long_help: false,
+            help_template: self.help_template,
         }
+    }
 
-        buf.write("\nSubcommands:\n".as_bytes())?;
+    /// Renders the full help message shown for `--help` as plain ascii,
+    /// guaranteed to contain no escape sequences or unicode (em dashes become
+    /// `-`, for example) whatever else this cli has been set to do
+    ///
+    /// This is useful for applications writing help into log files or crash
+    /// reports whilst still showing the usual help on interactive terminals.
+    ///
+    /// # Example
+    ///
+    /// What this may look like:
+    ///
+    /// ```none
+    /// Usage: ./my-app [OPTIONS]
+    ///
+    ///   My app v0.1.0 - A simple application
+    ///
+    /// Arguments:
+    ///   (-v, --verbose) - Verbose mode
+    /// ```
+    pub fn render_plain(&self) -> String {
+        let mut rendered: Vec<u8> = vec![];
+        self.help_msg(&mut rendered).unwrap(); // writing to a vec can't fail
 
-        if self.subcommands.len() > 0 {
-            for subcommand in self.subcommands.iter() {
-                subcommand.help_name_msg(buf)?;
-            }
-        } else {
-            buf.write("  No subcommands found\n".as_bytes())?;
+        to_plain(&String::from_utf8_lossy(&rendered))
+    }
+
+    /// Displays help infomation for only the arguments in a single `section`, for
+    /// either the root cli or a given `subcommand`. This is used for the
+    /// `--help <section>` form of help, see [Argument::section]
+    pub(crate) fn help_section_msg(
+        &self,
+        subcommand: Option<&Subcommand<'a>>,
+        section: &str,
+        buf: &mut impl Write,
+    ) -> std::io::Result<()> {
+        self.header_msg(subcommand.map(|subcommand| subcommand.name), buf)?;
+
+        buf.write_all(format!("\n{}\n", message(Key::SectionHeading, &[&section])).as_bytes())?;
+        section_msg(
+            match subcommand {
+                Some(subcommand) => &subcommand.arguments,
+                None => &self.arguments,
+            },
+            section,
+            self.width(),
+            self.slash_help,
+            buf,
+        )
+    }
+
+    /// Searches the help of all arguments and subcommands throughout this cli
+    /// for the given `query`, displaying any matches underneath the command path
+    /// they're found in. This is used for `--help-search` so users of large
+    /// clis can find what they're looking for
+    ///
+    /// # Example
+    ///
+    /// What this may look like:
+    ///
+    /// ```none
+    /// Results for 'proxy':
+    ///
+    /// MyApp net:
+    ///   (-p, --proxy) [text] — Proxy server to connect through
+    /// ```
+    pub(crate) fn help_search_msg(
+        &self,
+        query: impl AsRef<str>,
+        buf: &mut impl Write,
+    ) -> std::io::Result<()> {
+        let heading = message(Key::SearchResults, &[&query.as_ref()]);
+        buf.write_all(format!("{}\n", heading).as_bytes())?;
+
+        let found = search_level(
+            self.name.to_string(),
+            &self.arguments,
+            &self.subcommands,
+            &query.as_ref().to_lowercase(),
+            self.width(),
+            self.slash_help,
+            buf,
+        )?;
+
+        if !found {
+            writeln_term(message(Key::NoResults, &[]), self.width(), buf)?;
         }
 
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::io::Input;
+
+    /// Checks that the [CliMake::render_plain] method only renders plain ascii
+    #[test]
+    fn help_render_plain() {
+        let verbose = Argument::new("Verbose “mode”", vec!['v'], vec![], Input::None);
+        let cli = CliMake::new("MyApp", vec![&verbose], vec![], "Plain check", "1.0");
+        let rendered = cli.render_plain();
+
+        assert!(rendered.is_ascii());
+        assert!(rendered.contains("\n  MyApp v1.0 - Plain check\n"));
+        assert!(rendered.contains("\n  -v - Verbose \"mode\"\n"));
+    }
+
+    /// Checks that [render_sections] renders help the same way for the root cli
+    /// and subcommands, only showing examples for the root
+    #[test]
+    fn help_sections_shared() -> std::io::Result<()> {
+        let (mut root_vec, mut subcommand_vec): (Vec<u8>, Vec<u8>) = (vec![], vec![]);
+
+        let verbose = Argument::new("Verbose mode", vec!['v'], vec![], Input::None);
+        let inner = Subcommand::new("inner", vec![], vec![], "Inner subcommand");
+        let add = Subcommand::new("add", vec![&verbose], vec![&inner], "Adds things");
+        let mut cli = CliMake::new("MyApp", vec![&verbose], vec![&inner], "Shared check", None);
+        cli.add_example("myapp -v");
+
+        cli.help_msg(&mut root_vec)?;
+        add.help_msg(&cli, false, &mut subcommand_vec)?;
+
+        let root = String::from_utf8(root_vec).unwrap();
+        let subcommand = String::from_utf8(subcommand_vec).unwrap();
+        let sections =
+            "\nArguments:\n  -v — Verbose mode\n\nSubcommands:\n  inner — Inner subcommand\n";
+
+        assert!(root.ends_with(&format!("{}\nExamples:\n  myapp -v\n", sections)));
+        assert!(subcommand.ends_with(sections));
+
+        Ok(())
+    }
+
+    /// Checks that [CliMake::help_template] lays out help for the root cli and
+    /// subcommands, leaving unknown placeholders as-is
+    #[test]
+    fn help_template() -> std::io::Result<()> {
+        let (mut root_vec, mut subcommand_vec): (Vec<u8>, Vec<u8>) = (vec![], vec![]);
+
+        let verbose = Argument::new("Verbose mode", vec!['v'], vec![], Input::None);
+        let add = Subcommand::new("add", vec![&verbose], vec![], "Adds things");
+        let mut cli = CliMake::new("mytool", vec![&verbose], vec![&add], "A tool", None);
+        cli.reproducible_docs(true)
+            .add_example("mytool add")
+            .help_template("{usage}{after-help}{arguments}\nSee {docs}\n");
+
+        cli.help_msg(&mut root_vec)?;
+        add.help_msg(&cli, false, &mut subcommand_vec)?;
+
+        assert_eq!(
+            String::from_utf8(root_vec).unwrap(),
+            "Usage: ./mytool [OPTIONS]\n\nExamples:\n  mytool add\n\nArguments:\n  -v — Verbose mode\n\nSee {docs}\n"
+        );
+        assert_eq!(
+            String::from_utf8(subcommand_vec).unwrap(),
+            "Usage: ./mytool add [OPTIONS]\n\nArguments:\n  -v — Verbose mode\n\nSee {docs}\n"
+        );
+
+        Ok(())
+    }
+
+    /// Checks that [CliMake::before_help] and [CliMake::after_help] text is
+    /// wrapped around help, with subcommands using their own
+    #[test]
+    fn help_before_after() -> std::io::Result<()> {
+        let (mut root_vec, mut subcommand_vec): (Vec<u8>, Vec<u8>) = (vec![], vec![]);
+
+        let mut add = Subcommand::new("add", vec![], vec![], "Adds things");
+        add.after_help = Some("See also: mytool remove");
+        let mut cli = CliMake::new("mytool", vec![], vec![&add], "A tool", None);
+        cli.reproducible_docs(true)
+            .max_width(30)
+            .add_example("mytool add")
+            .before_help("Made by example")
+            .after_help("Links:\n\nCopyright someone, licensed under MIT");
+
+        cli.help_msg(&mut root_vec)?;
+        add.help_msg(&cli, false, &mut subcommand_vec)?;
+
+        let root = String::from_utf8(root_vec).unwrap();
+        let subcommand = String::from_utf8(subcommand_vec).unwrap();
+
+        assert!(root.starts_with("  Made by example\n\nUsage: ./mytool [OPTIONS]\n"));
+        assert!(root.ends_with(
+            "\nExamples:\n  mytool add\n\n  Links:\n\n  Copyright someone, licensed\n  under MIT\n"
+        ));
+        assert!(subcommand.starts_with("Usage: ./mytool add [OPTIONS]\n"));
+        assert!(subcommand.ends_with("  No subcommands found\n\n  See also: mytool remove\n"));
+
+        Ok(())
+    }
+
+    /// Checks that arguments and subcommands which aren't available are hidden
+    #[test]
+    fn help_unavailable() -> std::io::Result<()> {
+        let mut chk_vec: Vec<u8> = vec![];
+
+        let mut color = Argument::new("Color", vec!['c'], vec![], Input::None);
+        color.available_if(|| false);
+        let verbose = Argument::new("Verbose mode", vec!['v'], vec![], Input::None);
+        let mut service = Subcommand::new("service", vec![], vec![], "Windows only");
+        service.available_if(|| false);
+        let cli = CliMake::new("MyApp", vec![&color, &verbose], vec![&service], None, None);
+
+        cli.help_msg(&mut chk_vec)?;
+        assert!(std::str::from_utf8(chk_vec.as_slice()).unwrap().ends_with(
+            "\nArguments:\n  -v — Verbose mode\n\nSubcommands:\n  No subcommands found\n"
+        ));
+
+        Ok(())
+    }
+
+    /// Checks that help is byte-stable when [CliMake::reproducible_docs] is set
+    #[test]
+    fn help_reproducible() -> std::io::Result<()> {
+        let mut chk_vec: Vec<u8> = vec![];
+
+        let mut add = Subcommand::new("add", vec![], vec![], "Adds things");
+        add.version = Some("0.2.0");
+        let mut cli = CliMake::new("mytool", vec![], vec![&add], "A tool", "1.0.0");
+        cli.reproducible_docs(true);
+
+        cli.help_msg(&mut chk_vec)?;
+        assert!(std::str::from_utf8(chk_vec.as_slice())
+            .unwrap()
+            .starts_with("Usage: ./mytool [OPTIONS]\n\n  mytool — A tool\n"));
+        chk_vec = vec![];
+
+        add.help_msg(&cli, false, &mut chk_vec)?;
+        assert!(std::str::from_utf8(chk_vec.as_slice())
+            .unwrap()
+            .contains("\nAbout:\n  Adds things\n"));
+
+        Ok(())
+    }
+
+    /// Checks that the [CliMake::help_search_msg] method finds matches at every
+    /// level of the cli
+    #[test]
+    fn help_search() -> std::io::Result<()> {
+        let mut chk_vec: Vec<u8> = vec![];
+
+        let proxy = Argument::new("Proxy server to use", vec!['p'], vec![], Input::Text);
+        let verbose = Argument::new("Verbose mode", vec!['v'], vec![], Input::None);
+        let fetch = Subcommand::new("fetch", vec![&proxy], vec![], "Fetches a url");
+        let net = Subcommand::new("net", vec![], vec![&fetch], "Networking, PROXY aware");
+        let cli = CliMake::new("MyApp", vec![&verbose], vec![&net], "Search check", None);
+
+        cli.help_search_msg("proxy", &mut chk_vec)?;
+        assert_eq!(
+            std::str::from_utf8(chk_vec.as_slice()).unwrap(),
+            "Results for 'proxy':\n\nMyApp:\n  net — Networking, PROXY aware\n\nMyApp net fetch:\n  -p [text] — Proxy server to use\n"
+        );
+        chk_vec = vec![];
+
+        cli.help_search_msg("nothing", &mut chk_vec)?;
+        assert_eq!(
+            std::str::from_utf8(chk_vec.as_slice()).unwrap(),
+            "Results for 'nothing':\n  No results found\n"
+        );
+
+        Ok(())
+    }
+}
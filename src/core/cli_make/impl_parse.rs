@@ -1,96 +1,4304 @@
 //! Contains parsing implementations for [CliMake]
 
 use super::{Argument, CliMake, Subcommand};
-use crate::core::argument::CallType;
-use crate::parsed::{ParsedArgument, ParsedCli, ParsedSubcommand};
+use crate::core::argument::{CallType, RepeatPolicy};
+use crate::core::render::{HelpRender, RenderOptions};
+use crate::core::utils::{platform_quote, split_shell, suggest, Shared};
+use crate::io::{Data, Input};
+use crate::lexer::{lex, Lexer, Token};
+use crate::messages::{message, Key};
+use crate::parsed::{Override, ParsedArgument, ParsedCli, ParsedSubcommand};
 
+use std::collections::HashMap;
 use std::env;
+use std::ffi::{OsStr, OsString};
+use std::fmt;
+use std::io::{self, Write};
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+use std::process;
 
-/// Container enumeration for [crate::parsed]-related structs to be sent up the
-/// chain from [match_next] recursive parsing
-enum ParsedLayer<'a> {
-    ParsedArgument(ParsedArgument<'a>),
-    ParsedSubcommand(ParsedSubcommand<'a>),
+/// Maximum amount of available subcommands listed in a [ParseError::SubcommandNotFound]
+/// message before the rest are summarised as "and N more"
+const SUBCOMMANDS_LISTED: usize = 5;
+
+/// Order given to the parsed arguments of the cli and each subcommand, as set by
+/// [CliMake::argument_order]
+///
+/// Arguments filled in from an [Argument::env] fallback weren't given by the user
+/// so always come after those which were, in the order they were declared in.
+#[derive(Debug, Default, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum ArgumentOrder {
+    /// In the order the user gave them on the command line, the default
+    #[default]
+    Given,
+
+    /// In the order the arguments were declared in the cli or subcommand
+    Declared,
 }
 
-/// Internal error enum representing instances of user-facing errors whilst parsing
-/// (i.e. due to bad user input). These should be converted into strings and shown
-/// to the user as directly as possible
-enum ParseError {
-    /// When a given subcommand which is being parsed in [match_next_subcommand]
-    /// could not be found
-    SubcommandNotFound(String),
+/// A single level of the cli being parsed, i.e. the root cli or a subcommand
+struct Level<'a> {
+    /// Name of the level, used for messages
+    name: &'a str,
+
+    /// Arguments which may be given at this level
+    arguments: &'a [&'a Argument<'a>],
+
+    /// Subcommands which may be given at this level
+    subcommands: &'a [&'a Subcommand<'a>],
+
+    /// The subcommand this level is for, or [None] for the root cli
+    subcommand: Option<&'a Subcommand<'a>>,
+
+    /// If this level is the applet selected by [CliMake::multicall], meaning the
+    /// root cli's arguments can only be given here
+    applet: bool,
 }
 
-/// Recurses down from an initial empty [ParsedSubcommand] to fill it in. This
-/// is used as the main "entrypoint" to parsing
-fn match_next_subcommand<'a>(
-    inputs: &mut impl Iterator<Item = String>,
-    mut parsed_subcommand: ParsedSubcommand<'a>,
-) -> Result<ParsedSubcommand<'a>, ParseError> {
-    loop {
-        let next_input = inputs.next();
-
-        match next_input {
-            Some(input) => {
-                if input.starts_with('-') {
-                    // argument matched
-                    // match find_argument(input, parsed_subcommand.inner.arguments) {
-                    //     TODO
-                    // }
-                } else {
-                    // subcommand matched
-                    match find_subcommand(&input, &parsed_subcommand.inner.subcommands) {
-                        Some(subcommand) => parsed_subcommand.subcommands.push(
-                            match_next_subcommand(inputs, ParsedSubcommand::new_empty(subcommand))?,
-                        ), // found subcommand, parse and add to `subcommands`
-                        None => return Err(ParseError::SubcommandNotFound(input)), // subcommand was not found
-                    }
+impl<'a> Level<'a> {
+    /// Creates the level for the root of the `cli`
+    fn root(cli: &'a CliMake<'a>) -> Self {
+        Self {
+            name: cli.name,
+            arguments: &cli.arguments,
+            subcommands: &cli.subcommands,
+            subcommand: None,
+            applet: false,
+        }
+    }
+
+    /// Creates the level for a `subcommand` which was given
+    fn of(subcommand: &'a Subcommand<'a>) -> Self {
+        Self {
+            name: subcommand.name,
+            arguments: &subcommand.arguments,
+            subcommands: &subcommand.subcommands,
+            subcommand: Some(subcommand),
+            applet: false,
+        }
+    }
+}
+
+/// Location of an input which caused an error whilst parsing, see [Diagnostic]
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub(crate) struct Span {
+    /// Position of the input, counting from `1` for the first input after the
+    /// executable as with [Lexer::taken]
+    position: usize,
+
+    /// Byte range inside of the input which is at fault if it's only part of
+    /// it, e.g. a single short call inside of a cluster such as `-vx`
+    range: Option<Range<usize>>,
+}
+
+impl Span {
+    /// Creates a span for the whole input at `position`
+    fn at(position: usize) -> Self {
+        Self {
+            position,
+            range: None,
+        }
+    }
+}
+
+/// Error whilst parsing alongside the inputs which caused it, so they may be
+/// pointed out when it's shown, see [CliMake::error_msg]
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub(crate) struct Diagnostic<'a> {
+    /// The error itself, which is all [CliMake::try_parse_custom] gives back
+    pub(crate) error: Box<ParseError>,
+
+    /// Spans of the inputs at fault, if any are known
+    spans: Vec<Span>,
+
+    /// Every input given, starting with the executable
+    inputs: Vec<String>,
+
+    /// The subcommand whose level the error came from, or [None] for the root
+    subcommand: Option<&'a Subcommand<'a>>,
+}
+
+impl<'a> From<ParseError> for Diagnostic<'a> {
+    fn from(error: ParseError) -> Self {
+        Self {
+            error: Box::new(error),
+            spans: vec![],
+            inputs: vec![],
+            subcommand: None,
+        }
+    }
+}
+
+/// Everything parsed from a single [Level] of the cli
+#[derive(Default)]
+struct ParsedLevel<'a> {
+    /// Arguments given for this level
+    arguments: Vec<ParsedArgument<'a>>,
+
+    /// Subcommands given for this level
+    subcommands: Vec<ParsedSubcommand<'a>>,
+
+    /// Arguments of parents given at this level or below, alongside the index of
+    /// the parent they belong to, see [CliMake::bubble_arguments]
+    bubbled: Vec<(usize, ParsedArgument<'a>)>,
+
+    /// Inputs left once parsing was stopped by a `--` terminator, or by a
+    /// positional input with [CliMake::stop_at_positional] set. This is only
+    /// ever set for the root level
+    trailing: Vec<String>,
+
+    /// Inputs which weren't recognised, see [CliMake::lenient]. This is only
+    /// ever set for the root level
+    unknown: Vec<String>,
+
+    /// Values ignored as they were overridden, see [CliMake::record_overrides].
+    /// This is only ever set for the root level
+    overrides: Vec<Override>,
+}
+
+/// Outcome of parsing given by [CliMake::outcome_custom], with requests for help
+/// or the version kept apart from errors so nothing needs to be printed or exit
+/// the process
+///
+/// # Example
+///
+/// ```rust
+/// use climake::prelude::*;
+/// use climake::Outcome;
+///
+/// let mut cli = CliMake::new("tool", vec![], vec![], None, "1.0.0");
+/// cli.embedded(true);
+/// let inputs = vec!["tool", "--version"].into_iter().map(String::from);
+///
+/// match cli.outcome_custom(inputs) {
+///     Outcome::VersionRequested(version) => assert_eq!(version, "tool 1.0.0\n"),
+///     _ => unreachable!(),
+/// }
+/// ```
+#[derive(Debug, PartialEq, Clone)]
+pub enum Outcome<'a> {
+    /// Parsing finished successfully, containing the parsed cli
+    Parsed(ParsedCli<'a>),
+
+    /// Help was requested, containing the rendered help message to display
+    HelpRequested(String),
+
+    /// The version was requested, containing the version message to display
+    VersionRequested(String),
+
+    /// Parsing failed due to bad user input, containing the error
+    Error(ParseError),
+}
+
+impl<'a> From<Result<ParsedCli<'a>, ParseError>> for Outcome<'a> {
+    fn from(result: Result<ParsedCli<'a>, ParseError>) -> Self {
+        match result {
+            Ok(parsed) => Outcome::Parsed(parsed),
+            Err(ParseError::Help(help)) => Outcome::HelpRequested(help),
+            Err(ParseError::Version(version)) => Outcome::VersionRequested(version),
+            Err(error) => Outcome::Error(error),
+        }
+    }
+}
+
+/// User-facing error whilst parsing (i.e. due to bad user input) given by
+/// [CliMake::try_parse], exported as `climake::Error`. These should be converted
+/// into strings and shown to the user as directly as possible
+///
+/// # Example
+///
+/// As this implements [std::error::Error], it may be returned from `main` using
+/// `?` alongside other errors:
+///
+/// ```rust
+/// use climake::prelude::*;
+///
+/// fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     let verbose = Argument::new("Verbose mode", vec!['v'], vec![], Input::None);
+///     let cli = CliMake::new("tool", vec![&verbose], vec![], None, None);
+///
+///     let inputs = vec!["tool", "-v"].into_iter().map(String::from);
+///     let parsed = cli.try_parse_custom(inputs)?;
+///
+///     assert_eq!(parsed.arguments.len(), 1);
+///     Ok(())
+/// }
+/// ```
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+pub enum ParseError {
+    /// When a given subcommand which is being parsed could not be found
+    SubcommandNotFound {
+        /// Name the user provided
+        name: String,
+
+        /// Names of subcommands which are available at this level
+        available: Vec<String>,
+    },
+
+    /// When a value given to an [Input::Choice](crate::io::Input::Choice)
+    /// argument isn't one of its possible values
+    InvalidChoice {
+        /// Call used for the argument, e.g. `--format`
+        call: String,
+
+        /// Value the user provided
+        value: String,
+
+        /// Possible values for the argument
+        choices: Vec<String>,
+    },
+
+    /// When an argument was given a different amount of values than allowed by
+    /// its [Argument::arity]
+    ValueCount {
+        /// Call used for the argument, e.g. `--point`
+        call: String,
+
+        /// Description of the amount of values allowed, e.g. `2 to 3`
+        expected: String,
+
+        /// Amount of values which were given
+        found: usize,
+    },
+
+    /// When an argument set with [Argument::require_equals] was given its value
+    /// as the next input instead of attaching it with `=`, containing the call
+    EqualsRequired(String),
+
+    /// When the rest of a short cluster after an argument taking a value is made
+    /// up only of other short calls, e.g. `-ov` where `-o` takes a value and `-v`
+    /// is a flag, so it's unclear if the rest was meant as the value or not
+    AmbiguousCluster {
+        /// Call of the argument taking a value, e.g. `-o`
+        call: String,
+
+        /// Rest of the cluster which would be taken as its value, e.g. `v`
+        rest: String,
+    },
+
+    /// When a value couldn't be mapped to the [Input] of an argument, such as
+    /// text given for an [Input::Float]
+    InvalidValue {
+        /// Call used for the argument, e.g. `--ratio`
+        call: String,
+
+        /// Value the user provided
+        value: String,
+
+        /// Name of the kind of value which was expected, e.g. `number`
+        expected: String,
+    },
+
+    /// When an argument's [Argument::validator] rejected its data
+    Invalidated {
+        /// Call used for the argument, e.g. `--pattern`
+        call: String,
+
+        /// Message given by the validator
+        message: String,
+    },
+
+    /// When an argument which takes a value wasn't given one, either due to it
+    /// being the last input or the next input being another argument
+    MissingValue {
+        /// Call used for the argument, e.g. `--output`
+        call: String,
+
+        /// Name of the kind of value which was expected, e.g. `path`
+        expected: String,
+
+        /// Argument found in place of the value, if any
+        found: Option<String>,
+    },
+
+    /// When an argument given after a subcommand only exists for a parent of it,
+    /// i.e. the root cli, whilst [CliMake::bubble_arguments] isn't set
+    ParentArgument {
+        /// Call used for the argument, e.g. `--verbose`
+        call: String,
+
+        /// Name of the parent the argument exists for
+        owner: String,
+
+        /// Name of the subcommand the argument was given after
+        subcommand: String,
+    },
+
+    /// When an abbreviated long call matched calls of more than one argument,
+    /// see [CliMake::abbreviations]
+    AmbiguousCall {
+        /// Call the user provided, e.g. `--ver`
+        call: String,
+
+        /// Calls which the abbreviation could've meant, e.g. `--verbose`
+        candidates: Vec<String>,
+    },
+
+    /// When an abbreviated subcommand name matched more than one subcommand, see
+    /// [CliMake::abbreviate_subcommands]
+    AmbiguousSubcommand {
+        /// Name the user provided, e.g. `p`
+        name: String,
+
+        /// Subcommands which the abbreviation could've meant, e.g. `post`
+        candidates: Vec<String>,
+    },
+
+    /// When an argument was given which doesn't exist at this level of the cli
+    ArgumentNotFound {
+        /// Call the user provided, e.g. `--verbose`
+        call: String,
+
+        /// Calls of arguments which are available at this level
+        available: Vec<String>,
+    },
+
+    /// When required arguments weren't given for the cli or any subcommand which
+    /// was invoked, containing a description of each, e.g. `--name for 'add'`
+    MissingArguments(Vec<String>),
+
+    /// When an argument which doesn't take a value was given one using `=`
+    UnexpectedValue {
+        /// Call used for the argument, e.g. `--verbose`
+        call: String,
+
+        /// Value the user provided
+        value: String,
+    },
+
+    /// When an argument or subcommand was given which isn't available, as decided
+    /// by [Argument::available_if] or [Subcommand::available_if], containing the
+    /// call or name given
+    Unavailable(String),
+
+    /// When an argument set to [RepeatPolicy::ErrorOnDuplicate] was given more
+    /// than once, containing its call
+    Duplicate(String),
+
+    /// When an argument was given more often than its
+    /// [Argument::max_occurrences] allows
+    TooManyOccurrences {
+        /// Call of the argument
+        call: String,
+
+        /// Amount of times it may be given
+        max: usize,
+
+        /// Amount of times it was given
+        found: usize,
+    },
+
+    /// When a line given to [CliMake::try_parse_str] has a quote which was never
+    /// closed, containing the quote
+    UnclosedQuote(char),
+
+    /// When subcommands were nested deeper than [CliMake::max_depth], containing
+    /// the limit
+    TooDeep(usize),
+
+    /// When help was requested instead of parsing finishing, e.g. when invoking
+    /// a subcommand set with [Subcommand::help_if_empty] on its own. This isn't
+    /// an error as such, containing the rendered help message to display
+    Help(String),
+
+    /// When the version was requested using `-V` or `--version` instead of
+    /// parsing finishing, see [CliMake::disable_version_flag]. This isn't an
+    /// error as such, containing the version message to display
+    Version(String),
+
+    /// When a value read from an [Argument::env] environment variable caused an
+    /// error, containing the variable alongside the underlying error
+    FromEnv {
+        /// Environment variable which supplied the value
+        var: String,
+
+        /// Error the value caused
+        error: Box<ParseError>,
+    },
+
+    /// When several independent errors were found, such as many unknown calls
+    /// alongside missing required arguments, which are all reported at once in
+    /// the order they were found
+    Multiple(Vec<ParseError>),
+}
+
+impl ParseError {
+    /// Creates the apt error for a `value` which couldn't be mapped into data
+    /// for an `argument`, i.e. a [ParseError::InvalidChoice] for choices or a
+    /// [ParseError::InvalidValue] otherwise
+    fn bad_value(argument: &Argument, value: String) -> Self {
+        match argument.possible_values() {
+            Some(choices) if !choices.contains(&value) => ParseError::InvalidChoice {
+                call: argument.display_call(),
+                value,
+                choices,
+            },
+            _ => ParseError::InvalidValue {
+                call: argument.display_call(),
+                value,
+                expected: argument.input.hint().unwrap_or_default(),
+            },
+        }
+    }
+
+    /// Combines many `errors` into one, giving a [ParseError::Multiple] only if
+    /// there's more than one of them
+    fn combine(mut errors: Vec<ParseError>) -> Self {
+        match errors.len() {
+            1 => errors.remove(0),
+            _ => ParseError::Multiple(errors),
+        }
+    }
+}
+
+/// Quotes a suggested value so it can be copied into the platform's shell as-is,
+/// keeping the usual `'value'` look for values which don't need any quoting
+fn quote_suggestion(value: &str) -> String {
+    let quoted = platform_quote(value);
+
+    if quoted == value {
+        format!("'{}'", value)
+    } else {
+        quoted
+    }
+}
+
+impl ParseError {
+    /// Gets the stable [Key] of the message shown for this error, which may be
+    /// used instead of matching the english message as it's never changed once
+    /// released. Errors with more than one form of message give the key of the
+    /// form which is shown
+    pub fn key(&self) -> Key {
+        match self {
+            ParseError::SubcommandNotFound { available, .. } if available.is_empty() => {
+                Key::SubcommandNotFoundNone
+            }
+            ParseError::SubcommandNotFound { available, .. }
+                if available.len() > SUBCOMMANDS_LISTED =>
+            {
+                Key::SubcommandNotFoundMore
+            }
+            ParseError::SubcommandNotFound { .. } => Key::SubcommandNotFound,
+            ParseError::InvalidChoice { value, choices, .. } => {
+                match suggest(value, choices.iter().map(|choice| choice.as_str())) {
+                    Some(_) => Key::InvalidChoiceSuggestion,
+                    None => Key::InvalidChoice,
+                }
+            }
+            ParseError::EqualsRequired(_) => Key::EqualsRequired,
+            ParseError::AmbiguousCluster { .. } => Key::AmbiguousCluster,
+            ParseError::InvalidValue { .. } => Key::InvalidValue,
+            ParseError::Invalidated { .. } => Key::Invalidated,
+            ParseError::FromEnv { .. } => Key::FromEnv,
+            ParseError::MissingValue { found: Some(_), .. } => Key::MissingValueFound,
+            ParseError::MissingValue { found: None, .. } => Key::MissingValue,
+            ParseError::ValueCount { .. } => Key::ValueCount,
+            ParseError::ParentArgument { .. } => Key::ParentArgument,
+            ParseError::ArgumentNotFound { call, available } => {
+                match suggest(call, available.iter().map(|call| call.as_str())) {
+                    Some(_) => Key::ArgumentNotFoundSuggestion,
+                    None => Key::ArgumentNotFound,
+                }
+            }
+            ParseError::AmbiguousCall { .. } => Key::AmbiguousCall,
+            ParseError::AmbiguousSubcommand { .. } => Key::AmbiguousSubcommand,
+            ParseError::MissingArguments(missing) if missing.len() > 1 => Key::MissingArguments,
+            ParseError::MissingArguments(_) => Key::MissingArgument,
+            ParseError::UnexpectedValue { .. } => Key::UnexpectedValue,
+            ParseError::Unavailable(_) => Key::Unavailable,
+            ParseError::Duplicate(_) => Key::Duplicate,
+            ParseError::TooManyOccurrences { .. } => Key::TooManyOccurrences,
+            ParseError::UnclosedQuote(_) => Key::UnclosedQuote,
+            ParseError::TooDeep(_) => Key::TooDeep,
+            ParseError::Help(_) => Key::Help,
+            ParseError::Version(_) => Key::Version,
+            ParseError::Multiple(_) => Key::MultipleErrors,
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let key = self.key();
+        let shown = match self {
+            ParseError::SubcommandNotFound { name, available } => {
+                let listed = available
+                    .iter()
+                    .take(SUBCOMMANDS_LISTED)
+                    .map(|name| name.as_str())
+                    .collect::<Vec<&str>>()
+                    .join(", ");
+                let more = available.len().saturating_sub(SUBCOMMANDS_LISTED);
+
+                message(key, &[name, &listed, &more])
+            }
+            ParseError::InvalidChoice {
+                call,
+                value,
+                choices,
+            } => match suggest(value, choices.iter().map(|choice| choice.as_str())) {
+                Some(closest) => message(key, &[value, call, &quote_suggestion(closest)]),
+                None => message(key, &[value, call, &choices.join(", ")]),
+            },
+            ParseError::EqualsRequired(call) => message(key, &[call, call]),
+            ParseError::AmbiguousCluster { call, rest } => message(
+                key,
+                &[call, rest, call, rest, call, rest, call, rest, rest, call],
+            ),
+            ParseError::InvalidValue {
+                call,
+                value,
+                expected,
+            } => message(key, &[value, expected, call]),
+            ParseError::Invalidated {
+                call,
+                message: reason,
+            } => message(key, &[call, reason]),
+            ParseError::FromEnv { var, error } => message(key, &[error, var]),
+            ParseError::MissingValue {
+                call,
+                expected,
+                found,
+            } => match found {
+                Some(found) => message(key, &[call, expected, found]),
+                None => message(key, &[call, expected]),
+            },
+            ParseError::ValueCount {
+                call,
+                expected,
+                found,
+            } => message(key, &[expected, call, found]),
+            ParseError::ParentArgument {
+                call,
+                owner,
+                subcommand,
+            } => message(key, &[call, subcommand, owner, subcommand]),
+            ParseError::ArgumentNotFound { call, available } => {
+                match suggest(call, available.iter().map(|call| call.as_str())) {
+                    Some(closest) => message(key, &[call, &closest]),
+                    None => message(key, &[call]),
+                }
+            }
+            ParseError::AmbiguousCall { call, candidates } => {
+                message(key, &[call, &candidates.join(", ")])
+            }
+            ParseError::AmbiguousSubcommand { name, candidates } => {
+                message(key, &[name, &candidates.join(", ")])
+            }
+            ParseError::MissingArguments(missing) => match missing.split_last() {
+                Some((last, rest)) if !rest.is_empty() => message(key, &[&rest.join(", "), last]),
+                _ => message(key, &[&missing.join("")]),
+            },
+            ParseError::UnexpectedValue { call, value } => message(key, &[call, value]),
+            ParseError::Unavailable(given) => message(key, &[given]),
+            ParseError::Duplicate(call) => message(key, &[call]),
+            ParseError::TooManyOccurrences { call, max, found } => {
+                message(key, &[call, max, found])
+            }
+            ParseError::UnclosedQuote(quote) => message(key, &[quote]),
+            ParseError::TooDeep(limit) => message(key, &[limit]),
+            ParseError::Help(help) => message(key, &[help]),
+            ParseError::Version(version) => message(key, &[version]),
+            ParseError::Multiple(errors) => {
+                let mut shown = message(key, &[&errors.len()]);
+
+                for error in errors {
+                    shown.push_str(&format!("\n  {}", error));
+                }
+
+                shown
+            }
+        };
+
+        f.write_str(&shown)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Traces a single parsing decision to stderr, see [CliMake::trace]. Traces are
+/// only for debugging, so failing to write them never stops parsing
+fn trace(cli: &CliMake, input: impl AsRef<str>, decision: impl fmt::Display) {
+    cli.trace(input, decision, &mut warnings(cli)).ok();
+}
+
+/// Gets the buffer warnings should be written to, which is stderr unless
+/// [CliMake::embedded] is set
+fn warnings(cli: &CliMake) -> Box<dyn Write> {
+    if cli.embedded {
+        Box::new(io::sink())
+    } else {
+        Box::new(io::stderr())
+    }
+}
+
+/// Renders help using the given `render` closure, for sending up the chain as a
+/// [ParseError::Help]
+fn help(render: impl FnOnce(&mut Vec<u8>) -> io::Result<()>) -> ParseError {
+    let mut help = vec![];
+    render(&mut help).expect("Writing help to a vector can't fail");
+
+    ParseError::Help(String::from_utf8_lossy(&help).to_string())
+}
+
+/// Checks if the `next` input may be taken as a value for `argument` instead of
+/// being something else, i.e. another call or one of the `subcommands`
+///
+/// Only arguments whose values may run on or be left out give way to the
+/// `subcommands`, as a single required value is always the argument's own.
+#[allow(deprecated)]
+fn is_value(argument: &Argument, next: &str, subcommands: &[&Subcommand]) -> bool {
+    let open_ended = argument.optional_value
+        || argument.greedy
+        || argument.input == Input::Paths
+        || argument
+            .arity
+            .is_some_and(|(min, max)| min == 0 || max != Some(1));
+
+    match Token::classify(next) {
+        Token::Positional(_) => !open_ended || find_subcommand(next, subcommands).is_none(),
+        Token::ShortCluster(_) | Token::Long { .. } if argument.allow_hyphen_values => true,
+        // negative numbers look like short calls
+        Token::ShortCluster(_) => {
+            argument.input == Input::Float && argument.to_data(vec![next.to_string()]).is_ok()
+        }
+        _ => false,
+    }
+}
+
+/// Takes any further values for an `argument` from the `lexer` onto the given
+/// `values`, for as long as its [Argument::arity] allows (or every value for
+/// [Input::Paths] and [Argument::greedy] arguments without one)
+#[allow(deprecated)]
+fn take_values(
+    argument: &Argument,
+    values: &mut Vec<String>,
+    subcommands: &[&Subcommand],
+    lexer: &mut Lexer<impl Iterator<Item = String>>,
+) {
+    let takes_more = |taken: usize| match argument.arity {
+        Some((_, max)) => max.map_or(true, |max| taken < max),
+        None => argument.input == Input::Paths || argument.greedy,
+    };
+
+    while takes_more(values.len())
+        && lexer
+            .peek_raw()
+            .is_some_and(|next| is_value(argument, next, subcommands))
+    {
+        values.push(lexer.next_raw().unwrap());
+    }
+}
+
+/// Parses the value(s) for an `argument` given using `call`, from either the
+/// `inline` value attached to the call (e.g. `--output=file`) or the following
+/// inputs, into a [ParsedArgument]
+///
+/// Arguments taking [Input::Paths] take every following input up until the next
+/// call or one of the `subcommands` given at this level, unless limited by their
+/// [Argument::arity].
+fn parse_argument<'a>(
+    cli: &CliMake<'a>,
+    argument: &'a Argument<'a>,
+    call: &CallType,
+    inline: Option<String>,
+    subcommands: &[&Subcommand],
+    lexer: &mut Lexer<impl Iterator<Item = String>>,
+) -> Result<ParsedArgument<'a>, ParseError> {
+    let position = lexer.taken();
+    let values = match inline {
+        Some(value) if argument.input == Input::None => {
+            return Err(ParseError::UnexpectedValue {
+                call: call.to_string(),
+                value,
+            })
+        }
+        Some(value)
+            if (argument.arity.is_some() || argument.greedy) && !argument.optional_value =>
+        {
+            let mut values = vec![value];
+            take_values(argument, &mut values, subcommands, lexer);
+            values
+        }
+        Some(value) => vec![value],
+        None if argument.input == Input::None || argument.optional_value => vec![],
+        None => match lexer.peek_raw() {
+            Some(next) if is_value(argument, next, subcommands) => {
+                if argument.require_equals {
+                    return Err(ParseError::EqualsRequired(call.to_string()));
                 }
+
+                let mut values = vec![lexer.next_raw().unwrap()];
+                take_values(argument, &mut values, subcommands, lexer);
+                values
             }
-            None => break,
+            _ if argument.arity.is_some_and(|(min, _)| min == 0) => vec![],
+            found => {
+                return Err(ParseError::MissingValue {
+                    call: call.to_string(),
+                    expected: argument.input.hint().unwrap_or_default(),
+                    found: found.cloned(),
+                })
+            }
+        },
+    };
+
+    for value in values.iter() {
+        trace(cli, value, format_args!("taken as value for {}", call));
+    }
+
+    match argument.arity {
+        Some((min, _)) if values.len() < min && !argument.optional_value => {
+            return Err(ParseError::ValueCount {
+                call: call.to_string(),
+                expected: argument.arity_msg(),
+                found: values.len(),
+            })
         }
+        _ => (),
     }
 
-    Ok(parsed_subcommand)
+    let data = if argument.negatable && argument.input == Input::None {
+        Data::Bool(!argument.is_negation(call))
+    } else {
+        argument
+            .to_data(values)
+            .map_err(|value| ParseError::bad_value(argument, value))?
+    };
+
+    Ok(ParsedArgument {
+        position: Some(position),
+        ..new_parsed_argument(argument, data)?
+    })
 }
 
-/// Finds `name`'d argument(s) in the passed vector of [Argument]s
-fn find_argument<'a>(call: impl AsRef<str>, arguments: Vec<&'a Argument<'a>>) -> Vec<&'a Argument<'a>> {
-    let mut found_arguments = vec![]; // arg output vec
+/// Parses a single `call` given at a `level` of the cli, with any `inline` value
+/// attached to it. This finds the argument at this level, or otherwise one of
+/// its `parents` or a request for help
+fn parse_call<'a>(
+    cli: &'a CliMake<'a>,
+    level: &Level<'a>,
+    parents: &[(&'a str, &'a [&'a Argument<'a>])],
+    call: CallType,
+    inline: Option<String>,
+    lexer: &mut Lexer<impl Iterator<Item = String>>,
+    parsed_level: &mut ParsedLevel<'a>,
+) -> Result<(), ParseError> {
+    let call = match call {
+        CallType::Long(name) if cli.case_insensitive => {
+            CallType::Long(match_case(name, level.arguments, parents))
+        }
+        call => call,
+    };
+    let call = rename_call(cli, call, level.arguments, parents, &mut warnings(cli));
+    let call = match call {
+        CallType::Long(ref name)
+            if cli.abbreviations
+                && !matches!(name.as_str(), "help" | "help-search")
+                && find_parent_argument(&call, parents).is_none() =>
+        {
+            let expanded = expand_abbreviation(call.clone(), level.arguments)?;
+
+            if expanded != call {
+                trace(
+                    cli,
+                    call.to_string(),
+                    format_args!("expanded to {}", expanded),
+                );
+            }
+
+            expanded
+        }
+        call => call,
+    };
+
+    if let Some(argument) = find_argument(&call, level.arguments, &mut warnings(cli)) {
+        trace(
+            cli,
+            call.to_string(),
+            format_args!("matched as argument {}", argument.display_call()),
+        );
+
+        if !argument.is_available() {
+            return Err(ParseError::Unavailable(call.to_string()));
+        }
+
+        let parsed_argument =
+            parse_argument(cli, argument, &call, inline, level.subcommands, lexer)?;
+        parsed_level.arguments.push(parsed_argument);
+        return Ok(());
+    }
+
+    // the root can't be given before an applet, so its arguments always bubble
+    let bubble = level.applet
+        || level
+            .subcommand
+            .and_then(|subcommand| subcommand.bubble_arguments)
+            .unwrap_or(cli.bubble_arguments);
+    let parent = find_parent_argument(&call, parents);
+
+    // parent arguments are matched before the automatic calls so they aren't hijacked
+    if let Some((index, argument)) = parent.filter(|(_, argument)| bubble || argument.global) {
+        trace(
+            cli,
+            call.to_string(),
+            format_args!(
+                "matched as argument {} of parent '{}'",
+                argument.display_call(),
+                parents[index].0
+            ),
+        );
+
+        if !argument.is_available() {
+            return Err(ParseError::Unavailable(call.to_string()));
+        }
+
+        let parsed_argument =
+            parse_argument(cli, argument, &call, inline, level.subcommands, lexer)?;
+        parsed_level.bubbled.push((index, parsed_argument));
+        return Ok(());
+    }
+
+    if let Some(version) = version_msg(cli, level, &call, inline.as_deref(), lexer)? {
+        return Err(ParseError::Version(version));
+    }
+
+    match &call {
+        _ if !help_enabled(cli, level) => (),
+        CallType::Short('h') => return Err(level_help(cli, level, None, false)),
+        CallType::Long(name) if name == "help" => {
+            let is_section = |next: &String| {
+                level
+                    .arguments
+                    .iter()
+                    .any(|argument| argument.section == Some(next.as_str()))
+            };
+            let section = match inline {
+                Some(section) => Some(section),
+                None if lexer.peek_raw().is_some_and(is_section) => lexer.next_raw(),
+                None => None,
+            };
 
-    if &call.as_ref()[..2] == "--" {
-        // long call matched
-        let call_match = &call.as_ref()[2..];
+            return Err(level_help(cli, level, section, true));
+        }
+        CallType::Long(name) if name == "help-search" => {
+            let query = match inline.or_else(|| lexer.next_raw()) {
+                Some(query) => query,
+                None => {
+                    return Err(ParseError::MissingValue {
+                        call: call.to_string(),
+                        expected: Input::Text.hint().unwrap_or_default(),
+                        found: None,
+                    })
+                }
+            };
 
-        for argument in arguments.iter() {
-            unimplemented!()
+            return Err(help(|buf| cli.help_search_msg(query, buf)));
         }
+        _ => (),
     }
 
-    found_arguments
+    match parent {
+        Some((index, _)) => Err(ParseError::ParentArgument {
+            call: call.to_string(),
+            owner: parents[index].0.to_string(),
+            subcommand: level.name.to_string(),
+        }),
+        None => Err(ParseError::ArgumentNotFound {
+            call: call.to_string(),
+            available: level
+                .arguments
+                .iter()
+                .filter(|argument| argument.is_available())
+                .flat_map(|argument| {
+                    let shorts = argument.shorts.iter().map(|c| CallType::Short(*c));
+                    let longs = argument.longs.iter().map(|l| CallType::Long(l.clone()));
+                    shorts.chain(longs).map(|call| call.to_string())
+                })
+                .collect(),
+        }),
+    }
 }
 
-/// Finds `name`'d subcommand in the passed vector of `subcommands`
-fn find_subcommand<'a>(name: impl AsRef<str>, subcommands: &Vec<&'a Subcommand>) -> Option<&'a Subcommand<'a>> {
-    for subcommand in subcommands.iter() {
-        if name.as_ref() == subcommand.name {
-            return Some(subcommand);
+/// Checks if the automatic help calls are enabled for a `level` of the cli, see
+/// [CliMake::disable_help_flag] and [Subcommand::disable_help_flag]
+fn help_enabled(cli: &CliMake, level: &Level) -> bool {
+    !level
+        .subcommand
+        .and_then(|subcommand| subcommand.disable_help_flag)
+        .unwrap_or(cli.disable_help_flag)
+}
+
+/// Checks if the automatic `help` subcommand may be used at a `level` of the
+/// cli, which must have subcommands of its own, see
+/// [CliMake::disable_help_subcommand]
+fn help_subcommand_enabled(cli: &CliMake, level: &Level) -> bool {
+    !cli.disable_help_subcommand && !level.subcommands.is_empty() && help_enabled(cli, level)
+}
+
+/// Renders help for the subcommand named by the rest of the inputs after an
+/// automatic `help` subcommand, walking down from the given `level`
+fn help_subcommand(
+    cli: &CliMake,
+    level: &Level,
+    lexer: &mut Lexer<impl Iterator<Item = String>>,
+) -> ParseError {
+    let mut subcommand = level.subcommand;
+    let mut subcommands = level.subcommands;
+
+    while let Some(name) = lexer.next_raw() {
+        let found = match find_subcommand_prefix(cli, &name, subcommands) {
+            Ok(found) => found,
+            Err(error) => return error,
+        };
+
+        match found {
+            Some(found) => {
+                subcommand = Some(found);
+                subcommands = &found.subcommands;
+            }
+            None => return subcommand_not_found(name, subcommands),
         }
     }
 
-    None
+    match subcommand {
+        Some(subcommand) => level_help(cli, &Level::of(subcommand), None, true),
+        None => level_help(cli, level, None, true),
+    }
 }
 
-impl<'a> CliMake<'a> {
-    /// Parses all arguments from a custom iterator, see [CliMake::parse] for
-    /// default parsing from [env::args]
-    pub fn parse_custom(&'a self, inputs: impl IntoIterator<Item = String>) -> ParsedCli<'a> {
-        unimplemented!()
+/// Gives the error for a subcommand `name` which isn't one of the available
+/// `subcommands`
+fn subcommand_not_found(name: String, subcommands: &[&Subcommand]) -> ParseError {
+    ParseError::SubcommandNotFound {
+        name,
+        available: subcommands
+            .iter()
+            .filter(|subcommand| subcommand.is_available())
+            .map(|subcommand| subcommand.name.to_string())
+            .collect(),
     }
+}
 
-    /// Parses default arguments coming from [env::args]
-    pub fn parse(&'a self) -> ParsedCli<'a> {
-        self.parse_custom(env::args())
+/// Gets the version message if `call` is one of the automatic version calls for
+/// a `level` of the cli, using the version of the subcommand being parsed if it
+/// has one, see [CliMake::disable_version_flag] and
+/// [Subcommand::disable_version_flag]
+///
+/// The format of the version may be given as the `inline` value of the call or
+/// using a following `--format` taken from the `lexer`, which is either `text`
+/// (the default) or `json`, see [CliMake::build_info]. A `--format` is left alone
+/// if the `level` has an argument of its own using that call
+fn version_msg(
+    cli: &CliMake,
+    level: &Level,
+    call: &CallType,
+    inline: Option<&str>,
+    lexer: &mut Lexer<impl Iterator<Item = String>>,
+) -> Result<Option<String>, ParseError> {
+    let disabled = level
+        .subcommand
+        .and_then(|subcommand| subcommand.disable_version_flag)
+        .unwrap_or(cli.disable_version_flag);
+    let long = match call {
+        _ if disabled => return Ok(None),
+        CallType::Short('V') => false,
+        CallType::Long(name) if name == "version" => true,
+        _ => return Ok(None),
+    };
+    let (name, version, text) = match level.subcommand {
+        Some(subcommand) if subcommand.version.is_some() => {
+            (subcommand.name, subcommand.version, subcommand.version)
+        }
+        _ => (
+            cli.name,
+            cli.version,
+            cli.long_version.filter(|_| long).or(cli.version),
+        ),
+    };
+    let text = match text {
+        Some(text) => text,
+        None => return Ok(None),
+    };
+    let format_call = CallType::Long("format".to_string());
+    let has_format = find_argument(&format_call, level.arguments, &mut io::sink()).is_some();
+    let (format_call, format) = match inline {
+        Some(format) => (call.to_string(), Some(format.to_string())),
+        None if has_format => (String::new(), None),
+        None => match lexer.peek_raw().map(|raw| raw.as_str()) {
+            Some("--format") => {
+                lexer.next_raw();
+                match lexer.next_raw() {
+                    Some(format) => (format_call.to_string(), Some(format)),
+                    None => {
+                        return Err(ParseError::MissingValue {
+                            call: format_call.to_string(),
+                            expected: Input::Text.hint().unwrap_or_default(),
+                            found: None,
+                        })
+                    }
+                }
+            }
+            Some(raw) if raw.starts_with("--format=") => (
+                "--format".to_string(),
+                lexer
+                    .next_raw()
+                    .map(|raw| raw["--format=".len()..].to_string()),
+            ),
+            _ => (String::new(), None),
+        },
+    };
+
+    match format.as_deref() {
+        None | Some("text") => Ok(Some(format!("{} {}\n", name, text))),
+        Some("json") => Ok(Some(cli.version_json(name, version.unwrap_or(text)))),
+        Some(value) => Err(ParseError::InvalidChoice {
+            call: format_call,
+            value: value.to_string(),
+            choices: vec!["text".to_string(), "json".to_string()],
+        }),
+    }
+}
+
+/// Renders help for a `level` of the cli, or only for the arguments in a single
+/// `section` if one was given, see [CliMake::help_section_msg]. Extended help is
+/// included if `long` is set, as for `--help` rather than `-h`
+fn level_help(cli: &CliMake, level: &Level, section: Option<String>, long: bool) -> ParseError {
+    match (section, level.subcommand) {
+        (Some(section), subcommand) => help(|buf| cli.help_section_msg(subcommand, &section, buf)),
+        (None, Some(subcommand)) => help(|buf| subcommand.help_msg(cli, long, buf)),
+        (None, None) => help(|buf| {
+            let options = RenderOptions {
+                long_help: long,
+                ..cli.render_options()
+            };
+            cli.render_full(&options, buf)
+        }),
+    }
+}
+
+/// Collects the `given` input into `unknown` if the `result` of parsing it is an
+/// [ParseError::ArgumentNotFound] whilst [CliMake::lenient] is set, otherwise
+/// collecting the error and the `span` of the input into `errors` so parsing may
+/// carry on and report it alongside any others, in which case `true` is given
+/// back. Any other result is passed through as-is
+fn collect_unknown(
+    cli: &CliMake,
+    result: Result<(), ParseError>,
+    given: Option<String>,
+    span: Span,
+    unknown: &mut Vec<String>,
+    errors: &mut Vec<(ParseError, Option<Span>)>,
+) -> Result<bool, ParseError> {
+    match (result, given) {
+        (Err(ParseError::ArgumentNotFound { .. }), Some(given)) if cli.lenient => {
+            trace(cli, &given, "collected as unknown");
+            unknown.push(given);
+            Ok(false)
+        }
+        (Err(error @ ParseError::ArgumentNotFound { .. }), _) => {
+            errors.push((error, Some(span)));
+            Ok(true)
+        }
+        (result, _) => result.map(|_| false),
+    }
+}
+
+/// Skips the input following an unknown call collected as an error if it looks
+/// like the call's value, i.e. isn't a call or subcommand, so it isn't reported
+/// again as an unknown subcommand
+fn skip_unknown_value(
+    cli: &CliMake,
+    level: &Level,
+    lexer: &mut Lexer<impl Iterator<Item = String>>,
+) {
+    let is_value = |next: &String| {
+        !next.starts_with('-') && find_subcommand_case(cli, next, level.subcommands).is_none()
+    };
+
+    if lexer.peek_raw().is_some_and(is_value) {
+        trace(cli, lexer.peek_raw().unwrap(), "skipped as unknown value");
+        lexer.next_raw();
+    }
+}
+
+/// Parses every level of the cli using [walk_levels], reporting every unknown
+/// call found alongside the error which stopped parsing (if any) as one
+/// [ParseError::Multiple] with the spans of the inputs which caused them.
+/// Requests for help or the version are always given on their own
+fn parse_levels<'a>(
+    cli: &'a CliMake<'a>,
+    root: Level<'a>,
+    applet: Option<&'a Subcommand<'a>>,
+    lexer: &mut Lexer<impl Iterator<Item = String>>,
+) -> Result<ParsedLevel<'a>, (ParseError, Vec<Span>, Option<&'a Subcommand<'a>>)> {
+    let mut errors = vec![];
+    let mut walked = false;
+    let mut failed = None;
+
+    match walk_levels(
+        cli,
+        root,
+        applet,
+        lexer,
+        &mut errors,
+        &mut walked,
+        &mut failed,
+    ) {
+        Err(ParseError::Help(help)) => return Err((ParseError::Help(help), vec![], None)),
+        Err(ParseError::Version(version)) => {
+            return Err((ParseError::Version(version), vec![], None))
+        }
+        Ok(parsed_level) if errors.is_empty() => return Ok(parsed_level),
+        Ok(_) => (),
+        // errors whilst finishing levels aren't due to the last input taken
+        Err(error) => errors.push((error, (!walked).then(|| Span::at(lexer.taken())))),
+    }
+
+    let (errors, spans): (Vec<ParseError>, Vec<Option<Span>>) = errors.into_iter().unzip();
+    Err((
+        ParseError::combine(errors),
+        spans.into_iter().flatten().collect(),
+        failed,
+    ))
+}
+
+/// Parses every level of the cli from the inputs left in the `lexer`, starting
+/// from the `root` level and descending into any subcommands given
+///
+/// This walks down subcommands using an explicit stack instead of recursing so
+/// deeply nested inputs can't overflow the stack, erroring once they go deeper
+/// than [CliMake::max_depth]. Each level is finished off with [finish_level] once
+/// inputs run out, from the deepest level upwards, with `walked` being set
+/// beforehand. Unknown calls are collected into `errors` rather than stopping
+/// parsing, see [collect_unknown].
+///
+/// Parsing starts inside of the `applet` subcommand if one was selected by
+/// [CliMake::multicall], as if it had been given first. The subcommand of the
+/// level the first error came from is set as `failed`, for showing its usage.
+fn walk_levels<'a>(
+    cli: &'a CliMake<'a>,
+    root: Level<'a>,
+    applet: Option<&'a Subcommand<'a>>,
+    lexer: &mut Lexer<impl Iterator<Item = String>>,
+    errors: &mut Vec<(ParseError, Option<Span>)>,
+    walked: &mut bool,
+    failed: &mut Option<&'a Subcommand<'a>>,
+) -> Result<ParsedLevel<'a>, ParseError> {
+    let mut parents: Vec<(&'a str, &'a [&'a Argument<'a>])> = vec![];
+    let mut stack = vec![];
+
+    if let Some(applet) = applet {
+        if !applet.is_available() {
+            return Err(ParseError::Unavailable(applet.name.to_string()));
+        }
+
+        parents.push((root.name, root.arguments));
+        stack.push((root, ParsedLevel::default()));
+        stack.push((
+            Level {
+                applet: true,
+                ..Level::of(applet)
+            },
+            ParsedLevel::default(),
+        ));
+    } else {
+        stack.push((root, ParsedLevel::default()));
+    }
+
+    let mut trailing = vec![];
+    let mut unknown = vec![];
+
+    while let Some(token) = lexer.next() {
+        let depth = stack.len();
+        let position = lexer.taken();
+        let (level, parsed_level) = stack.last_mut().unwrap();
+
+        if errors.is_empty() {
+            *failed = level.subcommand;
+        }
+
+        match token {
+            Token::Long { name, inline_value } => {
+                let given = cli.lenient.then(|| match &inline_value {
+                    Some(value) => format!("--{}={}", name, value),
+                    None => format!("--{}", name),
+                });
+                let had_inline = inline_value.is_some();
+                let result = parse_call(
+                    cli,
+                    level,
+                    &parents,
+                    CallType::Long(name),
+                    inline_value,
+                    lexer,
+                    parsed_level,
+                );
+
+                let span = Span::at(position);
+                if collect_unknown(cli, result, given, span, &mut unknown, errors)? && !had_inline {
+                    skip_unknown_value(cli, level, lexer);
+                }
+            }
+            Token::ShortCluster(cluster) => {
+                let mut chars = cluster.chars();
+
+                let find_short = |c: char| {
+                    let call = CallType::Short(c);
+                    find_argument(&call, level.arguments, &mut io::sink())
+                        .or_else(|| find_parent_argument(&call, &parents).map(|(_, arg)| arg))
+                };
+
+                while let Some(c) = chars.next() {
+                    let call = CallType::Short(c);
+                    let takes_value =
+                        find_short(c).is_some_and(|argument| argument.input != Input::None);
+
+                    // the rest of the cluster is the value for arguments taking one,
+                    // optionally attached using `=` like long calls
+                    let rest = chars.as_str();
+                    let inline = match rest.strip_prefix('=') {
+                        Some(value) => Some(value.to_string()),
+                        None if takes_value && !rest.is_empty() => {
+                            if rest.chars().all(|r| find_short(r).is_some()) {
+                                return Err(ParseError::AmbiguousCluster {
+                                    call: call.to_string(),
+                                    rest: rest.to_string(),
+                                });
+                            }
+
+                            Some(rest.to_string())
+                        }
+                        None => None,
+                    };
+                    let had_inline = inline.is_some();
+                    let given = format!("-{}{}", c, if had_inline { rest } else { "" });
+                    let start = cluster.len() - rest.len() + 1 - c.len_utf8();
+                    let span = Span {
+                        position,
+                        range: Some(start..start + c.len_utf8()),
+                    };
+
+                    let result =
+                        parse_call(cli, level, &parents, call, inline, lexer, parsed_level);
+                    let collected =
+                        collect_unknown(cli, result, Some(given), span, &mut unknown, errors)?;
+
+                    if collected && !had_inline && chars.as_str().is_empty() {
+                        skip_unknown_value(cli, level, lexer);
+                    }
+
+                    if had_inline {
+                        break;
+                    }
+                }
+            }
+            Token::Positional(input) => {
+                let slash = (cli.slash_calls && !lexer.terminated())
+                    .then(|| {
+                        slash_call(&input, help_enabled(cli, level), level.arguments, &parents)
+                    })
+                    .flatten();
+
+                if let Some((call, inline)) = slash {
+                    let had_inline = inline.is_some();
+                    let result =
+                        parse_call(cli, level, &parents, call, inline, lexer, parsed_level);
+                    let span = Span::at(position);
+                    if collect_unknown(cli, result, Some(input), span, &mut unknown, errors)?
+                        && !had_inline
+                    {
+                        skip_unknown_value(cli, level, lexer);
+                    }
+                    continue;
+                }
+
+                // the automatic help subcommand takes priority over prefixes
+                let is_help = input == "help" && help_subcommand_enabled(cli, level);
+                let found = match find_subcommand_case(cli, &input, level.subcommands) {
+                    None if !is_help => find_subcommand_prefix(cli, &input, level.subcommands)?,
+                    found => found,
+                };
+
+                match found {
+                    Some(subcommand) => {
+                        trace(cli, &input, "matched as subcommand");
+
+                        if !subcommand.is_available() {
+                            return Err(ParseError::Unavailable(input));
+                        }
+
+                        if depth > cli.max_depth {
+                            return Err(ParseError::TooDeep(cli.max_depth));
+                        }
+
+                        parents.push((level.name, level.arguments));
+                        stack.push((Level::of(subcommand), ParsedLevel::default()));
+                    }
+                    None if is_help => {
+                        trace(cli, &input, "matched as help subcommand");
+                        return Err(help_subcommand(cli, level, lexer));
+                    }
+                    None if cli.stop_at_positional => {
+                        trace(cli, &input, "stops parsing");
+                        trailing.push(input);
+                        break;
+                    }
+                    None if cli.lenient => {
+                        trace(cli, &input, "collected as unknown");
+                        unknown.push(input);
+                    }
+                    None => return Err(subcommand_not_found(input, level.subcommands)),
+                }
+            }
+            Token::Terminator => {
+                trace(cli, "--", "stops parsing");
+                break;
+            }
+        }
+    }
+
+    *walked = true;
+
+    while let Some(input) = lexer.next_raw() {
+        trailing.push(input);
+    }
+
+    // missing arguments are collected across levels to be reported all at once
+    let mut missing = vec![];
+    let mut overrides = vec![];
+    let mut located = !errors.is_empty();
+
+    loop {
+        let (level, mut parsed_level) = stack.pop().unwrap();
+        let mut level_overrides = vec![];
+        let finished = finish_level(cli, &level, &mut parsed_level, &mut level_overrides);
+
+        if !located && finished.as_ref().map_or(true, |found| !found.is_empty()) {
+            *failed = level.subcommand;
+            located = true;
+        }
+
+        missing.splice(0..0, finished?);
+        overrides.splice(0..0, level_overrides);
+
+        let depth = stack.len();
+        let (subcommand, parent_level) = match (level.subcommand, stack.last_mut()) {
+            (Some(subcommand), Some((_, parent_level))) => (subcommand, parent_level),
+            _ if !missing.is_empty() => return Err(ParseError::MissingArguments(missing)),
+            _ => {
+                return Ok(ParsedLevel {
+                    trailing,
+                    unknown,
+                    overrides,
+                    ..parsed_level
+                })
+            }
+        };
+
+        // bubbled arguments are indexed by the parent level they belong to
+        for (index, parsed_argument) in parsed_level.bubbled {
+            if index + 1 == depth {
+                parent_level.arguments.push(parsed_argument);
+            } else {
+                parent_level.bubbled.push((index, parsed_argument));
+            }
+        }
+
+        parent_level.subcommands.push(ParsedSubcommand {
+            arguments: parsed_level.arguments,
+            subcommands: parsed_level.subcommands,
+            parents: parents[..depth].iter().map(|(name, _)| *name).collect(),
+            ..ParsedSubcommand::new_empty(subcommand)
+        });
+    }
+}
+
+/// Finishes off a `level` of the cli once inputs have run out, ordering its
+/// arguments, filling in any missing arguments from their [Argument::env]
+/// fallback and removing repeated values of [Argument::unique] arguments, giving
+/// back a description of each required argument still missing. Environment
+/// variables overridden by given arguments are added to `overrides` if
+/// [CliMake::record_overrides] is set
+fn finish_level<'a>(
+    cli: &CliMake<'a>,
+    level: &Level<'a>,
+    parsed_level: &mut ParsedLevel<'a>,
+    overrides: &mut Vec<Override>,
+) -> Result<Vec<String>, ParseError> {
+    if let Some(subcommand) = level.subcommand {
+        if subcommand.help_if_empty
+            && parsed_level.arguments.is_empty()
+            && parsed_level.subcommands.is_empty()
+        {
+            return Err(level_help(cli, level, None, false));
+        }
+    }
+
+    order_arguments(
+        cli.argument_order,
+        &mut parsed_level.arguments,
+        level.arguments,
+    );
+    count_occurrences(&mut parsed_level.arguments);
+    check_occurrences(&parsed_level.arguments)?;
+    apply_repeats(&mut parsed_level.arguments)?;
+    let mut missing = vec![];
+
+    for argument in level.arguments.iter() {
+        if parsed_level
+            .arguments
+            .iter()
+            .any(|parsed| parsed.id == argument.id())
+        {
+            let ignored = argument
+                .env
+                .filter(|_| cli.record_overrides)
+                .and_then(|var| Some((var, env::var(var).ok()?)));
+
+            if let Some((var, value)) = ignored {
+                trace(
+                    cli,
+                    var,
+                    format_args!("overridden by {}", argument.display_call()),
+                );
+                overrides.push(Override {
+                    call: argument.display_call(),
+                    subcommand: level
+                        .subcommand
+                        .map(|subcommand| subcommand.name.to_string()),
+                    var: var.to_string(),
+                    value,
+                });
+            }
+
+            continue;
+        }
+
+        match env_fallback(argument)? {
+            Some(parsed_argument) => {
+                trace(
+                    cli,
+                    argument.env.unwrap_or_default(),
+                    format_args!("read from environment for {}", argument.display_call()),
+                );
+                parsed_level.arguments.push(parsed_argument)
+            }
+            None if argument.required => missing.push(match level.subcommand {
+                Some(_) => message(
+                    Key::MissingSubcommandArgument,
+                    &[&argument.display_call(), &level.name],
+                ),
+                None => argument.display_call(),
+            }),
+            None => (),
+        }
+    }
+
+    for parsed_argument in parsed_level.arguments.iter_mut() {
+        if parsed_argument.inner.unique {
+            parsed_argument.duplicates = parsed_argument.data.dedup();
+        }
+    }
+
+    Ok(missing)
+}
+
+/// Creates a new [ParsedArgument] for an `argument` from its parsed `data`,
+/// running its [Argument::validator] (if any) to check the data and stash any
+/// custom item it gives back
+fn new_parsed_argument<'a>(
+    argument: &'a Argument<'a>,
+    data: Data,
+) -> Result<ParsedArgument<'a>, ParseError> {
+    let custom = match &argument.validator {
+        Some(validator) => match (validator.0)(&data) {
+            Ok(custom) => Some(Shared(custom)),
+            Err(message) => {
+                return Err(ParseError::Invalidated {
+                    call: argument.display_call(),
+                    message,
+                })
+            }
+        },
+        None => None,
+    };
+
+    Ok(ParsedArgument {
+        id: argument.id(),
+        inner: argument,
+        data,
+        is_present: true,
+        occurrences: 1,
+        duplicates: 0,
+        position: None,
+        custom,
+    })
+}
+
+/// Gets the [Argument::env] fallback for an `argument` which was missing from
+/// the inputs, if it has one set. This should only be used once an argument is
+/// known to be missing, so the environment is never needlessly read
+fn env_fallback<'a>(argument: &'a Argument<'a>) -> Result<Option<ParsedArgument<'a>>, ParseError> {
+    match argument.read_env() {
+        Some((var, Ok(data))) => match new_parsed_argument(argument, data) {
+            Ok(parsed_argument) => Ok(Some(ParsedArgument {
+                is_present: false,
+                occurrences: 0,
+                ..parsed_argument
+            })),
+            Err(error) => Err(ParseError::FromEnv {
+                var: var.to_string(),
+                error: Box::new(error),
+            }),
+        },
+        Some((var, Err(value))) => Err(ParseError::FromEnv {
+            var: var.to_string(),
+            error: Box::new(ParseError::bad_value(argument, value)),
+        }),
+        None => Ok(None),
+    }
+}
+
+/// Orders the `parsed` arguments of a single cli or subcommand level as given by
+/// `order`, which are expected to be in the order they were given. Any `declared`
+/// arguments are used to find the declaration order
+///
+/// Sorting is stable, so repeated arguments stay in the order they were given.
+fn order_arguments(order: ArgumentOrder, parsed: &mut [ParsedArgument], declared: &[&Argument]) {
+    if order == ArgumentOrder::Declared {
+        parsed.sort_by_key(|parsed_argument| {
+            declared
+                .iter()
+                .position(|argument| argument.id() == parsed_argument.id)
+        });
+    }
+}
+
+/// Gives the data of every parsed argument with an [Argument::bind] sink to it,
+/// from the top-level `arguments` down through the parsed `subcommands`
+fn fill_sinks(arguments: &[ParsedArgument], subcommands: &[ParsedSubcommand]) {
+    for parsed_argument in arguments.iter() {
+        if let Some(sink) = &parsed_argument.inner.sink {
+            (sink.0)(&parsed_argument.data);
+        }
+    }
+
+    for subcommand in subcommands.iter() {
+        fill_sinks(&subcommand.arguments, &subcommand.subcommands);
+    }
+}
+
+/// Copies every parsed argument set with [Argument::global] down into each of
+/// the parsed `subcommands` below the level it was given at, from the top-level
+/// `arguments` downwards
+fn spread_globals<'a>(arguments: &[ParsedArgument<'a>], subcommands: &mut [ParsedSubcommand<'a>]) {
+    let globals = |arguments: &[ParsedArgument<'a>]| -> Vec<ParsedArgument<'a>> {
+        arguments
+            .iter()
+            .filter(|parsed_argument| parsed_argument.inner.global)
+            .cloned()
+            .collect()
+    };
+    let mut stack: Vec<(Vec<ParsedArgument<'a>>, &mut ParsedSubcommand<'a>)> = subcommands
+        .iter_mut()
+        .map(|subcommand| (globals(arguments), subcommand))
+        .collect();
+
+    while let Some((inherited, subcommand)) = stack.pop() {
+        for parsed_argument in inherited {
+            if !subcommand
+                .arguments
+                .iter()
+                .any(|existing| existing.id == parsed_argument.id)
+            {
+                subcommand.arguments.push(parsed_argument);
+            }
+        }
+
+        let inherited = globals(&subcommand.arguments);
+        stack.extend(
+            subcommand
+                .subcommands
+                .iter_mut()
+                .map(|child| (inherited.clone(), child)),
+        );
+    }
+}
+
+/// Swaps each path inside of the parsed `arguments` and `subcommands` which was
+/// lossily converted from one of the `originals` back to the original, so the
+/// path given by the user survives parsing. Originals set to nothing were shared
+/// by multiple different inputs, so are left lossy
+fn restore_paths<'a>(
+    arguments: &mut [ParsedArgument<'a>],
+    subcommands: &mut [ParsedSubcommand<'a>],
+    originals: &HashMap<String, Option<OsString>>,
+) {
+    let restore = |path: &mut PathBuf| {
+        if let Some(Some(original)) = path.to_str().and_then(|lossy| originals.get(lossy)) {
+            *path = PathBuf::from(original);
+        }
+    };
+    let mut stack: Vec<&mut [ParsedArgument<'a>]> = vec![arguments];
+    let mut subcommands: Vec<&mut ParsedSubcommand<'a>> = subcommands.iter_mut().collect();
+
+    while let Some(subcommand) = subcommands.pop() {
+        stack.push(&mut subcommand.arguments);
+        subcommands.extend(subcommand.subcommands.iter_mut());
+    }
+
+    for parsed_argument in stack.into_iter().flatten() {
+        match &mut parsed_argument.data {
+            Data::Path(path) => restore(path),
+            Data::Paths(paths) => paths.iter_mut().for_each(restore),
+            _ => (),
+        }
+    }
+}
+
+/// Sets [ParsedArgument::occurrences] for every `parsed` argument to the amount
+/// of times the same argument was given
+fn count_occurrences(parsed: &mut [ParsedArgument]) {
+    let ids: Vec<usize> = parsed
+        .iter()
+        .map(|parsed_argument| parsed_argument.id)
+        .collect();
+
+    for parsed_argument in parsed.iter_mut() {
+        parsed_argument.occurrences = ids.iter().filter(|id| **id == parsed_argument.id).count();
+    }
+}
+
+/// Checks that no `parsed` argument was given more often than its
+/// [Argument::max_occurrences] allows, which must be done before repeats are
+/// applied so every occurrence is still counted
+fn check_occurrences(parsed: &[ParsedArgument]) -> Result<(), ParseError> {
+    for parsed_argument in parsed.iter() {
+        match parsed_argument.inner.max_occurrences {
+            Some(max) if parsed_argument.occurrences > max => {
+                return Err(ParseError::TooManyOccurrences {
+                    call: parsed_argument.inner.display_call(),
+                    max,
+                    found: parsed_argument.occurrences,
+                })
+            }
+            _ => (),
+        }
+    }
+
+    Ok(())
+}
+
+/// Applies the [RepeatPolicy] of every argument given more than once to the
+/// `parsed` arguments, see [Argument::repeats]
+fn apply_repeats(parsed: &mut Vec<ParsedArgument>) -> Result<(), ParseError> {
+    let mut index = 0;
+
+    while index < parsed.len() {
+        let (id, argument) = (parsed[index].id, parsed[index].inner);
+        let repeated = parsed[index + 1..].iter().any(|later| later.id == id);
+
+        match argument.repeats {
+            _ if !repeated => (),
+            RepeatPolicy::Each => (),
+            RepeatPolicy::ErrorOnDuplicate => {
+                return Err(ParseError::Duplicate(argument.display_call()))
+            }
+            RepeatPolicy::LastWins => {
+                parsed.remove(index);
+                continue;
+            }
+            RepeatPolicy::Append => {
+                let mut occurrences = vec![parsed[index].data.clone()];
+                let mut later = index + 1;
+
+                while later < parsed.len() {
+                    if parsed[later].id == id {
+                        occurrences.push(parsed.remove(later).data);
+                    } else {
+                        later += 1;
+                    }
+                }
+
+                parsed[index].data = Data::append(&argument.input, occurrences);
+            }
+        }
+
+        index += 1;
+    }
+
+    Ok(())
+}
+
+/// Finds the argument with the given `call` in the passed `arguments`
+///
+/// If multiple arguments share the same call due to a mistake in the definition,
+/// the first declared argument is always used and a warning is written to the
+/// `warnings` buffer (typically [std::io::stderr]), so parsing is predictable
+/// even if [CliMake::validate] isn't used. Failing to write warnings is ignored.
+fn find_argument<'a>(
+    call: &CallType,
+    arguments: &[&'a Argument<'a>],
+    warnings: &mut impl Write,
+) -> Option<&'a Argument<'a>> {
+    let mut found = arguments.iter().filter(|argument| argument.has_call(call));
+    let first = found.next().copied();
+
+    if found.next().is_some() {
+        warnings
+            .write_all(format!("{}\n", message(Key::DuplicateCallWarning, &[call])).as_bytes())
+            .ok();
+    }
+
+    first
+}
+
+/// Maps a long `call` using an old spelling from [CliMake::rename_map] to its
+/// canonical call, unless one of the `arguments` at this level or their
+/// `parents` has the old spelling as a call itself. A deprecation warning is
+/// written to the `warnings` buffer if [CliMake::warn_renamed] is set, with
+/// failing to write it being ignored
+fn rename_call(
+    cli: &CliMake,
+    call: CallType,
+    arguments: &[&Argument],
+    parents: &[(&str, &[&Argument])],
+    warnings: &mut impl Write,
+) -> CallType {
+    let canonical = match &call {
+        CallType::Long(name) => cli
+            .renames
+            .iter()
+            .find(|(old, _)| old == name)
+            .map(|(_, canonical)| CallType::Long(canonical.to_string())),
+        CallType::Short(_) => None,
+    };
+    let is_defined = || {
+        arguments.iter().any(|argument| argument.has_call(&call))
+            || find_parent_argument(&call, parents).is_some()
+    };
+
+    match canonical {
+        Some(canonical) if !is_defined() => {
+            trace(
+                cli,
+                call.to_string(),
+                format_args!("renamed to {}", canonical),
+            );
+
+            if cli.warn_renamed {
+                warnings
+                    .write_all(
+                        format!("{}\n", message(Key::RenamedWarning, &[&call, &canonical]))
+                            .as_bytes(),
+                    )
+                    .ok();
+            }
+
+            canonical
+        }
+        _ => call,
+    }
+}
+
+/// Gets the call and any value attached using `:` from an `input` given in the
+/// windows style, e.g. `/v` or `/output:file`, see [CliMake::slash_calls].
+/// Inputs which don't call help (if enabled) or one of the `arguments` at this
+/// level or their `parents` give nothing, so they're still positional (e.g.
+/// absolute paths)
+fn slash_call(
+    input: &str,
+    help: bool,
+    arguments: &[&Argument],
+    parents: &[(&str, &[&Argument])],
+) -> Option<(CallType, Option<String>)> {
+    let (name, inline) = match input.strip_prefix('/')?.split_once(':') {
+        Some((name, value)) => (name, Some(value.to_string())),
+        None => (input.strip_prefix('/')?, None),
+    };
+    let mut chars = name.chars();
+    let call = match (chars.next()?, chars.next()) {
+        ('?', None) => CallType::Short('h'),
+        (c, None) => CallType::Short(c),
+        _ => CallType::Long(name.to_string()),
+    };
+    let is_help = help && (matches!(&call, CallType::Short('h')) || name == "help");
+
+    let is_known = arguments.iter().any(|argument| argument.has_call(&call))
+        || find_parent_argument(&call, parents).is_some();
+
+    (is_help || is_known).then_some((call, inline))
+}
+
+/// Gets the long call of the `arguments` at this level or of their `parents`
+/// (including help) which `name` matches ignoring case, see
+/// [CliMake::case_insensitive]. Names which match exactly or don't match any
+/// call ignoring case are given back as they are
+fn match_case(name: String, arguments: &[&Argument], parents: &[(&str, &[&Argument])]) -> String {
+    let arguments: Vec<&Argument> = arguments
+        .iter()
+        .chain(
+            parents
+                .iter()
+                .rev()
+                .flat_map(|(_, arguments)| arguments.iter()),
+        )
+        .copied()
+        .collect();
+
+    if arguments
+        .iter()
+        .any(|argument| argument.has_call(&CallType::Long(name.clone())))
+    {
+        return name;
+    }
+
+    let mut longs = vec![];
+
+    for argument in arguments {
+        for long in argument.longs.iter() {
+            longs.push(long.clone());
+
+            if argument.negatable && argument.input == Input::None {
+                longs.push(format!("no-{}", long));
+            }
+        }
+    }
+
+    let lowercase = name.to_lowercase();
+    longs
+        .into_iter()
+        .chain(vec!["help".to_string(), "help-search".to_string()])
+        .find(|long| long.to_lowercase() == lowercase)
+        .unwrap_or(name)
+}
+
+/// Expands an abbreviated long `call` into the full long call of the only one of
+/// the `arguments` it's a prefix of, see [CliMake::abbreviations]. Calls which
+/// match exactly or aren't a prefix of any call are given back as they are
+fn expand_abbreviation(call: CallType, arguments: &[&Argument]) -> Result<CallType, ParseError> {
+    let prefix = match &call {
+        CallType::Long(prefix) if !arguments.iter().any(|argument| argument.has_call(&call)) => {
+            prefix
+        }
+        _ => return Ok(call),
+    };
+    let mut candidates: Vec<(usize, String)> = vec![];
+
+    for argument in arguments.iter().filter(|argument| argument.is_available()) {
+        for long in argument.longs.iter() {
+            let negation = format!("no-{}", long);
+            let is_negatable = argument.negatable && argument.input == Input::None;
+
+            if long.starts_with(prefix.as_str()) {
+                candidates.push((argument.id(), long.clone()));
+            }
+            if is_negatable && negation.starts_with(prefix.as_str()) {
+                candidates.push((argument.id(), negation));
+            }
+        }
+    }
+
+    match candidates.first() {
+        Some((id, long)) if candidates.iter().all(|(other, _)| other == id) => {
+            Ok(CallType::Long(long.clone()))
+        }
+        Some(_) => Err(ParseError::AmbiguousCall {
+            call: call.to_string(),
+            candidates: candidates
+                .into_iter()
+                .map(|(_, long)| format!("--{}", long))
+                .collect(),
+        }),
+        None => Ok(call),
+    }
+}
+
+/// Finds the argument with the given `call` in the closest of the `parents` of
+/// the level currently being parsed, alongside the index of the parent it was
+/// found in. Parents are named and ordered from the root cli downwards
+fn find_parent_argument<'a>(
+    call: &CallType,
+    parents: &[(&str, &[&'a Argument<'a>])],
+) -> Option<(usize, &'a Argument<'a>)> {
+    parents
+        .iter()
+        .enumerate()
+        .rev()
+        .find_map(|(index, (_, arguments))| {
+            arguments
+                .iter()
+                .find(|argument| argument.has_call(call))
+                .map(|argument| (index, *argument))
+        })
+}
+
+/// Finds `name`'d subcommand in the passed `subcommands`
+fn find_subcommand<'a>(
+    name: impl AsRef<str>,
+    subcommands: &[&'a Subcommand<'a>],
+) -> Option<&'a Subcommand<'a>> {
+    subcommands
+        .iter()
+        .find(|subcommand| name.as_ref() == subcommand.name)
+        .copied()
+}
+
+/// Finds `name`'d subcommand in the passed `subcommands` like [find_subcommand],
+/// falling back to ignoring case if set by [CliMake::case_insensitive]
+fn find_subcommand_case<'a>(
+    cli: &CliMake,
+    name: &str,
+    subcommands: &[&'a Subcommand<'a>],
+) -> Option<&'a Subcommand<'a>> {
+    find_subcommand(name, subcommands).or_else(|| {
+        let lowercase = name.to_lowercase();
+
+        subcommands
+            .iter()
+            .filter(|_| cli.case_insensitive)
+            .find(|subcommand| subcommand.name.to_lowercase() == lowercase)
+            .copied()
+    })
+}
+
+/// Finds `name`'d subcommand like [find_subcommand_case], falling back to the
+/// only subcommand `name` is a prefix of if set by
+/// [CliMake::abbreviate_subcommands]. Prefixes of more than one subcommand are
+/// rejected as being ambiguous
+fn find_subcommand_prefix<'a>(
+    cli: &CliMake,
+    name: &str,
+    subcommands: &[&'a Subcommand<'a>],
+) -> Result<Option<&'a Subcommand<'a>>, ParseError> {
+    if let Some(subcommand) = find_subcommand_case(cli, name, subcommands) {
+        return Ok(Some(subcommand));
+    }
+    if !cli.abbreviate_subcommands || name.is_empty() {
+        return Ok(None);
+    }
+
+    let normalise = |name: &str| match cli.case_insensitive {
+        true => name.to_lowercase(),
+        false => name.to_string(),
+    };
+    let prefix = normalise(name);
+    let candidates: Vec<&'a Subcommand<'a>> = subcommands
+        .iter()
+        .filter(|subcommand| subcommand.is_available())
+        .filter(|subcommand| normalise(subcommand.name).starts_with(&prefix))
+        .copied()
+        .collect();
+
+    match candidates.as_slice() {
+        [] => Ok(None),
+        [subcommand] => {
+            trace(cli, name, format_args!("expanded to {}", subcommand.name));
+            Ok(Some(subcommand))
+        }
+        _ => Err(ParseError::AmbiguousSubcommand {
+            name: name.to_string(),
+            candidates: candidates
+                .iter()
+                .map(|subcommand| subcommand.name.to_string())
+                .collect(),
+        }),
+    }
+}
+
+impl<'a> CliMake<'a> {
+    /// Checks if parsing decisions should be traced, from either [CliMake::debug]
+    /// or the `CLIMAKE_DEBUG` environment variable being set to `1`
+    fn debugging(&self) -> bool {
+        self.debug || env::var_os("CLIMAKE_DEBUG").is_some_and(|value| value == "1")
+    }
+
+    /// Traces a single parsing decision made for an `input` to the given buffer
+    /// (typically [std::io::stderr]) if [CliMake::debugging] is enabled
+    ///
+    /// # Example
+    ///
+    /// What this may look like:
+    ///
+    /// ```none
+    /// [climake] '--verbose' matched as argument --verbose
+    /// ```
+    pub(crate) fn trace(
+        &self,
+        input: impl AsRef<str>,
+        decision: impl fmt::Display,
+        buf: &mut impl Write,
+    ) -> std::io::Result<()> {
+        if self.debugging() {
+            buf.write_fmt(format_args!(
+                "[climake] '{}' {}\n",
+                input.as_ref(),
+                decision
+            ))?;
+        }
+
+        Ok(())
+    }
+
+    /// Parses all arguments from a custom iterator into a result, leaving any
+    /// errors or requests for help to the caller. The first input is expected to
+    /// be the executable, as with [env::args]
+    ///
+    /// Requests for help are given as a [ParseError::Help] containing the
+    /// rendered help (and likewise for [ParseError::Version]), so it may be
+    /// shown however the caller likes.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use climake::prelude::*;
+    ///
+    /// let cli = CliMake::new("tool", vec![], vec![], None, None);
+    /// let inputs = vec!["tool", "--unknown"].into_iter().map(String::from);
+    ///
+    /// match cli.try_parse_custom(inputs) {
+    ///     Ok(parsed) => println!("Parsed {:?}", parsed),
+    ///     Err(error) => assert_eq!(error.to_string(), "No such argument '--unknown'"),
+    /// }
+    /// ```
+    pub fn try_parse_custom(
+        &'a self,
+        inputs: impl IntoIterator<Item = String>,
+    ) -> Result<ParsedCli<'a>, ParseError> {
+        self.diagnose(inputs.into_iter().collect())
+            .map_err(|diagnostic| *diagnostic.error)
+    }
+
+    /// Parses all arguments from the given `inputs` like
+    /// [CliMake::try_parse_custom], keeping the inputs which caused any error
+    /// alongside it so they may be pointed out
+    fn diagnose(&'a self, inputs: Vec<String>) -> Result<ParsedCli<'a>, Diagnostic<'a>> {
+        let applet = inputs
+            .first()
+            .filter(|_| self.multicall)
+            .and_then(|invoked| self.multicall_subcommand(invoked));
+        let mut lexer = lex(inputs.iter().skip(1).cloned());
+        let mut parsed_level = parse_levels(self, Level::root(self), applet, &mut lexer).map_err(
+            |(error, spans, subcommand)| Diagnostic {
+                error: Box::new(error),
+                spans,
+                inputs: inputs.clone(),
+                subcommand,
+            },
+        )?;
+        fill_sinks(&parsed_level.arguments, &parsed_level.subcommands);
+        spread_globals(&parsed_level.arguments, &mut parsed_level.subcommands);
+
+        Ok(ParsedCli {
+            subcommands: parsed_level.subcommands,
+            arguments: parsed_level.arguments,
+            trailing: parsed_level.trailing,
+            unknown: parsed_level.unknown,
+            overrides: parsed_level.overrides,
+        })
+    }
+
+    /// Parses all arguments from a custom iterator of [OsString]s into a result,
+    /// see [CliMake::try_parse_custom] for more information
+    ///
+    /// Inputs which aren't valid utf8 are parsed lossily, apart from paths given
+    /// as their own input (i.e. not attached using `=` or split by
+    /// [Argument::value_delimiter]) which are kept exactly as given inside of
+    /// [Data::Path] and [Data::Paths]. This is the case on every platform, such
+    /// as for unpaired surrogates on windows. In the rare case of different
+    /// inputs being identical once lossily converted, paths given by them are
+    /// left lossy rather than risking the wrong path being used.
+    pub fn try_parse_custom_os(
+        &'a self,
+        inputs: impl IntoIterator<Item = OsString>,
+    ) -> Result<ParsedCli<'a>, ParseError> {
+        self.diagnose_os(inputs)
+            .map_err(|diagnostic| *diagnostic.error)
+    }
+
+    /// Parses all arguments from the given `inputs` like
+    /// [CliMake::try_parse_custom_os], see [CliMake::diagnose]
+    fn diagnose_os(
+        &'a self,
+        inputs: impl IntoIterator<Item = OsString>,
+    ) -> Result<ParsedCli<'a>, Diagnostic<'a>> {
+        // every input is recorded so a valid input which happens to match the lossy
+        // form of an invalid one makes that form ambiguous
+        let mut originals: HashMap<String, Option<OsString>> = HashMap::new();
+        let mut any_lossy = false;
+        let inputs: Vec<String> = inputs
+            .into_iter()
+            .map(|original| {
+                let lossy = original.to_string_lossy().into_owned();
+                any_lossy |= original.to_str().is_none();
+                let entry = originals
+                    .entry(lossy.clone())
+                    .or_insert_with(|| Some(original.clone()));
+
+                if entry.as_ref() != Some(&original) {
+                    *entry = None;
+                }
+
+                lossy
+            })
+            .collect();
+
+        let mut parsed = self.diagnose(inputs)?;
+        if any_lossy {
+            restore_paths(&mut parsed.arguments, &mut parsed.subcommands, &originals);
+        }
+
+        Ok(parsed)
+    }
+
+    /// Parses all arguments from a custom iterator, see [CliMake::parse] for
+    /// default parsing from [env::args_os]
+    ///
+    /// If help was requested, it's shown and the process exits successfully.
+    /// Any errors due to bad user input are shown alongside the usage of the cli
+    /// and a hint to use `--help`, with the process exiting with a code of `2`
+    /// or as set by [CliMake::error_code].
+    pub fn parse_custom(&'a self, inputs: impl IntoIterator<Item = String>) -> ParsedCli<'a> {
+        self.or_exit(self.diagnose(inputs.into_iter().collect()))
+    }
+
+    /// Parses all arguments from a custom iterator of [OsString]s, keeping paths
+    /// which aren't valid utf8 as described in [CliMake::try_parse_custom_os]
+    ///
+    /// Help and errors are shown in the same way as [CliMake::parse_custom].
+    pub fn parse_custom_os(&'a self, inputs: impl IntoIterator<Item = OsString>) -> ParsedCli<'a> {
+        self.or_exit(self.diagnose_os(inputs))
+    }
+
+    /// Parses all arguments from a single `line` into a result, which is split
+    /// into words like posix shells do before parsing. The line shouldn't
+    /// include the executable, unlike [CliMake::try_parse_custom]
+    ///
+    /// Words are split on whitespace, with single quotes, double quotes and
+    /// backslash escapes working as they do in shells. No other shell features
+    /// (e.g. variables) are expanded. This is useful for tests, repls and clis
+    /// invoked from config files.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use climake::prelude::*;
+    ///
+    /// let output = Argument::new("Output file", vec!['o'], vec!["out"], Input::Path);
+    /// let cli = CliMake::new("tool", vec![&output], vec![], None, None);
+    ///
+    /// let parsed = cli.try_parse_str("--out 'my file.txt'").unwrap();
+    /// assert_eq!(parsed.arguments[0].data.to_string(), "my file.txt");
+    /// ```
+    pub fn try_parse_str(&'a self, line: &str) -> Result<ParsedCli<'a>, ParseError> {
+        self.diagnose_str(line)
+            .map_err(|diagnostic| *diagnostic.error)
+    }
+
+    /// Parses all arguments from a single `line` like [CliMake::try_parse_str],
+    /// see [CliMake::diagnose]
+    fn diagnose_str(&'a self, line: &str) -> Result<ParsedCli<'a>, Diagnostic<'a>> {
+        let words = split_shell(line).map_err(ParseError::UnclosedQuote)?;
+        self.diagnose(
+            Some(self.name.to_string())
+                .into_iter()
+                .chain(words)
+                .collect(),
+        )
+    }
+
+    /// Parses all arguments from a single `line` split into words like posix
+    /// shells do, see [CliMake::try_parse_str] for more information
+    ///
+    /// Help and errors are shown in the same way as [CliMake::parse_custom].
+    pub fn parse_str(&'a self, line: &str) -> ParsedCli<'a> {
+        self.or_exit(self.diagnose_str(line))
+    }
+
+    /// Gets the subcommand selected by the name of the `invoked` executable when
+    /// [CliMake::multicall] is set, ignoring any directories or extension
+    pub(crate) fn multicall_subcommand(
+        &self,
+        invoked: impl AsRef<OsStr>,
+    ) -> Option<&'a Subcommand<'a>> {
+        let stem = Path::new(invoked.as_ref()).file_stem()?.to_str()?;
+        self.subcommands
+            .iter()
+            .find(|subcommand| subcommand.name == stem)
+            .copied()
+    }
+
+    /// Gets the parsed cli from a parsing `result`, otherwise showing help or the
+    /// error and exiting as described in [CliMake::parse_custom]
+    fn or_exit(&self, result: Result<ParsedCli<'a>, Diagnostic<'a>>) -> ParsedCli<'a> {
+        match result {
+            Ok(parsed) => {
+                if !self.embedded {
+                    self.show_tip();
+                }
+
+                parsed
+            }
+            Err(diagnostic)
+                if matches!(
+                    *diagnostic.error,
+                    ParseError::Help(_) | ParseError::Version(_)
+                ) =>
+            {
+                print!("{}", diagnostic.error);
+                process::exit(0)
+            }
+            Err(diagnostic) => {
+                self.error_msg(&diagnostic, &mut io::stderr()).ok();
+                process::exit(self.error_code)
+            }
+        }
+    }
+
+    /// Writes the message shown for a parse error, which is the usage of the
+    /// level of the cli the error came from followed by the error, the command
+    /// line with the inputs which caused it underlined (if known) and a hint to
+    /// use `--help` (unless disabled for that level using
+    /// [CliMake::disable_help_flag] or [Subcommand::disable_help_flag]), or only
+    /// the error on one line if [CliMake::quiet_errors] is set
+    pub(crate) fn error_msg(
+        &self,
+        diagnostic: &Diagnostic<'a>,
+        buf: &mut impl Write,
+    ) -> io::Result<()> {
+        let error = &diagnostic.error;
+
+        if self.quiet_errors {
+            let oneline = error.to_string().replace('\n', " ");
+            return buf.write_all(format!("{}\n", message(Key::Error, &[&oneline])).as_bytes());
+        }
+
+        self.header_msg(diagnostic.subcommand.map(|subcommand| subcommand.name), buf)?;
+        buf.write_all(format!("\n{}\n", message(Key::Error, &[error])).as_bytes())?;
+
+        if !diagnostic.spans.is_empty() {
+            self.spans_msg(diagnostic, buf)?;
+        }
+
+        let level = match diagnostic.subcommand {
+            Some(subcommand) => Level::of(subcommand),
+            None => Level::root(self),
+        };
+
+        if help_enabled(self, &level) {
+            buf.write_all(format!("{}\n", message(Key::HelpHint, &[])).as_bytes())?;
+        }
+
+        Ok(())
+    }
+
+    /// Writes the command line of a `diagnostic` with each input it spans
+    /// underlined using carets, e.g. `^^^^` below `--verbos`
+    fn spans_msg(&self, diagnostic: &Diagnostic<'a>, buf: &mut impl Write) -> io::Result<()> {
+        let executable = diagnostic
+            .inputs
+            .first()
+            .and_then(|invoked| Path::new(invoked).file_stem())
+            .map(|stem| stem.to_string_lossy().to_string())
+            .unwrap_or_else(|| self.name.to_string());
+        let words: Vec<(String, bool)> = Some(executable)
+            .into_iter()
+            .chain(diagnostic.inputs.iter().skip(1).cloned())
+            .map(|word| {
+                let quoted = platform_quote(&word);
+                let changed = quoted != word;
+                (if changed { quoted } else { word }, changed)
+            })
+            .collect();
+        let mut carets: Vec<char> = vec![];
+        let mut column = 0;
+
+        for (position, (word, quoted)) in words.iter().enumerate() {
+            for span in diagnostic
+                .spans
+                .iter()
+                .filter(|span| span.position == position)
+            {
+                // quoting shifts characters around, so underline the whole word
+                let range = match &span.range {
+                    Some(range) if !quoted => range.clone(),
+                    _ => 0..word.len(),
+                };
+                let start = column + word[..range.start].chars().count();
+                let width = word[range].chars().count().max(1);
+
+                if carets.len() < start + width {
+                    carets.resize(start + width, ' ');
+                }
+
+                carets[start..start + width].fill('^');
+            }
+
+            column += word.chars().count() + 1;
+        }
+
+        let carets: String = carets.into_iter().collect();
+        buf.write_all(
+            format!(
+                "\n{}{}\n{}{}\n\n",
+                self.tabbing,
+                words
+                    .iter()
+                    .map(|(word, _)| word.as_str())
+                    .collect::<Vec<&str>>()
+                    .join(" "),
+                self.tabbing,
+                carets
+            )
+            .as_bytes(),
+        )
+    }
+
+    /// Parses default arguments coming from [env::args_os], so paths which
+    /// aren't valid utf8 are kept as described in [CliMake::try_parse_custom_os]
+    pub fn parse(&'a self) -> ParsedCli<'a> {
+        self.parse_custom_os(env::args_os())
+    }
+
+    /// Parses all arguments from a custom iterator into an [Outcome], never
+    /// exiting the process. Nothing is printed either if [CliMake::embedded] is
+    /// set, with help and the version being given back to show as needed
+    pub fn outcome_custom(&'a self, inputs: impl IntoIterator<Item = String>) -> Outcome<'a> {
+        self.try_parse_custom(inputs).into()
+    }
+
+    /// Parses default arguments coming from [env::args_os] into an [Outcome],
+    /// see [CliMake::outcome_custom] for more information
+    pub fn outcome(&'a self) -> Outcome<'a> {
+        self.try_parse().into()
+    }
+
+    /// Parses default arguments coming from [env::args_os] into a result, see
+    /// [CliMake::try_parse_custom_os] for more information
+    pub fn try_parse(&'a self) -> Result<ParsedCli<'a>, ParseError> {
+        self.try_parse_custom_os(env::args_os())
+    }
+
+    /// Extracts only the given bootstrap calls (e.g. `--config` or `-v`) from the
+    /// `inputs` without a cli, ignoring everything else and never erroring. The
+    /// first input is expected to be the executable, as with [env::args]
+    ///
+    /// This allows applications to load a config file or set verbosity before
+    /// making the full cli, whose defaults may depend on it. Each of the `names`
+    /// is given alongside if it takes a value, and found calls are given back in
+    /// order alongside their value. This is the value attached using `=` or,
+    /// for calls taking a value, otherwise the next input if it isn't a call.
+    /// Calls after a `--` terminator are never found.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use climake::CliMake;
+    ///
+    /// let inputs = vec!["mytool", "-v", "add", "--config=my.toml", "serde"];
+    /// let names = &[("--config", true), ("-v", false)];
+    /// let found = CliMake::preparse(names, inputs.into_iter().map(String::from));
+    ///
+    /// assert_eq!(
+    ///     found,
+    ///     vec![("-v", None), ("--config", Some("my.toml".to_string()))]
+    /// );
+    /// ```
+    pub fn preparse<'n>(
+        names: &[(&'n str, bool)],
+        inputs: impl IntoIterator<Item = String>,
+    ) -> Vec<(&'n str, Option<String>)> {
+        let mut lexer = lex(inputs.into_iter().skip(1));
+        let mut found = vec![];
+        let next_value = |lexer: &mut Lexer<_>| match lexer.peek_raw().map(Token::classify) {
+            Some(Token::Positional(_)) => lexer.next_raw(),
+            _ => None,
+        };
+
+        while let Some(token) = lexer.next() {
+            match token {
+                Token::Long { name, inline_value } => {
+                    let call = format!("--{}", name);
+
+                    if let Some((name, takes_value)) = names.iter().find(|(name, _)| *name == call)
+                    {
+                        let value = inline_value
+                            .or_else(|| takes_value.then(|| next_value(&mut lexer)).flatten());
+                        found.push((*name, value));
+                    }
+                }
+                Token::ShortCluster(cluster) => {
+                    let amount = cluster.chars().count();
+
+                    for (ind, c) in cluster.chars().enumerate() {
+                        let call = format!("-{}", c);
+
+                        if let Some((name, takes_value)) =
+                            names.iter().find(|(name, _)| *name == call)
+                        {
+                            // only the last short call of a cluster may take a value
+                            let value = if *takes_value && ind + 1 == amount {
+                                next_value(&mut lexer)
+                            } else {
+                                None
+                            };
+                            found.push((*name, value));
+                        }
+                    }
+                }
+                Token::Terminator => break,
+                Token::Positional(_) => (),
+            }
+        }
+
+        found
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Makes owned inputs from string slices for easier testing
+    fn inputs(inputs: &[&str]) -> Vec<String> {
+        inputs.iter().map(|input| input.to_string()).collect()
+    }
+
+    /// Checks that [ParseError::SubcommandNotFound] lists available subcommands,
+    /// summarising them past [SUBCOMMANDS_LISTED]
+    #[test]
+    fn subcommand_not_found_msg() {
+        let names = |amount: usize| (0..amount).map(|ind| format!("s{}", ind)).collect();
+
+        assert_eq!(
+            ParseError::SubcommandNotFound {
+                name: "ad".to_string(),
+                available: vec![]
+            }
+            .to_string(),
+            "No such subcommand 'ad', no subcommands are available"
+        );
+        assert_eq!(
+            ParseError::SubcommandNotFound {
+                name: "ad".to_string(),
+                available: names(2)
+            }
+            .to_string(),
+            "No such subcommand 'ad', available subcommands are s0, s1"
+        );
+        assert_eq!(
+            ParseError::SubcommandNotFound {
+                name: "ad".to_string(),
+                available: names(8)
+            }
+            .to_string(),
+            "No such subcommand 'ad', available subcommands are s0, s1, s2, s3, s4 and 3 more"
+        );
+    }
+
+    /// Checks that [ParseError::InvalidChoice] suggests the closest possible value
+    #[test]
+    fn invalid_choice_msg() {
+        let choices = vec!["json".to_string(), "yaml".to_string()];
+
+        assert_eq!(
+            ParseError::InvalidChoice {
+                call: "--format".to_string(),
+                value: "jsn".to_string(),
+                choices: choices.clone()
+            }
+            .to_string(),
+            "'jsn' is not valid for --format; did you mean 'json'?"
+        );
+        assert_eq!(
+            ParseError::InvalidChoice {
+                call: "--format".to_string(),
+                value: "xml".to_string(),
+                choices
+            }
+            .to_string(),
+            "'xml' is not valid for --format; possible values are json, yaml"
+        );
+    }
+
+    /// Checks that [CliMake::trace] only writes when debugging is enabled
+    #[test]
+    fn trace_debug_only() -> std::io::Result<()> {
+        let mut chk_vec: Vec<u8> = vec![];
+        let mut cli = CliMake::new("example", vec![], vec![], "Trace check", None);
+
+        cli.trace("add", "matched as subcommand", &mut chk_vec)?;
+        if env::var_os("CLIMAKE_DEBUG").is_none() {
+            assert!(chk_vec.is_empty());
+        }
+
+        cli.debug(true)
+            .trace("add", "matched as subcommand", &mut chk_vec)?;
+        assert!(std::str::from_utf8(chk_vec.as_slice())
+            .unwrap()
+            .ends_with("[climake] 'add' matched as subcommand\n"));
+
+        Ok(())
+    }
+
+    /// Checks that [env_fallback] reports the variable which supplied a bad value
+    #[test]
+    fn env_fallback_errors() {
+        let mut arg = Argument::new("Ratio", vec!['r'], vec!["ratio"], Input::Float);
+        arg.env("CLIMAKE_TEST_ENV_FALLBACK");
+
+        assert!(matches!(env_fallback(&arg), Ok(None)));
+
+        env::set_var("CLIMAKE_TEST_ENV_FALLBACK", "half");
+        assert_eq!(
+            env_fallback(&arg).unwrap_err().to_string(),
+            "'half' is not a valid number for --ratio (from environment variable CLIMAKE_TEST_ENV_FALLBACK)"
+        );
+
+        env::set_var("CLIMAKE_TEST_ENV_FALLBACK", "0.5");
+        assert_eq!(
+            env_fallback(&arg)
+                .ok()
+                .flatten()
+                .map(|parsed| (parsed.data, parsed.is_present)),
+            Some((Data::Float(0.5), false))
+        );
+    }
+
+    /// Checks that [new_parsed_argument] runs validators and stashes the custom
+    /// item they give back
+    #[test]
+    fn validator_custom() {
+        let mut arg = Argument::new("Count", vec!['c'], vec!["count"], Input::Text);
+        arg.validator(|data| match data {
+            Data::Text(text) => text.parse::<u8>().map_err(|err| err.to_string()),
+            _ => Err("Expected text".to_string()),
+        });
+
+        let parsed = new_parsed_argument(&arg, Data::Text("12".to_string()))
+            .ok()
+            .unwrap();
+        assert!(parsed.is_present);
+        assert_eq!(parsed.get_custom::<u8>(), Some(&12));
+        assert_eq!(parsed.get_custom::<String>(), None);
+
+        assert_eq!(
+            new_parsed_argument(&arg, Data::Text("many".to_string()))
+                .unwrap_err()
+                .to_string(),
+            "Invalid value for --count: invalid digit found in string"
+        );
+    }
+
+    /// Checks that subcommands set with [Subcommand::help_if_empty] give help only
+    /// when they're invoked on their own
+    #[test]
+    fn help_if_empty() {
+        let inner = Subcommand::new("inner", vec![], vec![], None);
+        let mut add = Subcommand::new("add", vec![], vec![&inner], "Adds things");
+        add.help_if_empty = true;
+        let cli = CliMake::new("example", vec![], vec![&add], "Help check", None);
+
+        match cli.try_parse_custom(inputs(&["example", "add"])) {
+            Err(ParseError::Help(help)) => {
+                assert!(help.contains("add [OPTIONS]"));
+                assert!(help.contains("Adds things"));
+            }
+            _ => panic!("Expected help for an empty subcommand"),
+        }
+
+        assert!(cli
+            .try_parse_custom(inputs(&["example", "add", "inner"]))
+            .is_ok());
+    }
+
+    /// Checks that [ParseError::MissingValue] shows the expected value
+    #[test]
+    fn missing_value_msg() {
+        assert_eq!(
+            ParseError::MissingValue {
+                call: "--output".to_string(),
+                expected: "path".to_string(),
+                found: None
+            }
+            .to_string(),
+            "Missing value for --output [path]"
+        );
+        assert_eq!(
+            ParseError::MissingValue {
+                call: "--output".to_string(),
+                expected: "path".to_string(),
+                found: Some("-v".to_string())
+            }
+            .to_string(),
+            "Missing value for --output [path], found argument '-v' instead"
+        );
+    }
+
+    /// Checks that [find_argument] always uses the first declared argument for
+    /// duplicated calls and warns about them
+    #[test]
+    fn find_argument_duplicates() {
+        let mut chk_vec: Vec<u8> = vec![];
+
+        let first = Argument::new("First", vec!['a'], vec!["all"], Input::None);
+        let second = Argument::new("Second", vec!['a'], vec![], Input::None);
+        let arguments = vec![&first, &second];
+
+        let found = find_argument(&CallType::Long("all".to_string()), &arguments, &mut chk_vec);
+        assert_eq!(found.map(|argument| argument.help), Some(Some("First")));
+        assert!(chk_vec.is_empty());
+
+        let found = find_argument(&CallType::Short('a'), &arguments, &mut chk_vec);
+        assert_eq!(found.map(|argument| argument.help), Some(Some("First")));
+        assert_eq!(
+            std::str::from_utf8(chk_vec.as_slice()).unwrap(),
+            "Warning: -a is defined by multiple arguments, using the first declared\n"
+        );
+
+        assert!(find_argument(&CallType::Short('b'), &arguments, &mut chk_vec).is_none());
+    }
+
+    /// Checks that [order_arguments] only reorders for [ArgumentOrder::Declared],
+    /// keeping repeats in the order given
+    #[test]
+    fn argument_ordering() {
+        let first = Argument::new("First", vec!['a'], vec![], Input::None);
+        let second = Argument::new("Second", vec!['b'], vec![], Input::Text);
+        let declared = vec![&first, &second];
+        let parsed_with = |argument: &str| {
+            let argument = if argument == "a" { &first } else { &second };
+            new_parsed_argument(argument, Data::Text(String::new()))
+                .ok()
+                .unwrap()
+        };
+
+        let given = vec![parsed_with("b"), parsed_with("a")];
+        let mut parsed = given.clone();
+        order_arguments(ArgumentOrder::Given, &mut parsed, &declared);
+        assert_eq!(parsed, given);
+
+        let mut parsed = vec![parsed_with("b"), parsed_with("a"), parsed_with("b")];
+        parsed[0].data = Data::Text("one".to_string());
+        order_arguments(ArgumentOrder::Declared, &mut parsed, &declared);
+        assert!(parsed[0] == first && parsed[1] == second && parsed[2] == second);
+        assert_eq!(parsed[1].data, Data::Text("one".to_string()));
+    }
+
+    /// Checks that [find_parent_argument] finds the closest parent with the call
+    #[test]
+    fn parent_arguments() {
+        let verbose = Argument::new("Verbose", vec!['v'], vec![], Input::None);
+        let root_force = Argument::new("Force", vec!['f'], vec![], Input::None);
+        let force = Argument::new("Force", vec!['f'], vec![], Input::None);
+        let root = vec![&verbose, &root_force];
+        let add = vec![&force];
+        let parents = vec![("mypkg", root.as_slice()), ("add", add.as_slice())];
+
+        let found = find_parent_argument(&CallType::Short('f'), &parents);
+        assert_eq!(
+            found.map(|(index, argument)| (index, argument.id())),
+            Some((1, force.id()))
+        );
+
+        let found = find_parent_argument(&CallType::Short('v'), &parents);
+        assert_eq!(found.map(|(index, _)| index), Some(0));
+
+        assert!(find_parent_argument(&CallType::Short('q'), &parents).is_none());
+        assert_eq!(
+            ParseError::ParentArgument {
+                call: "-v".to_string(),
+                owner: "mypkg".to_string(),
+                subcommand: "add".to_string()
+            }
+            .to_string(),
+            "-v isn't an argument of 'add' but exists for 'mypkg', so must be given before 'add'"
+        );
+    }
+
+    /// Checks that [ParseError::EqualsRequired] shows how to attach the value
+    #[test]
+    fn equals_required_msg() {
+        assert_eq!(
+            ParseError::EqualsRequired("--level".to_string()).to_string(),
+            "A value for --level must be attached using '=', e.g. --level=value"
+        );
+    }
+
+    /// Checks that [CliMake::try_parse_custom] parses arguments given in each
+    /// way, including inside of subcommands
+    #[test]
+    fn parse_arguments() {
+        let verbose = Argument::new("Verbose", vec!['v'], vec!["verbose"], Input::None);
+        let quiet = Argument::new("Quiet", vec!['q'], vec![], Input::None);
+        let output = Argument::new("Output", vec!['o'], vec!["output"], Input::Path);
+        let ratio = Argument::new("Ratio", vec!['r'], vec!["ratio"], Input::Float);
+        let mut files = Argument::new("Files", vec!['f'], vec![], Input::Path);
+        files.arity(1..);
+        let inner = Subcommand::new("inner", vec![], vec![], None);
+        let add = Subcommand::new("add", vec![&files], vec![&inner], "Adds things");
+        let cli = CliMake::new(
+            "example",
+            vec![&verbose, &quiet, &output, &ratio],
+            vec![&add],
+            "Parse check",
+            None,
+        );
+
+        let parsed = cli
+            .try_parse_custom(inputs(&["example", "-vqoout.txt", "--ratio", "-0.5"]))
+            .ok()
+            .unwrap();
+        assert!(parsed.arguments[0] == verbose && parsed.arguments[1] == quiet);
+        assert_eq!(parsed.arguments[2].data, Data::Path("out.txt".into()));
+        assert_eq!(parsed.arguments[3].data, Data::Float(-0.5));
+
+        let parsed = cli
+            .try_parse_custom(inputs(&[
+                "example",
+                "--output=a",
+                "add",
+                "-f",
+                "a",
+                "b",
+                "inner",
+            ]))
+            .ok()
+            .unwrap();
+        assert_eq!(parsed.arguments[0].data, Data::Path("a".into()));
+        assert!(parsed.subcommands[0] == add);
+        assert_eq!(
+            parsed.subcommands[0].arguments[0].data,
+            Data::Paths(vec!["a".into(), "b".into()])
+        );
+        assert!(parsed.subcommands[0].subcommands[0] == inner);
+    }
+
+    /// Checks that short clusters whose value would only be made of other short
+    /// calls are rejected as ambiguous, unless attached using `=`
+    #[test]
+    fn parse_ambiguous_cluster() {
+        let output = Argument::new("Output", vec!['o'], vec![], Input::Text);
+        let verbose = Argument::new("Verbose", vec!['v'], vec![], Input::None);
+        let cli = CliMake::new(
+            "example",
+            vec![&output, &verbose],
+            vec![],
+            "Cluster check",
+            None,
+        );
+
+        assert_eq!(
+            cli.try_parse_custom(inputs(&["example", "-ov"]))
+                .err()
+                .unwrap()
+                .to_string(),
+            "-o takes a value, so 'v' in '-ov' would be taken as its value rather than as arguments; use '-o=v' or '-o v' for a value, or '-v -o <value>' for separate arguments"
+        );
+
+        for given in &[
+            &["example", "-o=v"][..],
+            &["example", "-oval"],
+            &["example", "-vo", "x"],
+        ] {
+            assert!(cli.try_parse_custom(inputs(given)).is_ok());
+        }
+    }
+
+    /// Checks that every missing required argument is reported at once, for the
+    /// cli and the subcommand which was invoked
+    #[test]
+    fn parse_missing_required() {
+        let mut output = Argument::new("Output", vec!['o'], vec!["output"], Input::Path);
+        output.required(true);
+        let mut input = Argument::new("Input", vec!['i'], vec![], Input::Path);
+        input.required(true);
+        let mut name = Argument::new("Name", vec![], vec!["name"], Input::Text);
+        name.required(true);
+        let add = Subcommand::new("add", vec![&name], vec![], "Adds things");
+        let cli = CliMake::new(
+            "example",
+            vec![&output, &input],
+            vec![&add],
+            "Required check",
+            None,
+        );
+
+        assert_eq!(
+            cli.try_parse_custom(inputs(&["example", "add"]))
+                .err()
+                .unwrap()
+                .to_string(),
+            "Missing required arguments --output, -i and --name for 'add'"
+        );
+        assert_eq!(
+            cli.try_parse_custom(inputs(&["example", "-i", "in", "add", "--name", "x"]))
+                .err()
+                .unwrap()
+                .to_string(),
+            "Missing required argument --output"
+        );
+    }
+
+    /// Checks that [CliMake::try_parse_custom] gives the apt errors for bad input
+    #[test]
+    fn parse_errors() {
+        let verbose = Argument::new("Verbose", vec!['v'], vec!["verbose"], Input::None);
+        let mut output = Argument::new("Output", vec!['o'], vec!["output"], Input::Path);
+        output.required(true);
+        let mut level = Argument::new("Level", vec![], vec!["level"], Input::Text);
+        level.require_equals(true);
+        let add = Subcommand::new("add", vec![], vec![], "Adds things");
+        let cli = CliMake::new(
+            "example",
+            vec![&verbose, &output, &level],
+            vec![&add],
+            "Error check",
+            None,
+        );
+        let error = |given: &[&str]| match cli.try_parse_custom(inputs(given)) {
+            Err(error) => error.to_string(),
+            Ok(_) => panic!("Expected {:?} to fail", given),
+        };
+
+        assert_eq!(
+            error(&["example", "--verbos", "-o", "out"]),
+            "No such argument '--verbos'; did you mean '--verbose'?"
+        );
+        assert_eq!(error(&["example", "-o"]), "Missing value for -o [path]");
+        assert_eq!(
+            error(&["example", "-o", "-v"]),
+            "Missing value for -o [path], found argument '-v' instead"
+        );
+        assert_eq!(
+            error(&["example", "-v"]),
+            "Missing required argument --output"
+        );
+        assert_eq!(
+            error(&["example", "-o", "out", "--level", "3"]),
+            "A value for --level must be attached using '=', e.g. --level=value"
+        );
+        assert_eq!(
+            error(&["example", "-o", "out", "--verbose=yes"]),
+            "--verbose doesn't take a value, but was given 'yes'"
+        );
+        assert_eq!(
+            error(&["example", "-o", "out", "add", "-v"]),
+            "-v isn't an argument of 'add' but exists for 'example', so must be given before 'add'"
+        );
+        assert!(matches!(
+            cli.try_parse_custom(inputs(&["example", "--help"])),
+            Err(ParseError::Help(_))
+        ));
+    }
+
+    /// Checks that parent arguments given after a subcommand are passed up to
+    /// the parent when [CliMake::bubble_arguments] is set
+    #[test]
+    fn parse_bubbled() {
+        let verbose = Argument::new("Verbose", vec!['v'], vec![], Input::None);
+        let force = Argument::new("Force", vec!['f'], vec![], Input::None);
+        let add = Subcommand::new("add", vec![&force], vec![], "Adds things");
+        let mut cli = CliMake::new("example", vec![&verbose], vec![&add], "Bubble check", None);
+        cli.bubble_arguments(true);
+
+        let parsed = cli
+            .try_parse_custom(inputs(&["example", "add", "-vf"]))
+            .ok()
+            .unwrap();
+        assert!(parsed.arguments.len() == 1 && parsed.arguments[0] == verbose);
+        assert!(parsed.subcommands[0].arguments[0] == force);
+    }
+
+    /// Checks that bubbled parent arguments using the automatic `-h` and `-V`
+    /// calls are matched rather than showing help or the version
+    #[test]
+    fn parse_bubbled_automatic_calls() {
+        let host = Argument::new("Host", vec!['h'], vec![], Input::Text);
+        let verbose = Argument::new("Verbose", vec!['V'], vec![], Input::None);
+        let add = Subcommand::new("add", vec![], vec![], "Adds things");
+        let mut cli = CliMake::new("tool", vec![&host, &verbose], vec![&add], None, "1.0");
+        cli.bubble_arguments(true);
+
+        let parsed = cli
+            .try_parse_custom(inputs(&["tool", "add", "-h", "x", "-V"]))
+            .ok()
+            .unwrap();
+        assert_eq!(parsed.arguments.len(), 2);
+        assert!(parsed.arguments[0] == host && parsed.arguments[1] == verbose);
+        assert_eq!(parsed.arguments[0].data, Data::Text("x".to_string()));
+    }
+
+    /// Checks that [CliMake::case_insensitive] matches long calls and subcommands
+    /// ignoring case, whilst preferring exact matches
+    #[test]
+    fn parse_case_insensitive() {
+        let verbose = Argument::new("Verbose", vec!['v'], vec!["verbose"], Input::None);
+        let upper = Argument::new("Upper", vec![], vec!["Verbose"], Input::None);
+        let add = Subcommand::new("add", vec![], vec![], None);
+        let mut cli = CliMake::new("example", vec![&verbose], vec![&add], "Case check", None);
+
+        assert!(cli
+            .try_parse_custom(inputs(&["example", "--VERBOSE"]))
+            .is_err());
+
+        cli.case_insensitive(true);
+        let parsed = cli
+            .try_parse_custom(inputs(&["example", "--VERBOSE", "Add"]))
+            .ok()
+            .unwrap();
+        assert!(parsed.arguments[0] == verbose && parsed.subcommands[0] == add);
+        assert!(matches!(
+            cli.try_parse_custom(inputs(&["example", "--Help"])),
+            Err(ParseError::Help(_))
+        ));
+
+        cli.add_arg(&upper);
+        let parsed = cli
+            .try_parse_custom(inputs(&["example", "--Verbose"]))
+            .ok()
+            .unwrap();
+        assert!(parsed.arguments[0] == upper);
+    }
+
+    /// Checks that [CliMake::abbreviations] accepts unambiguous prefixes of long
+    /// calls, rejecting those which are ambiguous
+    #[test]
+    fn parse_abbreviations() {
+        let mut verbose =
+            Argument::new("Verbose", vec![], vec!["verbose", "verbosity"], Input::None);
+        verbose.negatable(true);
+        let version = Argument::new("Version", vec![], vec!["version"], Input::None);
+        let mut cli = CliMake::new(
+            "example",
+            vec![&verbose, &version],
+            vec![],
+            "Abbreviation check",
+            None,
+        );
+        let error = |cli: &CliMake, given: &str| {
+            cli.try_parse_custom(inputs(&["example", given]))
+                .err()
+                .unwrap()
+                .to_string()
+        };
+
+        assert_eq!(error(&cli, "--verb"), "No such argument '--verb'");
+
+        cli.abbreviations(true);
+        let data = |given: &str| {
+            cli.try_parse_custom(inputs(&["example", given]))
+                .ok()
+                .unwrap()
+                .arguments[0]
+                .clone()
+        };
+
+        assert_eq!(data("--verb").data, Data::Bool(true));
+        assert_eq!(data("--no-v").data, Data::Bool(false));
+        assert!(data("--versi") == version);
+        assert_eq!(
+            error(&cli, "--ver"),
+            "'--ver' is ambiguous, it could mean --verbose, --verbosity, --version"
+        );
+    }
+
+    /// Checks that [CliMake::abbreviate_subcommands] resolves each segment of a
+    /// subcommand path from an unambiguous prefix, rejecting those which are
+    /// ambiguous
+    #[test]
+    fn parse_abbreviated_subcommands() {
+        let add = Subcommand::new("add", vec![], vec![], None);
+        let post = Subcommand::new("post", vec![], vec![&add], None);
+        let pull = Subcommand::new("pull", vec![], vec![], None);
+        let mut cli = CliMake::new("example", vec![], vec![&post, &pull], None, None);
+
+        assert!(cli
+            .try_parse_custom(inputs(&["example", "po", "a"]))
+            .is_err());
+
+        cli.abbreviate_subcommands(true);
+        let parsed = cli
+            .try_parse_custom(inputs(&["example", "po", "a"]))
+            .ok()
+            .unwrap();
+        assert_eq!(parsed.subcommands[0].inner, &post);
+        assert_eq!(parsed.subcommands[0].subcommands[0].inner, &add);
+        assert_eq!(
+            cli.try_parse_custom(inputs(&["example", "p", "a"]))
+                .err()
+                .unwrap()
+                .to_string(),
+            "'p' is ambiguous, it could mean subcommands post, pull"
+        );
+        assert!(matches!(
+            cli.try_parse_custom(inputs(&["example", "help", "po", "a"])),
+            Err(ParseError::Help(_))
+        ));
+    }
+
+    /// Checks that [Subcommand::bubble_arguments] overrides the cli's setting for
+    /// arguments given directly after the subcommand
+    #[test]
+    fn parse_bubbled_subcommand() {
+        let verbose = Argument::new("Verbose", vec!['v'], vec![], Input::None);
+        let mut log = Subcommand::new("log", vec![], vec![], "Shows logs");
+        log.bubble_arguments(true);
+        let mut add = Subcommand::new("add", vec![], vec![], "Adds things");
+        add.bubble_arguments(false);
+        let mut cli = CliMake::new(
+            "example",
+            vec![&verbose],
+            vec![&log, &add],
+            "Bubble check",
+            None,
+        );
+
+        assert!(cli
+            .try_parse_custom(inputs(&["example", "log", "-v"]))
+            .is_ok());
+
+        cli.bubble_arguments(true);
+        assert_eq!(
+            cli.try_parse_custom(inputs(&["example", "add", "-v"]))
+                .err()
+                .unwrap()
+                .to_string(),
+            "-v isn't an argument of 'add' but exists for 'example', so must be given before 'add'"
+        );
+    }
+
+    /// Checks that [CliMake::try_parse_custom] fills in missing arguments from
+    /// their environment variable, after those which were given
+    #[test]
+    fn parse_env_fallback() {
+        let mut ratio = Argument::new("Ratio", vec!['r'], vec![], Input::Float);
+        ratio.env("CLIMAKE_TEST_PARSE_ENV");
+        let verbose = Argument::new("Verbose", vec!['v'], vec![], Input::None);
+        let mut cli = CliMake::new("example", vec![&ratio, &verbose], vec![], "Env check", None);
+        cli.argument_order(ArgumentOrder::Declared);
+
+        env::set_var("CLIMAKE_TEST_PARSE_ENV", "0.5");
+        let parsed = cli
+            .try_parse_custom(inputs(&["example", "-v"]))
+            .ok()
+            .unwrap();
+
+        assert!(parsed.arguments[0] == verbose && parsed.arguments[0].is_present);
+        assert!(parsed.arguments[1] == ratio && !parsed.arguments[1].is_present);
+        assert_eq!(parsed.arguments[1].data, Data::Float(0.5));
+    }
+
+    /// Checks that environment variables overridden by given arguments are only
+    /// recorded when [CliMake::record_overrides] is set
+    #[test]
+    fn parse_record_overrides() {
+        let mut token = Argument::new("Token", vec![], vec!["token"], Input::Text);
+        token.env("CLIMAKE_TEST_OVERRIDES");
+        let deploy = Subcommand::new("deploy", vec![&token], vec![], None);
+        let mut cli = CliMake::new("example", vec![], vec![&deploy], None, None);
+
+        env::set_var("CLIMAKE_TEST_OVERRIDES", "from-env");
+        let given = inputs(&["example", "deploy", "--token", "given"]);
+        assert!(cli
+            .try_parse_custom(given.clone())
+            .ok()
+            .unwrap()
+            .overrides()
+            .is_empty());
+
+        cli.record_overrides(true);
+        assert_eq!(
+            cli.try_parse_custom(given).ok().unwrap().overrides(),
+            &[Override {
+                call: "--token".to_string(),
+                subcommand: Some("deploy".to_string()),
+                var: "CLIMAKE_TEST_OVERRIDES".to_string(),
+                value: "from-env".to_string(),
+            }]
+        );
+        assert!(cli
+            .try_parse_custom(inputs(&["example", "deploy"]))
+            .ok()
+            .unwrap()
+            .overrides()
+            .is_empty());
+    }
+
+    /// Checks that arguments and subcommands which aren't available are rejected
+    #[test]
+    fn parse_unavailable() {
+        let mut color = Argument::new("Color", vec!['c'], vec![], Input::None);
+        color.available_if(|| false);
+        let verbose = Argument::new("Verbose", vec!['v'], vec![], Input::None);
+        let mut service = Subcommand::new("service", vec![], vec![], "Windows only");
+        service.available_if(|| false);
+        let cli = CliMake::new(
+            "example",
+            vec![&color, &verbose],
+            vec![&service],
+            None,
+            None,
+        );
+
+        assert!(cli.try_parse_custom(inputs(&["example", "-v"])).is_ok());
+        assert_eq!(
+            cli.try_parse_custom(inputs(&["example", "-c"]))
+                .err()
+                .unwrap()
+                .to_string(),
+            "-c isn't available on this platform or configuration"
+        );
+        assert_eq!(
+            cli.try_parse_custom(inputs(&["example", "service"]))
+                .err()
+                .unwrap()
+                .to_string(),
+            "service isn't available on this platform or configuration"
+        );
+    }
+
+    /// Checks that arguments set with [Argument::negatable] are parsed into a
+    /// [Data::Bool] from either form
+    #[test]
+    fn parse_negatable() {
+        let mut color = Argument::new("Color", vec!['c'], vec!["color"], Input::None);
+        color.negatable(true);
+        let cli = CliMake::new("example", vec![&color], vec![], "Negate check", None);
+        let data = |given: &[&str]| {
+            cli.try_parse_custom(inputs(given))
+                .ok()
+                .unwrap()
+                .arguments
+                .into_iter()
+                .map(|parsed_argument| parsed_argument.data)
+                .collect::<Vec<Data>>()
+        };
+
+        assert_eq!(data(&["example", "--color"]), vec![Data::Bool(true)]);
+        assert_eq!(data(&["example", "-c"]), vec![Data::Bool(true)]);
+        assert_eq!(data(&["example", "--no-color"]), vec![Data::Bool(false)]);
+        assert!(cli
+            .try_parse_custom(inputs(&["example", "--no-color=yes"]))
+            .is_err());
+    }
+
+    /// Checks that [Argument::unique] removes repeated values across every
+    /// occurrence, counting how many were removed
+    #[test]
+    fn parse_unique() {
+        let mut include = Argument::new("Include", vec!['I'], vec![], Input::Path);
+        include
+            .arity(1..)
+            .repeats(RepeatPolicy::Append)
+            .unique(true);
+        let cli = CliMake::new("example", vec![&include], vec![], "Unique check", None);
+
+        let parsed = cli
+            .try_parse_custom(inputs(&["example", "-I", "a", "b", "a", "-I", "b", "c"]))
+            .ok()
+            .unwrap();
+        assert_eq!(
+            parsed.arguments[0].data,
+            Data::Paths(vec!["a".into(), "b".into(), "c".into()])
+        );
+        assert_eq!(parsed.arguments[0].duplicates, 2);
+    }
+
+    /// Checks that each [RepeatPolicy] is applied to repeated arguments
+    #[test]
+    fn parse_repeats() {
+        let mut include = Argument::new("Include", vec!['I'], vec![], Input::Path);
+        include.repeats(RepeatPolicy::Append);
+        let mut name = Argument::new("Name", vec!['n'], vec![], Input::Text);
+        name.repeats(RepeatPolicy::LastWins);
+        let mut output = Argument::new("Output", vec!['o'], vec![], Input::Text);
+        output.repeats(RepeatPolicy::ErrorOnDuplicate);
+        let cli = CliMake::new(
+            "example",
+            vec![&include, &name, &output],
+            vec![],
+            "Repeat check",
+            None,
+        );
+
+        let parsed = cli
+            .try_parse_custom(inputs(&[
+                "example", "-I", "a", "-n", "x", "-I", "b", "-n", "y", "-o", "z",
+            ]))
+            .ok()
+            .unwrap();
+        assert_eq!(parsed.arguments.len(), 3);
+        assert!(parsed.arguments[0] == include && parsed.arguments[0].occurrences == 2);
+        assert_eq!(
+            parsed.arguments[0].data,
+            Data::Paths(vec!["a".into(), "b".into()])
+        );
+        assert_eq!(parsed.arguments[1].data, Data::Text("y".to_string()));
+        assert_eq!(
+            cli.try_parse_custom(inputs(&["example", "-o", "a", "-o", "b"]))
+                .err()
+                .unwrap()
+                .to_string(),
+            "Argument -o may only be given once"
+        );
+    }
+
+    /// Checks that [Argument::bind] sinks are given the data of every occurrence
+    /// throughout the cli, only once parsing has succeeded
+    #[test]
+    fn parse_bind() {
+        let mut given: Vec<String> = vec![];
+        let mut jobs: usize = 1;
+
+        {
+            let mut include = Argument::new("Include", vec!['I'], vec![], Input::Text);
+            include.bind(|data| given.push(data.to_string()));
+            let mut jobs_arg = Argument::new("Jobs", vec!['j'], vec![], Input::Text);
+            jobs_arg.bind_to(&mut jobs);
+            let build = Subcommand::new("build", vec![&jobs_arg], vec![], None);
+            let cli = CliMake::new("example", vec![&include], vec![&build], None, None);
+
+            assert!(cli
+                .try_parse_custom(inputs(&["example", "-I", "a", "build", "--unknown"]))
+                .is_err());
+            cli.try_parse_custom(inputs(&[
+                "example", "-I", "a", "-I", "b", "build", "-j", "4",
+            ]))
+            .unwrap();
+        }
+
+        assert_eq!(given, vec!["a".to_string(), "b".to_string()]);
+        assert_eq!(jobs, 4);
+    }
+
+    /// Checks that arguments given more often than their
+    /// [Argument::max_occurrences] are rejected with the amount found
+    #[test]
+    fn parse_max_occurrences() {
+        let mut verbose = Argument::new("Verbose", vec!['v'], vec!["verbose"], Input::None);
+        verbose.max_occurrences(2);
+        let cli = CliMake::new("example", vec![&verbose], vec![], None, None);
+
+        let parsed = cli
+            .try_parse_custom(inputs(&["example", "-vv"]))
+            .ok()
+            .unwrap();
+        assert_eq!(parsed.arguments[0].occurrences, 2);
+
+        let error = cli
+            .try_parse_custom(inputs(&["example", "-vv", "--verbose"]))
+            .err()
+            .unwrap();
+        assert_eq!(error.key(), Key::TooManyOccurrences);
+        assert_eq!(
+            error.to_string(),
+            "Argument --verbose may be given at most 2 times, found 3"
+        );
+    }
+
+    /// Checks that repeated arguments have their occurrences counted
+    #[test]
+    fn parse_occurrences() {
+        let verbose = Argument::new("Verbose", vec!['v'], vec!["verbose"], Input::None);
+        let quiet = Argument::new("Quiet", vec!['q'], vec![], Input::None);
+        let cli = CliMake::new(
+            "example",
+            vec![&verbose, &quiet],
+            vec![],
+            "Count check",
+            None,
+        );
+
+        let parsed = cli
+            .try_parse_custom(inputs(&["example", "-vvq", "--verbose"]))
+            .ok()
+            .unwrap();
+
+        assert_eq!(parsed.arguments.len(), 4);
+        for parsed_argument in parsed.arguments.iter() {
+            let expected = if *parsed_argument == verbose { 3 } else { 1 };
+            assert_eq!(parsed_argument.occurrences, expected);
+        }
+    }
+
+    /// Checks that [CliMake::preparse] only finds the given calls, without
+    /// erroring on anything else
+    #[test]
+    fn preparse() {
+        let found = CliMake::preparse(
+            &[("--config", true), ("-v", true), ("-q", false)],
+            inputs(&[
+                "example",
+                "--unknown",
+                "-qv",
+                "add",
+                "--config",
+                "my.toml",
+                "-x",
+                "--",
+                "-v",
+            ]),
+        );
+
+        assert_eq!(
+            found,
+            vec![
+                ("-q", None),
+                ("-v", Some("add".to_string())),
+                ("--config", Some("my.toml".to_string()))
+            ]
+        );
+        assert!(CliMake::preparse(&[("-v", true)], inputs(&["example"])).is_empty());
+
+        let found = CliMake::preparse(
+            &[("--config", true), ("-v", false)],
+            inputs(&["example", "-v", "add", "--config", "my.toml"]),
+        );
+        assert_eq!(
+            found,
+            vec![("-v", None), ("--config", Some("my.toml".to_string()))]
+        );
+    }
+
+    /// Checks that subcommands can only be nested up to [CliMake::max_depth]
+    #[test]
+    fn parse_max_depth() {
+        let inner = Subcommand::new("inner", vec![], vec![], None);
+        let middle = Subcommand::new("middle", vec![], vec![&inner], None);
+        let outer = Subcommand::new("outer", vec![], vec![&middle], None);
+        let mut cli = CliMake::new("example", vec![], vec![&outer], "Depth check", None);
+        let given = inputs(&["example", "outer", "middle", "inner"]);
+
+        let parsed = cli.try_parse_custom(given.clone()).ok().unwrap();
+        assert!(parsed.subcommands[0].subcommands[0].subcommands[0] == inner);
+
+        cli.max_depth(2);
+        match cli.try_parse_custom(given) {
+            Err(error) => assert_eq!(
+                error.to_string(),
+                "Subcommands are nested too deeply, the limit is 2 levels"
+            ),
+            Ok(_) => panic!("Expected nesting past the limit to fail"),
+        }
+    }
+
+    /// Checks that values are checked against [Argument::choices_from] whilst
+    /// parsing, suggesting the closest possible value
+    #[test]
+    fn parse_choices_from() {
+        let mut profile = Argument::new("Profile", vec!['p'], vec!["profile"], Input::Text);
+        profile.choices_from(|| vec!["dev".to_string(), "release".to_string()]);
+        let cli = CliMake::new("example", vec![&profile], vec![], "Choices check", None);
+
+        let parsed = cli
+            .try_parse_custom(inputs(&["example", "-p", "release"]))
+            .ok()
+            .unwrap();
+        assert_eq!(parsed.arguments[0].data, Data::Text("release".to_string()));
+        assert_eq!(
+            cli.try_parse_custom(inputs(&["example", "--profile", "relase"]))
+                .err()
+                .unwrap()
+                .to_string(),
+            "'relase' is not valid for --profile; did you mean 'release'?"
+        );
+    }
+
+    /// Checks that unrecognised inputs are collected when [CliMake::lenient] is
+    /// set, rather than erroring
+    #[test]
+    fn parse_lenient() {
+        let verbose = Argument::new("Verbose", vec!['v'], vec![], Input::None);
+        let mut cli = CliMake::new("example", vec![&verbose], vec![], None, None);
+
+        let given = ["example", "-vx", "--color=never", "stray", "-y=1"];
+        assert!(cli.try_parse_custom(inputs(&given)).is_err());
+
+        cli.lenient(true);
+        let parsed = cli.try_parse_custom(inputs(&given)).ok().unwrap();
+        assert!(parsed.arguments[0] == verbose);
+        assert_eq!(parsed.unknown, vec!["-x", "--color=never", "stray", "-y=1"]);
+    }
+
+    /// Checks that parsing stops at the first positional which isn't a subcommand
+    /// when [CliMake::stop_at_positional] is set, keeping the rest as-is
+    #[test]
+    fn parse_stop_at_positional() {
+        let verbose = Argument::new("Verbose", vec!['v'], vec![], Input::None);
+        let run = Subcommand::new("run", vec![&verbose], vec![], None);
+        let mut cli = CliMake::new("example", vec![], vec![&run], None, None);
+
+        assert!(cli
+            .try_parse_custom(inputs(&["example", "run", "cargo", "-v"]))
+            .is_err());
+
+        cli.stop_at_positional(true);
+        let parsed = cli
+            .try_parse_custom(inputs(&["example", "run", "-v", "cargo", "-v", "--", "x"]))
+            .ok()
+            .unwrap();
+        assert!(parsed.subcommands[0].arguments[0] == verbose);
+        assert_eq!(parsed.trailing, vec!["cargo", "-v", "--", "x"]);
+
+        let parsed = cli
+            .try_parse_custom(inputs(&["example", "--", "run"]))
+            .ok()
+            .unwrap();
+        assert!(parsed.subcommands.is_empty());
+        assert_eq!(parsed.trailing, vec!["run"]);
+    }
+
+    /// Checks that every input after a `--` terminator is left in
+    /// [ParsedCli::trailing] rather than being matched as a subcommand or call
+    #[test]
+    fn parse_terminator() {
+        let verbose = Argument::new("Verbose", vec!['v'], vec![], Input::None);
+        let post = Subcommand::new("post", vec![&verbose], vec![], None);
+        let cli = CliMake::new("tool", vec![], vec![&post], None, None);
+        let parsed = |given: &[&str]| cli.try_parse_custom(inputs(given)).ok().unwrap();
+
+        let parsed_post = parsed(&["tool", "--", "post"]);
+        assert!(parsed_post.subcommands.is_empty());
+        assert_eq!(parsed_post.trailing, vec!["post"]);
+
+        let parsed_post = parsed(&["tool", "post", "--", "-v"]);
+        assert!(parsed_post.subcommands[0].arguments.is_empty());
+        assert_eq!(parsed_post.trailing, vec!["-v"]);
+
+        assert_eq!(
+            parsed(&["tool", "--", "file.txt", "--"]).trailing,
+            vec!["file.txt", "--"]
+        );
+    }
+
+    /// Checks that [Argument::global] arguments may be given after subcommands
+    /// and are found at every level below where they're attached
+    #[test]
+    fn parse_global() {
+        let mut verbose = Argument::new("Verbose", vec!['v'], vec!["verbose"], Input::None);
+        verbose.global(true);
+        let quiet = Argument::new("Quiet", vec!['q'], vec![], Input::None);
+        let fetch = Subcommand::new("fetch", vec![], vec![], None);
+        let net = Subcommand::new("net", vec![], vec![&fetch], None);
+        let cli = CliMake::new("example", vec![&verbose, &quiet], vec![&net], None, None);
+
+        let parsed = cli
+            .try_parse_custom(inputs(&["example", "net", "fetch", "-v"]))
+            .ok()
+            .unwrap();
+        let net_parsed = &parsed.subcommands[0];
+
+        assert!(parsed.arguments[0] == verbose);
+        assert!(net_parsed.arguments[0] == verbose);
+        assert!(net_parsed.subcommands[0].arguments[0] == verbose);
+        assert!(cli
+            .try_parse_custom(inputs(&["example", "net", "-q"]))
+            .is_err());
+    }
+
+    /// Checks that arguments take as many values as their [Argument::arity]
+    /// allows, erroring when given too few
+    #[test]
+    fn parse_arity() {
+        let mut point = Argument::new("Point", vec!['p'], vec!["point"], Input::Float);
+        point.arity(2..=2);
+        let mut files = Argument::new("Files", vec!['f'], vec![], Input::Path);
+        files.arity(..=2);
+        let cli = CliMake::new("example", vec![&point, &files], vec![], "Arity check", None);
+
+        let parsed = cli
+            .try_parse_custom(inputs(&["example", "-p", "1", "-2", "-f", "a", "b"]))
+            .ok()
+            .unwrap();
+        assert_eq!(parsed.arguments[0].data, Data::Floats(vec![1.0, -2.0]));
+        assert_eq!(
+            parsed.arguments[1].data,
+            Data::Paths(vec!["a".into(), "b".into()])
+        );
+
+        assert!(cli
+            .try_parse_custom(inputs(&["example", "-f", "a", "b", "c"]))
+            .is_err());
+        assert!(cli.try_parse_custom(inputs(&["example", "-f"])).is_ok());
+        assert_eq!(
+            cli.try_parse_custom(inputs(&["example", "--point=1"]))
+                .err()
+                .unwrap()
+                .to_string(),
+            "Expected 2 values for --point, found 1"
+        );
+    }
+
+    /// Checks that [CliMake::quiet_errors] reduces errors to a single line
+    #[test]
+    fn error_quiet() -> io::Result<()> {
+        let mut chk_vec: Vec<u8> = vec![];
+        let mut cli = CliMake::new("example", vec![], vec![], "Quiet check", None);
+        let error = ParseError::MissingArguments(vec!["--output".to_string()]);
+        let diagnostic = Diagnostic::from(error.clone());
+
+        cli.error_msg(&diagnostic, &mut chk_vec)?;
+        let shown = std::str::from_utf8(chk_vec.as_slice()).unwrap();
+        assert!(shown.starts_with("Usage: "));
+        assert!(shown.ends_with(&format!(
+            "\nError: {}\nTry '--help' for more information\n",
+            error
+        )));
+        chk_vec = vec![];
+
+        cli.quiet_errors(true)
+            .error_msg(&diagnostic, &mut chk_vec)?;
+        assert_eq!(
+            std::str::from_utf8(chk_vec.as_slice()).unwrap(),
+            format!("Error: {}\n", error)
+        );
+
+        Ok(())
+    }
+
+    /// Checks that errors from a subcommand show its usage, with the help hint
+    /// following that subcommand's [Subcommand::disable_help_flag]
+    #[test]
+    fn error_subcommand_usage() -> io::Result<()> {
+        let mut exec = Subcommand::new("exec", vec![], vec![], None);
+        exec.disable_help_flag(true);
+        let add = Subcommand::new("add", vec![], vec![], None);
+        let mut cli = CliMake::new("tool", vec![], vec![&exec, &add], None, None);
+        cli.reproducible_docs(true);
+
+        let shown = |given: &[&str]| -> io::Result<String> {
+            let mut chk_vec: Vec<u8> = vec![];
+            let diagnostic = cli.diagnose(inputs(given)).err().unwrap();
+            cli.error_msg(&diagnostic, &mut chk_vec)?;
+            Ok(String::from_utf8(chk_vec).unwrap())
+        };
+
+        let add_shown = shown(&["tool", "add", "--nope"])?;
+        assert!(add_shown.starts_with("Usage: ./tool add [OPTIONS]\n"));
+        assert!(add_shown.ends_with("Try '--help' for more information\n"));
+
+        let exec_shown = shown(&["tool", "exec", "--nope"])?;
+        assert!(exec_shown.starts_with("Usage: ./tool exec [OPTIONS]\n"));
+        assert!(!exec_shown.contains("Try '--help'"));
+
+        assert!(shown(&["tool", "--nope", "add"])?.starts_with("Usage: ./tool [OPTIONS]\n"));
+
+        Ok(())
+    }
+
+    /// Checks that [CliMake::try_parse_custom] gives errors and help back as a
+    /// result instead of exiting
+    #[test]
+    fn try_parse_result() {
+        let verbose = Argument::new("Verbose mode", vec!['v'], vec![], Input::None);
+        let cli = CliMake::new("example", vec![&verbose], vec![], "Result check", None);
+
+        assert_eq!(
+            cli.try_parse_custom(inputs(&["example", "--nope"])),
+            Err(ParseError::ArgumentNotFound {
+                call: "--nope".to_string(),
+                available: vec!["-v".to_string()]
+            })
+        );
+        assert!(matches!(
+            cli.try_parse_custom(inputs(&["example", "--help"])),
+            Err(ParseError::Help(_))
+        ));
+        assert!(cli.try_parse_custom(inputs(&["example", "-v"])).is_ok());
+    }
+
+    /// Checks that [CliMake::try_parse_custom_os] keeps paths which aren't valid
+    /// utf8 exactly as given
+    #[cfg(unix)]
+    #[test]
+    fn parse_os_paths() {
+        use std::os::unix::ffi::OsStringExt;
+
+        let output = Argument::new("Output", vec!['o'], vec![], Input::Path);
+        let name = Argument::new("Name", vec!['n'], vec![], Input::Text);
+        let cli = CliMake::new("example", vec![&output, &name], vec![], "Os check", None);
+        let invalid = OsString::from_vec(vec![b'a', 0xff, b'b']);
+        let given = vec![
+            OsString::from("example"),
+            OsString::from("-o"),
+            invalid.clone(),
+            OsString::from("-n"),
+            invalid.clone(),
+        ];
+
+        let parsed = cli.try_parse_custom_os(given).ok().unwrap();
+        assert_eq!(parsed.arguments[0].data, Data::Path(PathBuf::from(invalid)));
+        assert_eq!(
+            parsed.arguments[1].data,
+            Data::Text("a\u{fffd}b".to_string())
+        );
+    }
+
+    /// Checks that [CliMake::try_parse_custom_os] keeps non-utf8 paths inside of
+    /// subcommands and [Data::Paths], but not when they're indistinguishable
+    #[cfg(unix)]
+    #[test]
+    fn parse_os_paths_nested() {
+        use std::os::unix::ffi::OsStringExt;
+
+        let mut files = Argument::new("Files", vec!['f'], vec![], Input::Path);
+        files.arity(1..);
+        let output = Argument::new("Output", vec!['o'], vec![], Input::Path);
+        let add = Subcommand::new("add", vec![&files, &output], vec![], None);
+        let cli = CliMake::new("example", vec![], vec![&add], "Os check", None);
+        let first = OsString::from_vec(vec![0xff, b'1']);
+        let second = OsString::from_vec(vec![0xfe, b'2']);
+        let clash = OsString::from_vec(vec![0xfd, b'1']);
+        let given = |inputs: Vec<&OsString>| {
+            let mut given = vec![OsString::from("example"), OsString::from("add")];
+            given.extend(inputs.into_iter().cloned());
+            cli.try_parse_custom_os(given).ok().unwrap()
+        };
+
+        let parsed = given(vec![&OsString::from("-f"), &first, &second]);
+        assert_eq!(
+            parsed.subcommands[0].arguments[0].data,
+            Data::Paths(vec![PathBuf::from(&first), PathBuf::from(&second)])
+        );
+
+        let parsed = given(vec![
+            &OsString::from("-f"),
+            &first,
+            &OsString::from("-o"),
+            &clash,
+        ]);
+        assert_eq!(
+            parsed.subcommands[0].arguments[1].data,
+            Data::Path(PathBuf::from("\u{fffd}1"))
+        );
+
+        let parsed = given(vec![
+            &OsString::from("-f"),
+            &first,
+            &OsString::from("-o"),
+            &OsString::from("\u{fffd}1"),
+        ]);
+        assert_eq!(
+            parsed.subcommands[0].arguments[0].data,
+            Data::Paths(vec![PathBuf::from("\u{fffd}1")])
+        );
+        assert_eq!(
+            parsed.subcommands[0].arguments[1].data,
+            Data::Path(PathBuf::from("\u{fffd}1"))
+        );
+    }
+
+    /// Checks that [CliMake::try_parse_custom_os] keeps paths with unpaired
+    /// surrogates on windows exactly as given
+    #[cfg(windows)]
+    #[test]
+    fn parse_os_paths_windows() {
+        use std::os::windows::ffi::OsStringExt;
+
+        let output = Argument::new("Output", vec!['o'], vec![], Input::Path);
+        let cli = CliMake::new("example", vec![&output], vec![], "Os check", None);
+        let invalid = OsString::from_wide(&[0x61, 0xd800, 0x62]);
+        let given = vec![
+            OsString::from("example"),
+            OsString::from("-o"),
+            invalid.clone(),
+        ];
+
+        let parsed = cli.try_parse_custom_os(given).ok().unwrap();
+        assert_eq!(parsed.arguments[0].data, Data::Path(PathBuf::from(invalid)));
+    }
+
+    /// Checks that a single required value may name a subcommand, whilst
+    /// arguments taking many values still stop before one
+    #[test]
+    fn parse_value_naming_subcommand() {
+        let name = Argument::new("Name", vec![], vec!["name"], Input::Text);
+        let mut files = Argument::new("Files", vec![], vec!["files"], Input::Text);
+        files.arity(1..);
+        let rm = Subcommand::new("rm", vec![], vec![], None);
+        let cli = CliMake::new("example", vec![&name, &files], vec![&rm], None, None);
+
+        let parsed = cli
+            .try_parse_custom(inputs(&["example", "--name", "rm"]))
+            .ok()
+            .unwrap();
+        assert_eq!(parsed.arguments[0].data, Data::Text("rm".to_string()));
+        assert!(parsed.subcommands.is_empty());
+
+        let parsed = cli
+            .try_parse_custom(inputs(&["example", "--files", "a", "rm"]))
+            .ok()
+            .unwrap();
+        assert_eq!(parsed.arguments[0].data, Data::Texts(vec!["a".to_string()]));
+        assert_eq!(parsed.subcommands.len(), 1);
+    }
+
+    /// Checks that [Argument::greedy] captures values up until the next call,
+    /// subcommand or terminator
+    #[test]
+    fn parse_greedy() {
+        let mut message = Argument::new("Message", vec!['m'], vec![], Input::Text);
+        message.greedy(true);
+        let verbose = Argument::new("Verbose", vec!['v'], vec![], Input::None);
+        let push = Subcommand::new("push", vec![], vec![], None);
+        let cli = CliMake::new(
+            "example",
+            vec![&message, &verbose],
+            vec![&push],
+            "Greedy check",
+            None,
+        );
+
+        let parsed = cli
+            .try_parse_custom(inputs(&["example", "-m", "fix the", "bug", "-v", "push"]))
+            .ok()
+            .unwrap();
+        assert_eq!(
+            parsed.arguments[0].data,
+            Data::Text("fix the bug".to_string())
+        );
+        assert_eq!(parsed.arguments[1].inner, &verbose);
+        assert_eq!(parsed.subcommands[0].inner, &push);
+
+        let parsed = cli
+            .try_parse_custom(inputs(&["example", "-m=a", "b", "--"]))
+            .ok()
+            .unwrap();
+        assert_eq!(parsed.arguments[0].data, Data::Text("a b".to_string()));
+
+        let parsed = cli
+            .try_parse_custom(inputs(&["example", "-m", "this", "is", "--", "x"]))
+            .ok()
+            .unwrap();
+        assert_eq!(parsed.arguments[0].data, Data::Text("this is".to_string()));
+        assert_eq!(parsed.trailing, vec!["x"]);
+
+        message.allow_hyphen_values(true);
+        let cli = CliMake::new(
+            "example",
+            vec![&message, &verbose],
+            vec![],
+            "Greedy check",
+            None,
+        );
+        let parsed = cli
+            .try_parse_custom(inputs(&["example", "-m", "a", "-v"]))
+            .ok()
+            .unwrap();
+        assert_eq!(parsed.arguments[0].data, Data::Text("a -v".to_string()));
+    }
+
+    /// Checks that [CliMake::try_parse_str] splits the line before parsing
+    #[test]
+    fn parse_str_words() {
+        let output = Argument::new("Output", vec!['o'], vec![], Input::Path);
+        let build = Subcommand::new("build", vec![], vec![], None);
+        let cli = CliMake::new("example", vec![&output], vec![&build], "Str check", None);
+
+        let parsed = cli.try_parse_str("-o 'my file.txt' build").ok().unwrap();
+        assert_eq!(
+            parsed.arguments[0].data,
+            Data::Path(PathBuf::from("my file.txt"))
+        );
+        assert_eq!(parsed.subcommands[0].inner, &build);
+
+        assert_eq!(
+            cli.try_parse_str("-o \"my file.txt")
+                .err()
+                .unwrap()
+                .to_string(),
+            "Missing closing quote (\") in input"
+        );
+    }
+
+    /// Checks that values starting with `-` are only taken by arguments set with
+    /// [Argument::allow_hyphen_values], or by floats when they're numbers
+    #[test]
+    fn parse_hyphen_values() {
+        let mut offset = Argument::new("Offset", vec!['o'], vec![], Input::Text);
+        offset.allow_hyphen_values(true);
+        let name = Argument::new("Name", vec!['n'], vec![], Input::Text);
+        let ratio = Argument::new("Ratio", vec!['r'], vec![], Input::Float);
+        let cli = CliMake::new(
+            "example",
+            vec![&offset, &name, &ratio],
+            vec![],
+            "Hyphen check",
+            None,
+        );
+
+        let parsed = cli
+            .try_parse_custom(inputs(&["example", "-o", "-5", "-r", "-0.3"]))
+            .ok()
+            .unwrap();
+        assert_eq!(parsed.arguments[0].data, Data::Text("-5".to_string()));
+        assert_eq!(parsed.arguments[1].data, Data::Float(-0.3));
+
+        assert!(cli
+            .try_parse_custom(inputs(&["example", "-o", "--name"]))
+            .is_ok());
+        assert!(cli
+            .try_parse_custom(inputs(&["example", "-n", "-5"]))
+            .is_err());
+    }
+
+    /// Checks that values can be attached to calls using `=`, which is passed on
+    /// as-is after the first `=`
+    #[test]
+    fn parse_equals() {
+        let output = Argument::new("Output", vec!['o'], vec!["output"], Input::Path);
+        let verbose = Argument::new("Verbose", vec!['v'], vec![], Input::None);
+        let cli = CliMake::new(
+            "example",
+            vec![&output, &verbose],
+            vec![],
+            "Equals check",
+            None,
+        );
+        let data = |given: &[&str]| {
+            let parsed = cli.try_parse_custom(inputs(given)).ok().unwrap();
+            let found = parsed.arguments.iter().find(|parsed| **parsed == output);
+            found.unwrap().data.clone()
+        };
+
+        assert_eq!(
+            data(&["example", "--output=/tmp/foo"]),
+            Data::Path("/tmp/foo".into())
+        );
+        assert_eq!(
+            data(&["example", "-o=/tmp/foo"]),
+            Data::Path("/tmp/foo".into())
+        );
+        assert_eq!(data(&["example", "-vo=a=b"]), Data::Path("a=b".into()));
+        assert_eq!(data(&["example", "--output="]), Data::Path("".into()));
+        assert!(cli
+            .try_parse_custom(inputs(&["example", "-v=yes"]))
+            .is_err());
+    }
+
+    /// Checks that [CliMake::slash_calls] accepts windows style calls for known
+    /// arguments only, leaving absolute paths and terminated inputs positional
+    #[test]
+    fn parse_slash_calls() {
+        let verbose = Argument::new("Verbose", vec!['v'], vec![], Input::None);
+        let output = Argument::new("Output", vec![], vec!["output"], Input::Text);
+        let mut cli = CliMake::new("example", vec![&verbose, &output], vec![], None, None);
+
+        assert!(cli.try_parse_custom(inputs(&["example", "/v"])).is_err());
+
+        cli.slash_calls(true).lenient(true);
+        let parsed = cli
+            .try_parse_custom(inputs(&["example", "/v", "/output:file", "/usr"]))
+            .ok()
+            .unwrap();
+        assert_eq!(parsed.arguments.len(), 2);
+        assert_eq!(parsed.arguments[1].data, Data::Text("file".to_string()));
+        assert_eq!(parsed.unknown, vec!["/usr".to_string()]);
+
+        assert!(matches!(
+            cli.try_parse_custom(inputs(&["example", "/?"])),
+            Err(ParseError::Help(_))
+        ));
+
+        let parsed = cli
+            .try_parse_custom(inputs(&["example", "--", "/v"]))
+            .ok()
+            .unwrap();
+        assert!(parsed.arguments.is_empty());
+        assert_eq!(parsed.trailing, vec!["/v".to_string()]);
+    }
+
+    /// Checks that [CliMake::multicall] selects a subcommand from the invoked
+    /// executable's name, parsing as usual for any other name
+    #[test]
+    fn parse_multicall() {
+        let verbose = Argument::new("Verbose", vec!['v'], vec![], Input::None);
+        let quiet = Argument::new("Quiet", vec!['q'], vec![], Input::None);
+        let gzip = Subcommand::new("gzip", vec![], vec![], None);
+        let gunzip = Subcommand::new("gunzip", vec![&verbose], vec![], None);
+        let mut cli = CliMake::new("example", vec![&quiet], vec![&gzip, &gunzip], None, None);
+
+        assert!(cli
+            .try_parse_custom(inputs(&["/usr/bin/gunzip", "-v"]))
+            .is_err());
+
+        cli.multicall(true);
+        let parsed = cli
+            .try_parse_custom(inputs(&["/usr/bin/gunzip", "-v"]))
+            .ok()
+            .unwrap();
+        assert!(parsed.subcommands[0] == gunzip);
+        assert!(parsed.subcommands[0].arguments[0] == verbose);
+
+        let parsed = cli
+            .try_parse_custom(inputs(&["/usr/bin/gunzip", "-v", "-q"]))
+            .ok()
+            .unwrap();
+        assert!(parsed.arguments[0] == quiet);
+        assert!(parsed.subcommands[0].arguments[0] == verbose);
+        assert!(cli
+            .try_parse_custom(inputs(&["example", "gunzip", "-q"]))
+            .is_err());
+
+        let parsed = cli
+            .try_parse_custom(inputs(&["example", "gzip"]))
+            .ok()
+            .unwrap();
+        assert!(parsed.subcommands[0] == gzip);
+    }
+
+    /// Checks that [CliMake::disable_help_flag] and its override for subcommands
+    /// let help calls through as unknown calls or to arguments using them
+    #[test]
+    fn parse_disable_help_flag() {
+        let host = Argument::new("Host", vec!['h'], vec![], Input::Text);
+        let mut exec = Subcommand::new("exec", vec![], vec![], None);
+        exec.disable_help_flag(true);
+        let add = Subcommand::new("add", vec![], vec![], None);
+        let mut cli = CliMake::new("example", vec![], vec![&exec, &add], None, None);
+        cli.lenient(true);
+
+        let parsed = cli
+            .try_parse_custom(inputs(&["example", "exec", "-h", "--help"]))
+            .ok()
+            .unwrap();
+        assert_eq!(parsed.unknown, vec!["-h".to_string(), "--help".to_string()]);
+        assert!(matches!(
+            cli.try_parse_custom(inputs(&["example", "add", "--help"])),
+            Err(ParseError::Help(_))
+        ));
+
+        cli.disable_help_flag(true).add_arg(&host);
+        let parsed = cli
+            .try_parse_custom(inputs(&["example", "-h", "localhost", "--help"]))
+            .ok()
+            .unwrap();
+        assert_eq!(
+            parsed.arguments[0].data,
+            Data::Text("localhost".to_string())
+        );
+        assert_eq!(parsed.unknown, vec!["--help".to_string()]);
+    }
+
+    /// Checks that [Subcommand::disable_version_flag] lets version calls through
+    /// to a lenient pass-through subcommand whilst the cli keeps its own
+    #[test]
+    fn parse_disable_version_flag_subcommand() {
+        let mut exec = Subcommand::new("exec", vec![], vec![], None);
+        exec.disable_help_flag(true).disable_version_flag(true);
+        let mut cli = CliMake::new("tool", vec![], vec![&exec], None, "1.0");
+        cli.lenient(true);
+
+        let parsed = cli
+            .try_parse_custom(inputs(&["tool", "exec", "--version", "-V", "-h"]))
+            .ok()
+            .unwrap();
+        assert_eq!(
+            parsed.unknown,
+            vec!["--version".to_string(), "-V".to_string(), "-h".to_string()]
+        );
+        assert!(matches!(
+            cli.try_parse_custom(inputs(&["tool", "--version"])),
+            Err(ParseError::Version(_))
+        ));
+    }
+
+    /// Checks that the automatic `-V` and `--version` calls show the version of
+    /// the cli or subcommand, unless disabled or used by an argument
+    #[test]
+    fn parse_version_flag() {
+        let version = |cli: &CliMake, given: &[&str]| match cli.try_parse_custom(inputs(given)) {
+            Err(ParseError::Version(version)) => Some(version),
+            _ => None,
+        };
+        let mut add = Subcommand::new("add", vec![], vec![], None);
+        add.version = Some("0.2.0");
+        let mut cli = CliMake::new("example", vec![], vec![&add], None, "1.2.0");
+        cli.lenient(true)
+            .long_version("1.2.0\ncommit 3fb2eec\nbuilt 2024-05-01");
+
+        assert_eq!(
+            version(&cli, &["example", "-V"]).unwrap(),
+            "example 1.2.0\n"
+        );
+        assert_eq!(
+            version(&cli, &["example", "--version"]).unwrap(),
+            "example 1.2.0\ncommit 3fb2eec\nbuilt 2024-05-01\n"
+        );
+        assert_eq!(
+            version(&cli, &["example", "add", "-V"]).unwrap(),
+            "add 0.2.0\n"
+        );
+
+        assert_eq!(
+            version(&cli, &["example", "--version=json"]).unwrap(),
+            "{\"name\":\"example\",\"version\":\"1.2.0\",\"commit\":null,\"build_date\":null,\"features\":[]}\n"
+        );
+        assert_eq!(
+            version(&cli, &["example", "add", "-V", "--format", "json"]).unwrap(),
+            "{\"name\":\"add\",\"version\":\"0.2.0\",\"commit\":null,\"build_date\":null,\"features\":[]}\n"
+        );
+        assert_eq!(
+            cli.try_parse_custom(inputs(&["example", "--version", "--format=xml"]))
+                .err()
+                .unwrap()
+                .to_string(),
+            "'xml' is not valid for --format; possible values are text, json"
+        );
+        assert!(matches!(
+            cli.try_parse_custom(inputs(&["example", "--version", "--format"])),
+            Err(ParseError::MissingValue { found: None, .. })
+        ));
+
+        let format = Argument::new("Format", vec![], vec!["format"], Input::Text);
+        let own = CliMake::new("example", vec![&format], vec![], None, "1.2.0");
+        assert_eq!(
+            version(&own, &["example", "--version", "--format", "json"]).unwrap(),
+            "example 1.2.0\n"
+        );
+
+        cli.disable_version_flag(true);
+        assert_eq!(
+            cli.try_parse_custom(inputs(&["example", "--version"]))
+                .ok()
+                .unwrap()
+                .unknown,
+            vec!["--version".to_string()]
+        );
+        assert!(version(
+            &CliMake::new("example", vec![], vec![], None, None),
+            &["example", "-V"]
+        )
+        .is_none());
+    }
+
+    /// Checks that the automatic `help` subcommand shows nested help, unless
+    /// disabled or replaced by a subcommand named `help`
+    #[test]
+    fn parse_help_subcommand() -> io::Result<()> {
+        let img = Subcommand::new("img", vec![], vec![], "Adds an image");
+        let add = Subcommand::new("add", vec![], vec![&img], "Adds things");
+        let mut cli = CliMake::new("example", vec![], vec![&add], None, None);
+        let help_of = |cli: &CliMake, given: &[&str]| match cli.try_parse_custom(inputs(given)) {
+            Err(ParseError::Help(help)) => Some(help),
+            _ => None,
+        };
+
+        let mut chk_vec: Vec<u8> = vec![];
+        img.help_msg(&cli, true, &mut chk_vec)?;
+        assert_eq!(
+            help_of(&cli, &["example", "help", "add", "img"]).unwrap(),
+            String::from_utf8(chk_vec).unwrap()
+        );
+
+        let mut chk_vec: Vec<u8> = vec![];
+        cli.help_msg(&mut chk_vec)?;
+        assert_eq!(
+            help_of(&cli, &["example", "help"]).unwrap(),
+            String::from_utf8(chk_vec).unwrap()
+        );
+        assert!(help_of(&cli, &["example", "add", "help", "img"]).is_some());
+        assert_eq!(
+            cli.try_parse_custom(inputs(&["example", "help", "remove"]))
+                .err()
+                .unwrap(),
+            ParseError::SubcommandNotFound {
+                name: "remove".to_string(),
+                available: vec!["add".to_string()]
+            }
+        );
+
+        let help = Subcommand::new("help", vec![], vec![], None);
+        let custom = CliMake::new("example", vec![], vec![&add, &help], None, None);
+        let parsed = custom
+            .try_parse_custom(inputs(&["example", "help"]))
+            .ok()
+            .unwrap();
+        assert_eq!(parsed.subcommands[0].inner, &help);
+
+        cli.disable_help_subcommand(true);
+        assert!(help_of(&cli, &["example", "help"]).is_none());
+
+        Ok(())
+    }
+
+    /// Checks that `-h` shows compact help whilst `--help` also shows the
+    /// extended help of arguments and subcommands
+    #[test]
+    fn parse_long_help() {
+        let mut since = Argument::new(
+            "Only show recent changes",
+            vec![],
+            vec!["since"],
+            Input::Text,
+        );
+        since.long_help("Accepts dates such as 2024-05-01");
+        let mut log = Subcommand::new("log", vec![&since], vec![], "Shows the log");
+        log.long_help = Some("Shows the log of every change made, newest first");
+        let cli = CliMake::new("example", vec![&since], vec![&log], None, None);
+        let help_of = |given: &[&str]| match cli.try_parse_custom(inputs(given)) {
+            Err(ParseError::Help(help)) => help,
+            _ => panic!("Help wasn't given"),
+        };
+
+        assert!(!help_of(&["example", "-h"]).contains("Accepts dates"));
+        assert!(
+            help_of(&["example", "--help"]).contains("\n    Accepts dates such as 2024-05-01\n")
+        );
+        assert!(help_of(&["example", "log", "-h"]).contains("\n  Shows the log\n"));
+        assert!(help_of(&["example", "log", "--help"])
+            .contains("\n  Shows the log of every change made, newest first\n"));
+    }
+
+    /// Checks that [CliMake::outcome_custom] keeps requests for help and the
+    /// version apart from errors
+    #[test]
+    fn parse_outcome() {
+        let verbose = Argument::new("Verbose", vec!['v'], vec![], Input::None);
+        let mut cli = CliMake::new("example", vec![&verbose], vec![], None, "1.2.0");
+        cli.embedded(true);
+        let outcome = |given: &[&str]| cli.outcome_custom(inputs(given));
+
+        assert!(
+            matches!(outcome(&["example", "-v"]), Outcome::Parsed(parsed) if parsed.arguments.len() == 1)
+        );
+        assert!(matches!(
+            outcome(&["example", "--help"]),
+            Outcome::HelpRequested(_)
+        ));
+        assert_eq!(
+            outcome(&["example", "-V"]),
+            Outcome::VersionRequested("example 1.2.0\n".to_string())
+        );
+        assert_eq!(
+            outcome(&["example", "-x"]),
+            Outcome::Error(ParseError::ArgumentNotFound {
+                call: "-x".to_string(),
+                available: vec!["-v".to_string()]
+            })
+        );
+    }
+
+    /// Checks that unknown calls and missing required arguments are reported
+    /// together as one [ParseError::Multiple]
+    #[test]
+    fn parse_multiple_errors() {
+        let mut name = Argument::new("Name", vec![], vec!["name"], Input::Text);
+        name.required(true);
+        let cli = CliMake::new("example", vec![&name], vec![], None, None);
+
+        assert!(matches!(
+            cli.try_parse_custom(inputs(&["example", "--first", "-x", "--help"])),
+            Err(ParseError::Help(_))
+        ));
+
+        let error = cli
+            .try_parse_custom(inputs(&["example", "--first", "-x"]))
+            .err()
+            .unwrap();
+        assert_eq!(
+            error.to_string(),
+            "Found 3 errors:\n  No such argument '--first'\n  No such argument '-x'\n  Missing required argument --name"
+        );
+
+        let error = cli
+            .try_parse_custom(inputs(&["example", "--name", "serde", "-x"]))
+            .err()
+            .unwrap();
+        assert_eq!(error.to_string(), "No such argument '-x'");
+    }
+
+    /// Checks that the value following an unknown call isn't also reported as an
+    /// unknown subcommand when collecting errors
+    #[test]
+    fn parse_unknown_call_value() {
+        let name = Argument::new("Name", vec![], vec!["name"], Input::Text);
+        let add = Subcommand::new("add", vec![&name], vec![], None);
+        let cli = CliMake::new("tool", vec![], vec![&add], None, None);
+
+        let error = cli
+            .try_parse_custom(inputs(&["tool", "add", "--nme", "x"]))
+            .err()
+            .unwrap();
+        let errors = match error {
+            ParseError::Multiple(errors) => errors,
+            error => vec![error],
+        };
+        assert_eq!(errors.len(), 1);
+        assert!(matches!(errors[0], ParseError::ArgumentNotFound { .. }));
+
+        let error = cli
+            .try_parse_custom(inputs(&["tool", "add", "--nme", "x", "-q", "y"]))
+            .err()
+            .unwrap();
+        assert_eq!(
+            error.to_string(),
+            "Found 2 errors:\n  No such argument '--nme'; did you mean '--name'?\n  No such argument '-q'"
+        );
+    }
+
+    /// Checks that [CliMake::rename_map] maps old spellings to their canonical
+    /// calls, warning about them if [CliMake::warn_renamed] is set
+    #[test]
+    fn parse_rename_map() {
+        let color = Argument::new("Color", vec![], vec!["color"], Input::None);
+        let grey = Argument::new("Grey", vec![], vec!["grey"], Input::None);
+        let mut cli = CliMake::new("example", vec![&color, &grey], vec![], None, None);
+        cli.rename_map(vec![("colour", "color"), ("grey", "gray")]);
+
+        let parsed = cli
+            .try_parse_custom(inputs(&["example", "--colour", "--grey"]))
+            .ok()
+            .unwrap();
+        assert!(parsed.arguments[0] == color && parsed.arguments[1] == grey);
+
+        let mut chk_vec: Vec<u8> = vec![];
+        let call = CallType::Long("colour".to_string());
+        rename_call(&cli, call.clone(), &cli.arguments, &[], &mut chk_vec);
+        assert!(chk_vec.is_empty());
+
+        cli.warn_renamed(true);
+        let renamed = rename_call(&cli, call, &cli.arguments, &[], &mut chk_vec);
+        assert_eq!(renamed, CallType::Long("color".to_string()));
+        assert_eq!(
+            std::str::from_utf8(chk_vec.as_slice()).unwrap(),
+            "Warning: --colour is deprecated, use --color instead\n"
+        );
+    }
+
+    /// Checks that errors point at the inputs which caused them, including
+    /// single calls inside of clusters
+    #[test]
+    fn error_spans() -> io::Result<()> {
+        let verbose = Argument::new("Verbose", vec!['v'], vec!["verbose"], Input::None);
+        let add = Subcommand::new("add", vec![], vec![], None);
+        let cli = CliMake::new("example", vec![&verbose], vec![&add], None, None);
+        let shown = |given: &[&str]| -> io::Result<String> {
+            let mut chk_vec: Vec<u8> = vec![];
+            let diagnostic = cli.diagnose(inputs(given)).err().unwrap();
+            cli.error_msg(&diagnostic, &mut chk_vec)?;
+
+            let shown = String::from_utf8(chk_vec).unwrap();
+            Ok(shown[shown.find("\n\n  ").unwrap()..].to_string())
+        };
+
+        assert_eq!(
+            shown(&["/bin/example", "add", "--verbos"])?,
+            "\n\n  example add --verbos\n              ^^^^^^^^\n\nTry '--help' for more information\n"
+        );
+        assert_eq!(
+            shown(&["example", "-vxv", "-y"])?,
+            "\n\n  example -vxv -y\n            ^   ^\n\nTry '--help' for more information\n"
+        );
+        #[cfg(not(windows))]
+        assert_eq!(
+            shown(&["example", "--verbos=it's"])?,
+            "\n\n  example '--verbos=it'\\''s'\n          ^^^^^^^^^^^^^^^^^^\n\nTry '--help' for more information\n"
+        );
+
+        Ok(())
     }
 }
@@ -0,0 +1,146 @@
+//! Contains tip implementations for [CliMake]
+
+use super::CliMake;
+use crate::core::utils::{cache_dir, fnv1a};
+use crate::messages::{message, Key};
+
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+impl<'a> CliMake<'a> {
+    /// Adds a tip shown to users after a successful parse, chainable
+    ///
+    /// Tips are opt-in, so nothing is shown unless one is added. They're shown
+    /// on stderr one at a time in the order they were added, a single tip per
+    /// run, with no tip being shown again until every other one has been. Once
+    /// all of them have been shown the rotation starts over from the first, and
+    /// tips added in later releases are shown before any repeats. Which tips were
+    /// already shown is tracked by their text in a small state file in the
+    /// platform's cache directory, and no tips are shown if it can't be written.
+    /// Only the parsing methods which print on their own (e.g. [CliMake::parse])
+    /// show tips, and never whilst [CliMake::embedded] is set.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use climake::prelude::*;
+    ///
+    /// let mut cli = CliMake::new("mytool", vec![], vec![], None, None);
+    /// cli.add_tip("enable completions with `mytool completions install`");
+    /// ```
+    pub fn add_tip(&mut self, tip: &'a str) -> &mut Self {
+        self.tips.push(tip);
+        self
+    }
+
+    /// Gets the state file tracking which tips have been shown, which is
+    /// `climake/<name>.tips` inside of the platform's cache directory
+    fn tips_path(&self) -> Option<PathBuf> {
+        let name: String = self
+            .name
+            .chars()
+            .map(|c| match c {
+                'a'..='z' | 'A'..='Z' | '0'..='9' | '-' | '_' => c,
+                _ => '_',
+            })
+            .collect();
+
+        cache_dir().map(|dir| dir.join("climake").join(format!("{}.tips", name)))
+    }
+
+    /// Shows the next tip on stderr if there's one left, see [CliMake::add_tip].
+    /// Failing to show tips is ignored as they're never needed
+    pub(crate) fn show_tip(&self) {
+        if self.tips.is_empty() {
+            return;
+        }
+
+        if let Some(path) = self.tips_path() {
+            self.tip_msg(&path, &mut io::stderr()).ok();
+        }
+    }
+
+    /// Writes the next tip not yet recorded as shown in the state file at `path`
+    /// to the buffer, recording it first so a tip is never shown twice in one
+    /// rotation
+    ///
+    /// The state file holds the hash of each shown tip on its own line, so tips
+    /// may be added, removed or reordered between releases without others being
+    /// skipped. Hashes of tips which no longer exist are dropped when it's next
+    /// written.
+    pub(crate) fn tip_msg(&self, path: &Path, buf: &mut impl Write) -> io::Result<()> {
+        let state = fs::read_to_string(path).unwrap_or_default();
+        let hash = |tip: &str| format!("{:016x}", fnv1a(tip.as_bytes()));
+        let mut shown: Vec<String> = self
+            .tips
+            .iter()
+            .map(|tip| hash(tip))
+            .filter(|hash| state.lines().any(|line| line.trim() == hash))
+            .collect();
+        let tip = match self.tips.iter().find(|tip| !shown.contains(&hash(tip))) {
+            Some(tip) => tip,
+            None => {
+                // every tip has been shown, so the rotation starts over
+                shown.clear();
+                &self.tips[0]
+            }
+        };
+        shown.push(hash(tip));
+
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(path, format!("{}\n", shown.join("\n")))?;
+
+        buf.write_all(format!("{}\n", message(Key::Tip, &[tip])).as_bytes())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+
+    /// Checks that [CliMake::add_tip] tips are shown once each in order before
+    /// rotating, tracked by their hashes in the state file
+    #[test]
+    fn tips_rotate() -> io::Result<()> {
+        let path = env::temp_dir()
+            .join(format!("climake-tips-{}", std::process::id()))
+            .join("mytool.tips");
+        let shown = |cli: &CliMake, runs: usize| -> io::Result<Vec<String>> {
+            let mut shown = vec![];
+            for _ in 0..runs {
+                let mut chk_vec: Vec<u8> = vec![];
+                cli.tip_msg(&path, &mut chk_vec)?;
+                shown.push(String::from_utf8(chk_vec).unwrap());
+            }
+            Ok(shown)
+        };
+        let mut cli = CliMake::new("mytool", vec![], vec![], None, None);
+        cli.add_tip("first").add_tip("second");
+
+        assert_eq!(shown(&cli, 1)?, vec!["tip: first\n"]);
+        assert_eq!(
+            fs::read_to_string(&path)?,
+            format!("{:016x}\n", fnv1a(b"first"))
+        );
+
+        // a tip added before those already shown doesn't cause any to be skipped,
+        // and the rotation starts over once every tip has been shown
+        let mut cli = CliMake::new("mytool", vec![], vec![], None, None);
+        cli.add_tip("zeroth").add_tip("first").add_tip("second");
+        assert_eq!(
+            shown(&cli, 4)?,
+            vec![
+                "tip: zeroth\n",
+                "tip: second\n",
+                "tip: zeroth\n",
+                "tip: first\n"
+            ]
+        );
+
+        fs::remove_dir_all(path.parent().unwrap())
+    }
+}
@@ -0,0 +1,260 @@
+//! Contains validation implementations for [CliMake]
+
+use super::CliMake;
+use crate::{Argument, Subcommand};
+
+use std::fmt;
+
+/// Problem with the definition of a cli found by [CliMake::validate], which
+/// should be fixed by the developer of the cli rather than shown to users
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+pub enum DefinitionError {
+    /// Help message which is longer than [CliMake::help_lint_width], containing
+    /// a description of where it's from and its length
+    HelpTooLong {
+        /// Description of where the help is from, e.g. `argument --verbose`
+        item: String,
+
+        /// Length of the help in characters
+        length: usize,
+    },
+
+    /// Help message containing newlines or other control characters which would
+    /// break the compact help layout, containing a description of where it's from
+    HelpControlChars(String),
+
+    /// Call which is used by more than one argument at the same level, containing
+    /// the call and a description of the level, e.g. `subcommand add`
+    DuplicateCall {
+        /// Formatted call which is duplicated, e.g. `--verbose`
+        call: String,
+
+        /// Description of where the arguments are, e.g. `subcommand add`
+        item: String,
+    },
+
+    /// Argument without any short or long calls, meaning it can never be given,
+    /// containing its help (if any) and a description of the level it's at
+    NoCalls {
+        /// Help message of the argument, if it has one
+        help: Option<String>,
+
+        /// Description of where the argument is, e.g. `subcommand add`
+        item: String,
+    },
+}
+
+impl fmt::Display for DefinitionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DefinitionError::HelpTooLong { item, length } => {
+                write!(f, "Help for {} is too long at {} characters", item, length)
+            }
+            DefinitionError::HelpControlChars(item) => write!(
+                f,
+                "Help for {} contains newlines or control characters",
+                item
+            ),
+            DefinitionError::DuplicateCall { call, item } => write!(
+                f,
+                "Call {} is used by multiple arguments of {}, only the first will be used",
+                call, item
+            ),
+            DefinitionError::NoCalls {
+                help: Some(help),
+                item,
+            } => write!(f, "Argument '{}' of {} has no calls", help, item),
+            DefinitionError::NoCalls { help: None, item } => {
+                write!(f, "An argument of {} has no calls", item)
+            }
+        }
+    }
+}
+
+impl std::error::Error for DefinitionError {}
+
+impl<'a> CliMake<'a> {
+    /// Validates the definition of this cli, returning all problems found
+    ///
+    /// This is designed to be used inside of tests or debug builds so that
+    /// mistakes such as overly long help messages are caught whilst making the
+    /// cli, rather than once it's been shipped to users.
+    pub fn validate(&self) -> Result<(), Vec<DefinitionError>> {
+        let mut errors = vec![];
+
+        self.lint_help(format!("cli {}", self.name), self.description, &mut errors);
+        self.validate_level(
+            format!("cli {}", self.name),
+            &self.arguments,
+            &self.subcommands,
+            &mut errors,
+        );
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// Validates a single level of the cli described by `item`, recursing into
+    /// any subcommands
+    fn validate_level(
+        &self,
+        item: String,
+        arguments: &[&Argument],
+        subcommands: &[&Subcommand],
+        errors: &mut Vec<DefinitionError>,
+    ) {
+        let mut calls_seen = vec![];
+
+        for argument in arguments.iter() {
+            if argument.shorts.is_empty() && argument.longs.is_empty() {
+                errors.push(DefinitionError::NoCalls {
+                    help: argument.help.map(String::from),
+                    item: item.clone(),
+                });
+            }
+
+            let calls = argument
+                .shorts
+                .iter()
+                .map(|c| format!("-{}", c))
+                .chain(argument.longs.iter().map(|string| format!("--{}", string)));
+
+            for call in calls {
+                if calls_seen.contains(&call) {
+                    errors.push(DefinitionError::DuplicateCall {
+                        call,
+                        item: item.clone(),
+                    });
+                } else {
+                    calls_seen.push(call);
+                }
+            }
+        }
+
+        for argument in arguments.iter() {
+            self.lint_help(
+                format!("argument {}", argument.display_call()),
+                argument.help,
+                errors,
+            );
+        }
+
+        for subcommand in subcommands.iter() {
+            self.lint_help(
+                format!("subcommand {}", subcommand.name),
+                subcommand.help,
+                errors,
+            );
+            self.validate_level(
+                format!("subcommand {}", subcommand.name),
+                &subcommand.arguments,
+                &subcommand.subcommands,
+                errors,
+            );
+        }
+    }
+
+    /// Checks a single `help` message for problems, with `item` describing where
+    /// the help is from
+    fn lint_help(&self, item: String, help: Option<&str>, errors: &mut Vec<DefinitionError>) {
+        let help = match help {
+            Some(help) => help,
+            None => return,
+        };
+
+        if help.chars().any(char::is_control) {
+            errors.push(DefinitionError::HelpControlChars(item.clone()));
+        }
+
+        let length = help.chars().count();
+
+        if length > self.help_lint_width {
+            errors.push(DefinitionError::HelpTooLong { item, length });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::io::Input;
+
+    /// Checks that the [CliMake::validate] method finds calls which are used by
+    /// multiple arguments at the same level only
+    #[test]
+    fn validate_duplicates() {
+        let first = Argument::new("First", vec!['a'], vec!["all"], Input::None);
+        let second = Argument::new("Second", vec!['a', 'b'], vec!["all"], Input::None);
+        let sub = Subcommand::new("sub", vec![&first, &second], vec![], None);
+        let cli = CliMake::new("example", vec![&first], vec![&sub], "Validate check", None);
+
+        assert_eq!(
+            cli.validate(),
+            Err(vec![
+                DefinitionError::DuplicateCall {
+                    call: "-a".to_string(),
+                    item: "subcommand sub".to_string()
+                },
+                DefinitionError::DuplicateCall {
+                    call: "--all".to_string(),
+                    item: "subcommand sub".to_string()
+                }
+            ])
+        );
+    }
+
+    /// Checks that the [CliMake::validate] method finds arguments without calls
+    #[test]
+    fn validate_no_calls() {
+        let empty = Argument::new("Empty", vec![], vec![], Input::Text);
+        let cli = CliMake::new("example", vec![&empty], vec![], "Validate check", None);
+
+        assert_eq!(
+            cli.validate(),
+            Err(vec![DefinitionError::NoCalls {
+                help: Some("Empty".to_string()),
+                item: "cli example".to_string()
+            }])
+        );
+        assert_eq!(
+            cli.validate().unwrap_err()[0].to_string(),
+            "Argument 'Empty' of cli example has no calls"
+        );
+    }
+
+    /// Checks that the [CliMake::validate] method lints help messages throughout
+    /// the cli
+    #[test]
+    fn validate_help() {
+        let long = Argument::new(
+            "A rather long help message",
+            vec![],
+            vec!["long"],
+            Input::None,
+        );
+        let newline = Subcommand::new("newline", vec![&long], vec![], "Line one\nLine two");
+        let mut cli = CliMake::new("example", vec![&long], vec![], "Validate check", None);
+
+        assert_eq!(cli.validate(), Ok(()));
+
+        cli.add_subcmd(&newline);
+        cli.help_lint_width(20);
+        assert_eq!(
+            cli.validate(),
+            Err(vec![
+                DefinitionError::HelpTooLong {
+                    item: "argument --long".to_string(),
+                    length: 26
+                },
+                DefinitionError::HelpControlChars("subcommand newline".to_string()),
+                DefinitionError::HelpTooLong {
+                    item: "argument --long".to_string(),
+                    length: 26
+                }
+            ])
+        );
+    }
+}
@@ -0,0 +1,97 @@
+//! Contains version implementations for [CliMake]
+
+use super::CliMake;
+use crate::core::utils::json_quote;
+
+/// Build metadata of a cli shown alongside its version by `--version=json`, see
+/// [CliMake::build_info]
+///
+/// # Example
+///
+/// ```rust
+/// use climake::prelude::*;
+/// use climake::BuildInfo;
+///
+/// let mut cli = CliMake::new("tool", vec![], vec![], None, "1.2.0");
+/// cli.build_info(BuildInfo {
+///     commit: option_env!("GIT_COMMIT"),
+///     date: Some("2024-05-01"),
+///     features: vec!["tls"],
+/// });
+/// ```
+#[derive(Debug, Default, PartialEq, Eq, Hash, Clone)]
+pub struct BuildInfo<'a> {
+    /// Optional hash of the commit the cli was built from
+    pub commit: Option<&'a str>,
+
+    /// Optional date the cli was built on, ideally as `YYYY-MM-DD`
+    pub date: Option<&'a str>,
+
+    /// Features the cli was built with, if any
+    pub features: Vec<&'a str>,
+}
+
+impl<'a> CliMake<'a> {
+    /// Sets the build metadata shown alongside the version by `--version=json`
+    /// or `--version --format json`, chainable
+    ///
+    /// The json form of the version is meant for release automation and bug
+    /// report tooling, which can read it reliably unlike [CliMake::long_version].
+    /// It's always shown on one line as an object with `name`, `version`,
+    /// `commit`, `build_date` and `features` keys, with `null` for anything not
+    /// set. Subcommands with their own version show it in place of the cli's.
+    pub fn build_info(&mut self, build_info: BuildInfo<'a>) -> &mut Self {
+        self.build_info = build_info;
+        self
+    }
+
+    /// Generates the json form of the version, see [CliMake::build_info]
+    pub(crate) fn version_json(&self, name: &str, version: &str) -> String {
+        let optional = |value: Option<&str>| match value {
+            Some(value) => json_quote(value),
+            None => "null".to_string(),
+        };
+        let features: Vec<String> = self
+            .build_info
+            .features
+            .iter()
+            .map(|feature| json_quote(feature))
+            .collect();
+
+        format!(
+            "{{\"name\":{},\"version\":{},\"commit\":{},\"build_date\":{},\"features\":[{}]}}\n",
+            json_quote(name),
+            json_quote(version),
+            optional(self.build_info.commit),
+            optional(self.build_info.date),
+            features.join(",")
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Checks that [CliMake::version_json] gives every key, using `null` for
+    /// build metadata which wasn't set
+    #[test]
+    fn version_json() {
+        let mut cli = CliMake::new("tool", vec![], vec![], None, "1.2.0");
+
+        assert_eq!(
+            cli.version_json("tool", "1.2.0"),
+            "{\"name\":\"tool\",\"version\":\"1.2.0\",\"commit\":null,\"build_date\":null,\"features\":[]}\n"
+        );
+
+        cli.build_info(BuildInfo {
+            commit: Some("3fb2eec"),
+            date: Some("2024-05-01"),
+            features: vec!["tls", "zstd"],
+        });
+        assert_eq!(
+            cli.version_json("tool", "1.2.0"),
+            "{\"name\":\"tool\",\"version\":\"1.2.0\",\"commit\":\"3fb2eec\",\"build_date\":\"2024-05-01\",\"features\":[\"tls\",\"zstd\"]}\n"
+        );
+    }
+}
@@ -1,12 +1,18 @@
 //! Contains [CliMake]-related items, see specific documentation for more information
 
 mod impl_basic;
+mod impl_export;
 mod impl_help;
 mod impl_parse;
+mod impl_tips;
+mod impl_validate;
+mod impl_version;
 
-pub use impl_basic::*;
-pub use impl_help::*;
-pub use impl_parse::*;
+pub(crate) use impl_help::render_sections;
+pub use impl_parse::ArgumentOrder;
+pub use impl_parse::{Outcome, ParseError};
+pub use impl_validate::DefinitionError;
+pub use impl_version::BuildInfo;
 
 use crate::{Argument, Subcommand};
 
@@ -15,16 +21,16 @@ use crate::{Argument, Subcommand};
 #[derive(Debug, PartialEq, Clone)]
 pub struct CliMake<'a> {
     /// Name of the program using the cli
-    name: &'a str,
+    pub(crate) name: &'a str,
 
     /// Internal [Argument]s stored inside the cli once created/added to
-    arguments: Vec<&'a Argument<'a>>,
+    pub(crate) arguments: Vec<&'a Argument<'a>>,
 
     /// Internal [Subcommand]s stored inside the cli once created/added to
-    subcommands: Vec<&'a Subcommand<'a>>,
+    pub(crate) subcommands: Vec<&'a Subcommand<'a>>,
 
     /// Optional short description of the program using the cli
-    description: Option<&'a str>,
+    pub(crate) description: Option<&'a str>,
 
     /// Optional version string of the program using the cli
     ///
@@ -44,8 +50,126 @@ pub struct CliMake<'a> {
     ///     )
     /// }
     /// ```
-    version: Option<&'a str>,
+    pub(crate) version: Option<&'a str>,
+
+    /// Optional multi-line version shown for `--version`, see
+    /// [CliMake::long_version]
+    pub(crate) long_version: Option<&'a str>,
+
+    /// Build metadata shown by `--version=json`, see [CliMake::build_info]
+    pub(crate) build_info: BuildInfo<'a>,
 
     /// Internal/private tabbing to use, defaults to [CLI_TABBING](crate::CLI_TABBING)
-    tabbing: &'static str,
+    pub(crate) tabbing: &'static str,
+
+    /// If each parsing decision should be traced to stderr, see [CliMake::debug]
+    pub(crate) debug: bool,
+
+    /// Amount of arguments allowed before sections are collapsed in help, see
+    /// [CliMake::section_threshold]
+    pub(crate) section_threshold: usize,
+
+    /// Example invocations of the cli shown in help, see [CliMake::add_example]
+    pub(crate) examples: Vec<&'a str>,
+
+    /// Layout of help messages, see [CliMake::help_template]
+    pub(crate) help_template: &'a str,
+
+    /// Optional free text shown before the usage line of help, see
+    /// [CliMake::before_help]
+    pub(crate) before_help: Option<&'a str>,
+
+    /// Optional free text shown at the end of help, see [CliMake::after_help]
+    pub(crate) after_help: Option<&'a str>,
+
+    /// Tips shown once each after successful parses, see [CliMake::add_tip]
+    pub(crate) tips: Vec<&'a str>,
+
+    /// Maximum length of help messages allowed by [CliMake::validate], see
+    /// [CliMake::help_lint_width]
+    pub(crate) help_lint_width: usize,
+
+    /// If arguments of a parent given after a subcommand are passed up to it
+    /// rather than rejected, see [CliMake::bubble_arguments]
+    pub(crate) bubble_arguments: bool,
+
+    /// Maximum depth subcommands may be nested to, see [CliMake::max_depth]
+    pub(crate) max_depth: usize,
+
+    /// Order given to parsed arguments, see [CliMake::argument_order]
+    pub(crate) argument_order: ArgumentOrder,
+
+    /// If generated help should be byte-stable, see [CliMake::reproducible_docs]
+    pub(crate) reproducible: bool,
+
+    /// Maximum width help is wrapped to, see [CliMake::max_width]
+    pub(crate) max_width: usize,
+
+    /// Minimum width help is wrapped to, see [CliMake::min_width]
+    pub(crate) min_width: usize,
+
+    /// Terminal width used instead of detecting it, see [CliMake::term_width]
+    pub(crate) term_width: Option<usize>,
+
+    /// If parse errors are reduced to a single line, see [CliMake::quiet_errors]
+    pub(crate) quiet_errors: bool,
+
+    /// Exit code used when parsing fails, see [CliMake::error_code]
+    pub(crate) error_code: i32,
+
+    /// If the first positional which isn't a subcommand stops parsing, see
+    /// [CliMake::stop_at_positional]
+    pub(crate) stop_at_positional: bool,
+
+    /// If unrecognised inputs are collected instead of erroring, see
+    /// [CliMake::lenient]
+    pub(crate) lenient: bool,
+
+    /// If unambiguous prefixes of long calls are accepted, see
+    /// [CliMake::abbreviations]
+    pub(crate) abbreviations: bool,
+
+    /// If unambiguous prefixes of subcommand names are accepted, see
+    /// [CliMake::abbreviate_subcommands]
+    pub(crate) abbreviate_subcommands: bool,
+
+    /// If long calls and subcommand names are matched ignoring case, see
+    /// [CliMake::case_insensitive]
+    pub(crate) case_insensitive: bool,
+
+    /// If windows style calls such as `/v` are accepted, see [CliMake::slash_calls]
+    pub(crate) slash_calls: bool,
+
+    /// If calls are shown in the windows style in help, see [CliMake::slash_help]
+    pub(crate) slash_help: bool,
+
+    /// If the invoked executable name selects a subcommand, see
+    /// [CliMake::multicall]
+    pub(crate) multicall: bool,
+
+    /// If the automatic help calls are disabled, see [CliMake::disable_help_flag]
+    pub(crate) disable_help_flag: bool,
+
+    /// If the automatic version calls are disabled, see
+    /// [CliMake::disable_version_flag]
+    pub(crate) disable_version_flag: bool,
+
+    /// If the automatic `help` subcommand is disabled, see
+    /// [CliMake::disable_help_subcommand]
+    pub(crate) disable_help_subcommand: bool,
+
+    /// Old spellings of long calls alongside their canonical call, see
+    /// [CliMake::rename_map]
+    pub(crate) renames: Vec<(&'a str, &'a str)>,
+
+    /// If old spellings from [CliMake::rename_map] give a deprecation warning,
+    /// see [CliMake::warn_renamed]
+    pub(crate) warn_renamed: bool,
+
+    /// If climake never prints anything on its own, see [CliMake::embedded]
+    pub(crate) embedded: bool,
+
+    /// If values ignored due to being overridden are recorded, see
+    /// [CliMake::record_overrides]
+    pub(crate) record_overrides: bool,
 }
@@ -1,10 +1,14 @@
 //! Core components of climake, re-exported with wildcard into library root
 
-mod argument;
+pub(crate) mod argument;
 mod cli_make;
+mod render;
 mod subcommand;
-mod utils;
+pub(crate) mod utils;
 
-pub use argument::Argument;
-pub use cli_make::CliMake;
+pub use argument::{Argument, RepeatPolicy};
+pub use cli_make::{
+    ArgumentOrder, BuildInfo, CliMake, DefinitionError, Outcome, ParseError as Error,
+};
+pub use render::{HelpRender, RenderOptions};
 pub use subcommand::Subcommand;
@@ -0,0 +1,202 @@
+//! Contains the [HelpRender] trait which every entity shown in help renders
+//! through, see specific documentation for more information
+
+use super::cli_make::render_sections;
+use super::utils::writeln_term;
+use super::{Argument, CliMake, Subcommand};
+use crate::messages::Key;
+
+use std::io::Write;
+
+/// Options used whilst rendering help, typically gotten from a cli using
+/// [CliMake::render_options] so entities render consistently with the rest of it
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub struct RenderOptions<'b> {
+    /// Amount of arguments allowed before sections are collapsed, see
+    /// [CliMake::section_threshold]
+    pub section_threshold: usize,
+
+    /// Width to wrap help to, see [CliMake::max_width]
+    pub width: usize,
+
+    /// Example invocations to show, which are only given for the root cli
+    pub examples: &'b [&'b str],
+
+    /// If help should be byte-stable, see [CliMake::reproducible_docs]
+    pub reproducible: bool,
+
+    /// If calls are shown in the windows style, see [CliMake::slash_help]
+    pub slash_calls: bool,
+
+    /// If extended help is shown as for `--help` rather than `-h`, see
+    /// [Argument::long_help] and [Subcommand::long_help]
+    pub long_help: bool,
+
+    /// Layout of full help, see [CliMake::help_template]
+    pub help_template: &'b str,
+}
+
+/// Entity which may be rendered into help, either compactly as a single entry in
+/// a list or in full as its own help message
+///
+/// This is implemented by [CliMake], [Subcommand] and [Argument] so they may all
+/// be rendered through one interface, e.g. as a `Vec<&dyn HelpRender>`, and can
+/// be implemented for your own entities so they're shown alongside them.
+///
+/// # Example
+///
+/// ```rust
+/// use climake::prelude::*;
+/// use climake::{HelpRender, RenderOptions};
+///
+/// let verbose = Argument::new("Verbose mode", vec!['v'], vec![], Input::None);
+/// let cli = CliMake::new("tool", vec![&verbose], vec![], None, None);
+///
+/// let mut buf: Vec<u8> = vec![];
+/// verbose.render_compact(&cli.render_options(), &mut buf).unwrap();
+///
+/// assert_eq!(std::str::from_utf8(&buf).unwrap(), "  -v — Verbose mode\n");
+/// ```
+pub trait HelpRender {
+    /// Renders a compact single entry for this entity, as it'd be listed inside
+    /// of another entity's help
+    fn render_compact(&self, options: &RenderOptions, buf: &mut dyn Write) -> std::io::Result<()>;
+
+    /// Renders full help for this entity, as shown when help is asked for it
+    fn render_full(&self, options: &RenderOptions, buf: &mut dyn Write) -> std::io::Result<()>;
+}
+
+impl<'a> HelpRender for Argument<'a> {
+    fn render_compact(
+        &self,
+        options: &RenderOptions,
+        mut buf: &mut dyn Write,
+    ) -> std::io::Result<()> {
+        self.help_name_msg(options.width, options.slash_calls, &mut buf)
+    }
+
+    /// Renders the same entry as [HelpRender::render_compact] followed by the
+    /// extended [Argument::long_help], as arguments have no help of their own
+    fn render_full(&self, options: &RenderOptions, mut buf: &mut dyn Write) -> std::io::Result<()> {
+        self.render_compact(options, buf)?;
+        self.long_help_msg(options.width, &mut buf)
+    }
+}
+
+impl<'a> HelpRender for Subcommand<'a> {
+    fn render_compact(
+        &self,
+        options: &RenderOptions,
+        mut buf: &mut dyn Write,
+    ) -> std::io::Result<()> {
+        self.help_name_msg(options.width, &mut buf)
+    }
+
+    /// Renders the about section and every section of this subcommand, without
+    /// the usage line which needs the cli it's attached to
+    fn render_full(&self, options: &RenderOptions, mut buf: &mut dyn Write) -> std::io::Result<()> {
+        self.full_msg(None, options, &mut buf)
+    }
+}
+
+impl<'a> HelpRender for CliMake<'a> {
+    fn render_compact(
+        &self,
+        options: &RenderOptions,
+        mut buf: &mut dyn Write,
+    ) -> std::io::Result<()> {
+        let description = self.description.unwrap_or(Key::NoHelp.template());
+        writeln_term(
+            format!("{} — {}", self.name, description),
+            options.width,
+            &mut buf,
+        )
+    }
+
+    fn render_full(&self, options: &RenderOptions, mut buf: &mut dyn Write) -> std::io::Result<()> {
+        let header = |placeholder: &str, mut buf: &mut dyn Write| match placeholder {
+            "usage" => self.usage_msg(None, &mut buf).map(|_| true),
+            "about" => self.about_msg(&mut buf).map(|_| true),
+            _ => Ok(false),
+        };
+
+        render_sections(
+            &self.arguments,
+            &self.subcommands,
+            options,
+            self.before_help,
+            self.after_help,
+            header,
+            &mut buf,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::io::Input;
+
+    /// User-defined entity for checking that [HelpRender] may be implemented
+    /// outside of the library
+    struct Note(&'static str);
+
+    impl HelpRender for Note {
+        fn render_compact(
+            &self,
+            options: &RenderOptions,
+            mut buf: &mut dyn Write,
+        ) -> std::io::Result<()> {
+            writeln_term(format!("note — {}", self.0), options.width, &mut buf)
+        }
+
+        fn render_full(&self, options: &RenderOptions, buf: &mut dyn Write) -> std::io::Result<()> {
+            self.render_compact(options, buf)
+        }
+    }
+
+    /// Checks that entities may be rendered compactly through one interface
+    #[test]
+    fn render_compact_many() -> std::io::Result<()> {
+        let arg = Argument::new("Verbose mode", vec!['v'], vec![], Input::None);
+        let subcmd = Subcommand::new("add", vec![], vec![], "Adds an item");
+        let cli = CliMake::new("tool", vec![&arg], vec![&subcmd], None, None);
+        let note = Note("Made by hand");
+
+        let entities: Vec<&dyn HelpRender> = vec![&arg, &subcmd, &cli, &note];
+        let mut chk_vec: Vec<u8> = vec![];
+
+        for entity in entities {
+            entity.render_compact(&cli.render_options(), &mut chk_vec)?;
+        }
+
+        assert_eq!(
+            std::str::from_utf8(chk_vec.as_slice()).unwrap(),
+            format!(
+                "  -v — Verbose mode\n  add — Adds an item\n  tool — {}\n  note — Made by hand\n",
+                Key::NoHelp.template()
+            )
+        );
+
+        Ok(())
+    }
+
+    /// Checks that [Subcommand]s render in full with their sections
+    #[test]
+    fn render_full_subcommand() -> std::io::Result<()> {
+        let arg = Argument::new("Verbose mode", vec!['v'], vec![], Input::None);
+        let mut subcmd = Subcommand::new("add", vec![&arg], vec![], "Adds an item");
+        subcmd.version = Some("1.0.0");
+        let mut cli = CliMake::new("tool", vec![], vec![&subcmd], None, None);
+        cli.reproducible_docs(true);
+
+        let mut chk_vec: Vec<u8> = vec![];
+        subcmd.render_full(&cli.render_options(), &mut chk_vec)?;
+
+        let rendered = String::from_utf8(chk_vec).unwrap();
+        assert!(rendered.starts_with("\nAbout:\n  Adds an item\n"));
+        assert!(rendered.contains("  -v — Verbose mode\n"));
+
+        Ok(())
+    }
+}
@@ -1,16 +1,22 @@
 //! Contains [Subcommand]-related items, see specific documentation for more
 //! information
 
-use super::utils::writeln_term;
+use super::cli_make::render_sections;
+use super::render::RenderOptions;
+use super::utils::{writeln_term, DefinitionId, Predicate, Shared};
 use super::{Argument, CliMake};
-use crate::HELP_DEFAULT;
+use crate::messages::{message, Key};
 
 use std::io::Write;
+use std::sync::Arc;
 
 /// A subcommand attached to the cli, allowing commands and sections of the cli
 /// to form
-#[derive(Debug, PartialEq, Clone)]
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
 pub struct Subcommand<'a> {
+    /// Stable identifier of this subcommand, see [Subcommand::id]
+    pub(crate) id: DefinitionId,
+
     /// Name of subcommand, used both in help and as the single calling method
     pub name: &'a str,
 
@@ -22,6 +28,44 @@ pub struct Subcommand<'a> {
 
     /// Optional short description of this subcommand
     pub help: Option<&'a str>,
+
+    /// Optional extended description of this subcommand, shown instead of
+    /// [Subcommand::help] in its own help when asked for using `--help` rather
+    /// than `-h`
+    pub long_help: Option<&'a str>,
+
+    /// Optional free text shown before the usage line in this subcommand's own
+    /// help, see [CliMake::before_help]
+    pub before_help: Option<&'a str>,
+
+    /// Optional free text shown at the end of this subcommand's own help, see
+    /// [CliMake::after_help]
+    pub after_help: Option<&'a str>,
+
+    /// Optional version string of this subcommand, typically only used when an
+    /// entire cli has been embedded with [Subcommand::from_cli]
+    pub version: Option<&'a str>,
+
+    /// If invoking this subcommand without any arguments or subcommands of its
+    /// own should display its help, e.g. `mytool add` showing how to add
+    pub help_if_empty: bool,
+
+    /// If arguments of parents may be given after this subcommand, overriding
+    /// [CliMake::bubble_arguments] if set, see [Subcommand::bubble_arguments]
+    pub(crate) bubble_arguments: Option<bool>,
+
+    /// If the automatic help calls are disabled after this subcommand, overriding
+    /// [CliMake::disable_help_flag] if set, see [Subcommand::disable_help_flag]
+    pub(crate) disable_help_flag: Option<bool>,
+
+    /// If the automatic version calls are disabled after this subcommand,
+    /// overriding [CliMake::disable_version_flag] if set, see
+    /// [Subcommand::disable_version_flag]
+    pub(crate) disable_version_flag: Option<bool>,
+
+    /// Optional predicate deciding if this subcommand is available, see
+    /// [Subcommand::available_if]
+    pub(crate) available: Option<Shared<Predicate<'a>>>,
 }
 
 impl<'a> Subcommand<'a> {
@@ -33,54 +77,215 @@ impl<'a> Subcommand<'a> {
         help: impl Into<Option<&'a str>>,
     ) -> Self {
         Self {
+            id: DefinitionId::new(),
             name: name.into(),
             arguments: arguments.into(),
             subcommands: subcommands.into(),
             help: help.into(),
+            long_help: None,
+            before_help: None,
+            after_help: None,
+            version: None,
+            help_if_empty: false,
+            bubble_arguments: None,
+            disable_help_flag: None,
+            disable_version_flag: None,
+            available: None,
         }
     }
 
+    /// Creates many new [Subcommand]s at once from `(name, help)` tuples, which
+    /// are returned owned so they may be attached afterwards using
+    /// [CliMake::add_subcmds], e.g. `cli.add_subcmds(&subcommands)`
+    pub fn many(definitions: impl IntoIterator<Item = (&'a str, &'a str)>) -> Vec<Self> {
+        definitions
+            .into_iter()
+            .map(|(name, help)| Self::new(name, vec![], vec![], help))
+            .collect()
+    }
+
+    /// Creates a new subcommand from an entire [CliMake], allowing independently
+    /// defined tools to be composed into one umbrella cli
+    ///
+    /// The subcommand is named after the cli and takes all of its arguments and
+    /// subcommands, with the description becoming this subcommand's help and the
+    /// version being kept so it can be shown in this subcommand's help. Settings
+    /// for the cli itself such as [CliMake::debug] aren't carried over, as the
+    /// umbrella cli's settings are used whilst parsing.
+    pub fn from_cli(cli: &CliMake<'a>) -> Self {
+        Self {
+            id: DefinitionId::new(),
+            name: cli.name,
+            arguments: cli.arguments.clone(),
+            subcommands: cli.subcommands.clone(),
+            help: cli.description,
+            long_help: None,
+            before_help: cli.before_help,
+            after_help: cli.after_help,
+            version: cli.version,
+            help_if_empty: false,
+            bubble_arguments: None,
+            disable_help_flag: None,
+            disable_version_flag: None,
+            available: None,
+        }
+    }
+
+    /// Sets a predicate deciding if this subcommand is available, chainable
+    ///
+    /// Like [Argument::available_if], unavailable subcommands are hidden from
+    /// help and rejected whilst parsing with an error saying they aren't
+    /// available, e.g. `available_if(|| cfg!(unix))` for a unix-only subcommand.
+    pub fn available_if(&mut self, predicate: impl Fn() -> bool + Send + Sync + 'a) -> &mut Self {
+        self.available = Some(Shared(Arc::new(predicate)));
+        self
+    }
+
+    /// Sets if arguments of parents may be interspersed after this subcommand
+    /// (e.g. `git log -p` rather than only `git -p log`), overriding
+    /// [CliMake::bubble_arguments] for arguments given directly after it,
+    /// chainable
+    ///
+    /// This allows a cli to be strict apart from a few subcommands, or the other
+    /// way around. Arguments set with [Argument::global] may always be given
+    /// after any subcommand.
+    pub fn bubble_arguments(&mut self, bubble: bool) -> &mut Self {
+        self.bubble_arguments = Some(bubble);
+        self
+    }
+
+    /// Sets if the automatic `-h`, `--help` and `--help-search` calls are
+    /// disabled for arguments given directly after this subcommand, overriding
+    /// [CliMake::disable_help_flag], chainable
+    ///
+    /// This allows subcommands such as a pass-through `exec` to receive `-h`
+    /// as an ordinary input whilst help stays available everywhere else.
+    pub fn disable_help_flag(&mut self, disable: bool) -> &mut Self {
+        self.disable_help_flag = Some(disable);
+        self
+    }
+
+    /// Sets if the automatic `-V` and `--version` calls are disabled for
+    /// arguments given directly after this subcommand, overriding
+    /// [CliMake::disable_version_flag], chainable
+    ///
+    /// Alongside [Subcommand::disable_help_flag], this allows pass-through
+    /// subcommands such as `exec` to receive `--version` as an ordinary input.
+    pub fn disable_version_flag(&mut self, disable: bool) -> &mut Self {
+        self.disable_version_flag = Some(disable);
+        self
+    }
+
+    /// Checks if this subcommand is available, see [Subcommand::available_if]
+    pub(crate) fn is_available(&self) -> bool {
+        self.available
+            .as_ref()
+            .map_or(true, |predicate| (predicate.0)())
+    }
+
+    /// Gets the stable identifier of this subcommand, which is unique to where it
+    /// was created and kept whenever it's cloned
+    ///
+    /// This is given to [ParsedSubcommand](crate::parsed::ParsedSubcommand)s as
+    /// well, so parsed subcommands may be matched up to their definition by
+    /// identifier alone. Identifiers are not taken into account when comparing
+    /// subcommands.
+    pub fn id(&self) -> usize {
+        self.id.0
+    }
+
     /// Displays help infomation for this subcommand specifically which is used
     /// inside the execution of the cli
     ///
     /// A referenced [CliMake] is needed for this method due to it displaying a
-    /// header message using [CliMake::header_msg] with an altered usage line, as
-    /// seen in the examples.
-    pub(crate) fn help_msg(&self, climake: &CliMake, buf: &mut impl Write) -> std::io::Result<()> {
-        climake.header_msg(self.name, buf)?;
-
-        match self.help {
-            Some(help) => {
-                buf.write("\nAbout:\n".as_bytes())?;
-                writeln_term(help, buf)?;
-            }
-            None => (),
+    /// usage line using [CliMake::usage_msg] altered to include this subcommand,
+    /// and for its [CliMake::help_template]. Extended help is included if `long`
+    /// is set, as for `--help` rather than `-h`.
+    pub(crate) fn help_msg(
+        &self,
+        climake: &CliMake,
+        long: bool,
+        buf: &mut impl Write,
+    ) -> std::io::Result<()> {
+        let options = RenderOptions {
+            long_help: long,
+            ..climake.render_options()
         };
 
-        // TODO: merge this into a utility func shared with CliMake::help_msg
-        buf.write("\nArguments:\n".as_bytes())?;
+        self.full_msg(Some(climake), &options, buf)
+    }
+
+    /// Displays this subcommand's help laid out by the
+    /// [RenderOptions::help_template], i.e. the usage line of the `climake` it's
+    /// attached to if given, the about section and every section from
+    /// [render_sections]. Examples in the `options` are never shown as they're
+    /// only for the root cli
+    pub(crate) fn full_msg(
+        &self,
+        climake: Option<&CliMake>,
+        options: &RenderOptions,
+        buf: &mut impl Write,
+    ) -> std::io::Result<()> {
+        let header = |placeholder: &str, mut buf: &mut dyn Write| match placeholder {
+            "usage" => match climake {
+                Some(climake) => climake.usage_msg(self.name, &mut buf).map(|_| true),
+                None => Ok(true),
+            },
+            "about" => self.about_msg(options, &mut buf).map(|_| true),
+            _ => Ok(false),
+        };
 
-        if self.arguments.len() > 0 {
-            for argument in self.arguments.iter() {
-                argument.help_name_msg(buf)?;
-            }
-        } else {
-            buf.write("  No arguments found\n".as_bytes())?;
-        }
+        render_sections(
+            &self.arguments,
+            &self.subcommands,
+            &RenderOptions {
+                examples: &[],
+                ..*options
+            },
+            self.before_help,
+            self.after_help,
+            header,
+            buf,
+        )
+    }
 
-        buf.write("\nSubcommands:\n".as_bytes())?;
+    /// Displays the about section of this subcommand's help if it has any help,
+    /// with [Subcommand::long_help] shown in place of the usual help if the
+    /// `options` ask for extended help
+    fn about_msg(&self, options: &RenderOptions, buf: &mut impl Write) -> std::io::Result<()> {
+        let help = self.long_help.filter(|_| options.long_help).or(self.help);
 
-        if self.subcommands.len() > 0 {
-            for subcommand in self.subcommands.iter() {
-                subcommand.help_name_msg(buf)?;
+        match (help, self.version.filter(|_| !options.reproducible)) {
+            (Some(help), Some(version)) => {
+                buf.write_all(format!("\n{}\n", message(Key::AboutHeading, &[])).as_bytes())?;
+                writeln_term(
+                    format!("{} v{} — {}", self.name, version, help),
+                    options.width,
+                    buf,
+                )?;
             }
-        } else {
-            buf.write("  No subcommands found\n".as_bytes())?;
-        }
+            (Some(help), None) => {
+                buf.write_all(format!("\n{}\n", message(Key::AboutHeading, &[])).as_bytes())?;
+                writeln_term(help, options.width, buf)?;
+            }
+            (None, _) => (),
+        };
 
         Ok(())
     }
 
+    /// Checks if the lowercase `query` is contained in this subcommand's name or
+    /// help message, used for searching help
+    pub(crate) fn search(&self, query: &str) -> bool {
+        self.is_available()
+            && (self.name.to_lowercase().contains(query)
+                || self
+                    .help
+                    .into_iter()
+                    .chain(self.long_help)
+                    .any(|help| help.to_lowercase().contains(query)))
+    }
+
     /// Generates compact help message for current [Subcommand]
     ///
     /// This writes directly to a buffer of some kind (typically [std::io::stdout])
@@ -93,19 +298,47 @@ impl<'a> Subcommand<'a> {
     /// ```none
     ///   example — A simple example subcommand
     /// ```
-    pub(crate) fn help_name_msg(&self, buf: &mut impl Write) -> std::io::Result<()> {
+    pub(crate) fn help_name_msg(&self, width: usize, buf: &mut impl Write) -> std::io::Result<()> {
         let formatted_help = match self.help {
             Some(msg) => msg,
-            None => HELP_DEFAULT,
+            None => Key::NoHelp.template(),
         };
 
-        writeln_term(format!("{} — {}", self.name, formatted_help), buf)
+        writeln_term(format!("{} — {}", self.name, formatted_help), width, buf)
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::io::Input;
+
+    /// Checks that the [Subcommand::from_cli] method carries over the cli
+    #[test]
+    fn from_cli() {
+        let arg = Argument::new("An argument", vec!['a'], vec![], Input::None);
+        let inner = Subcommand::new("inner", vec![], vec![], None);
+        let cli = CliMake::new("tool", vec![&arg], vec![&inner], "A tool", "1.0.0");
+
+        let mut expected = Subcommand::new("tool", vec![&arg], vec![&inner], "A tool");
+        expected.version = Some("1.0.0");
+
+        assert_eq!(Subcommand::from_cli(&cli), expected);
+    }
+
+    /// Checks that the [Subcommand::many] method works correctly
+    #[test]
+    fn many() {
+        let subcommands = Subcommand::many(vec![("add", "Adds"), ("remove", "Removes")]);
+
+        assert_eq!(
+            subcommands,
+            vec![
+                Subcommand::new("add", vec![], vec![], "Adds"),
+                Subcommand::new("remove", vec![], vec![], "Removes")
+            ]
+        );
+    }
 
     /// Checks that the [Subcommand::help_name_msg] method works correctly
     #[test]
@@ -113,7 +346,7 @@ mod tests {
         let mut chk_vec: Vec<u8> = vec![];
 
         Subcommand::new("command", vec![], vec![], "A simple command")
-            .help_name_msg(&mut chk_vec)?;
+            .help_name_msg(80, &mut chk_vec)?;
         assert_eq!(
             std::str::from_utf8(chk_vec.as_slice()).unwrap(),
             "  command — A simple command\n"
@@ -2,20 +2,596 @@
 
 use crate::CLI_TABBING;
 
-use std::io::{LineWriter, Write};
+use std::env;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::io::Write;
+use std::path::PathBuf;
+#[cfg(feature = "terminal-size")]
+use std::process::{Command, Stdio};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, OnceLock};
 
-/// Writes a given buffer to terminal using [LineWriter] and splits every 80
-/// characters, making it ideal for concise terminal displays for help messages
+/// Counter used to give each [DefinitionId] a unique value
+static NEXT_ID: AtomicUsize = AtomicUsize::new(0);
+
+/// Stable numeric identifier assigned to each argument or subcommand once it's
+/// defined, which is kept when cloned and passed on to parsed structures
+///
+/// These deliberately don't take part in equality so that two definitions made
+/// in the same way still compare as equal.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct DefinitionId(pub(crate) usize);
+
+impl DefinitionId {
+    /// Assigns a new, unique identifier
+    pub(crate) fn new() -> Self {
+        DefinitionId(NEXT_ID.fetch_add(1, Ordering::Relaxed))
+    }
+}
+
+impl PartialEq for DefinitionId {
+    fn eq(&self, _other: &Self) -> bool {
+        true
+    }
+}
+
+impl Eq for DefinitionId {}
+
+impl Hash for DefinitionId {
+    fn hash<H: Hasher>(&self, _state: &mut H) {} // consistent with always being equal
+}
+
+/// Type-erased predicate stored inside of arguments or subcommands to decide if
+/// they're available, see [Argument::available_if](crate::Argument::available_if)
+pub(crate) type Predicate<'a> = dyn Fn() -> bool + Send + Sync + 'a;
+
+/// Shared and possibly type-erased item, typically a closure, which may be stored
+/// inside of structures without them losing their [Debug], [PartialEq] and
+/// [Clone] derives. Two of these are only equal if they share the same item
+///
+/// Items are kept in an [Arc] rather than an `Rc` so that anything holding them,
+/// such as the cli and its parsed results, stays [Send] and [Sync].
+pub(crate) struct Shared<T: ?Sized>(pub(crate) Arc<T>);
+
+impl<T: ?Sized> Clone for Shared<T> {
+    fn clone(&self) -> Self {
+        Shared(Arc::clone(&self.0))
+    }
+}
+
+impl<T: ?Sized> PartialEq for Shared<T> {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl<T: ?Sized> Eq for Shared<T> {}
+
+impl<T: ?Sized> Hash for Shared<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        (Arc::as_ptr(&self.0) as *const ()).hash(state)
+    }
+}
+
+impl<T: ?Sized> fmt::Debug for Shared<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Shared({:p})", Arc::as_ptr(&self.0))
+    }
+}
+
+/// Writes a given buffer to terminal, tabbed and wrapped by word so no line goes
+/// over `width` characters, making it ideal for concise terminal displays for
+/// help messages
 pub(crate) fn writeln_term(
     to_write: impl Into<String>,
+    width: usize,
     buf: &mut impl Write,
 ) -> std::io::Result<()> {
-    let mut line_buf = LineWriter::new(buf);
-    let newline_byte = "\n".as_bytes();
+    let text_width = width.saturating_sub(CLI_TABBING.chars().count()).max(1);
 
-    for line in to_write.into().as_bytes().chunks(80 - CLI_TABBING.len()) {
-        line_buf.write(&[CLI_TABBING.as_bytes(), line, newline_byte].concat())?;
+    for line in wrap(&to_write.into(), text_width) {
+        buf.write_all(format!("{}{}\n", CLI_TABBING, line).as_bytes())?;
     }
 
     Ok(())
 }
+
+/// Width of the terminal queried from the platform, which is only done once as
+/// it spawns a process
+static QUERIED_WIDTH: OnceLock<Option<usize>> = OnceLock::new();
+
+/// Detects the width of the terminal in columns from the `COLUMNS` environment
+/// variable, falling back to querying the platform if the `terminal-size`
+/// feature is enabled
+pub(crate) fn terminal_width() -> Option<usize> {
+    env::var("COLUMNS")
+        .ok()
+        .and_then(|columns| columns.trim().parse().ok())
+        .or_else(|| *QUERIED_WIDTH.get_or_init(query_terminal_width))
+}
+
+/// Gets the platform's directory for cached per-user files, i.e. `%LOCALAPPDATA%`
+/// on windows, `~/Library/Caches` on macos and `$XDG_CACHE_HOME` or `~/.cache`
+/// elsewhere
+pub(crate) fn cache_dir() -> Option<PathBuf> {
+    let var = |name: &str| {
+        env::var_os(name)
+            .map(PathBuf::from)
+            .filter(|path| path.is_absolute())
+    };
+
+    if cfg!(windows) {
+        var("LOCALAPPDATA")
+    } else if cfg!(target_os = "macos") {
+        var("HOME").map(|home| home.join("Library").join("Caches"))
+    } else {
+        var("XDG_CACHE_HOME").or_else(|| var("HOME").map(|home| home.join(".cache")))
+    }
+}
+
+/// Queries the width of the controlling terminal using `stty`, as the standard
+/// library has no way of doing so without unsafe code
+#[cfg(all(feature = "terminal-size", unix))]
+fn query_terminal_width() -> Option<usize> {
+    let tty = std::fs::File::open("/dev/tty").ok()?;
+    let output = Command::new("stty")
+        .arg("size")
+        .stdin(tty)
+        .stderr(Stdio::null())
+        .output()
+        .ok()?;
+
+    // gives `rows columns`
+    String::from_utf8(output.stdout)
+        .ok()?
+        .split_whitespace()
+        .nth(1)?
+        .parse()
+        .ok()
+}
+
+/// Queries the width of the console window using powershell, as the standard
+/// library has no way of doing so without unsafe code
+#[cfg(all(feature = "terminal-size", windows))]
+fn query_terminal_width() -> Option<usize> {
+    let output = Command::new("powershell")
+        .args(["-NoProfile", "-Command", "$Host.UI.RawUI.WindowSize.Width"])
+        .stderr(Stdio::null())
+        .output()
+        .ok()?;
+
+    String::from_utf8(output.stdout).ok()?.trim().parse().ok()
+}
+
+/// Platform queries are disabled without the `terminal-size` feature
+#[cfg(not(all(feature = "terminal-size", any(unix, windows))))]
+fn query_terminal_width() -> Option<usize> {
+    None
+}
+
+/// Wraps `text` into lines of at most `width` characters, breaking on spaces
+/// where possible and splitting words which are too long to fit on any line
+pub(crate) fn wrap(text: &str, width: usize) -> Vec<String> {
+    let width = width.max(1);
+    let mut lines = vec![];
+
+    for paragraph in text.split('\n') {
+        let mut line = String::new();
+        let mut line_len = 0;
+
+        for word in paragraph.split_whitespace() {
+            let word_len = word.chars().count();
+
+            if line_len != 0 && line_len + 1 + word_len <= width {
+                line.push(' ');
+                line.push_str(word);
+                line_len += 1 + word_len;
+                continue;
+            } else if line_len != 0 {
+                lines.push(std::mem::take(&mut line));
+            }
+
+            let chars: Vec<char> = word.chars().collect();
+            let mut chunks = chars.chunks(width).peekable();
+
+            while let Some(chunk) = chunks.next() {
+                if chunks.peek().is_some() {
+                    lines.push(chunk.iter().collect());
+                } else {
+                    line = chunk.iter().collect();
+                    line_len = chunk.len();
+                }
+            }
+        }
+
+        lines.push(line);
+    }
+
+    lines
+}
+
+/// Hashes `bytes` using 64-bit FNV-1a, which unlike the hashers in the standard
+/// library is guaranteed to give the same result on every platform and version
+pub(crate) fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ *byte as u64).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+/// Quotes `value` as a json string, escaping quotes, backslashes and control
+/// characters
+pub(crate) fn json_quote(value: &str) -> String {
+    let mut quoted = String::from("\"");
+
+    for c in value.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            c if c.is_control() => quoted.push_str(&format!("\\u{:04x}", c as u32)),
+            c => quoted.push(c),
+        }
+    }
+
+    quoted.push('"');
+    quoted
+}
+
+/// Quotes `value` for use as a single word in posix shells, leaving it as-is if
+/// it's only made of characters which are always safe
+pub(crate) fn shell_quote(value: &str) -> String {
+    let safe = |c: char| c.is_ascii_alphanumeric() || "-_./=:,+@%".contains(c);
+
+    if !value.is_empty() && value.chars().all(safe) {
+        value.to_string()
+    } else {
+        format!("'{}'", value.replace('\'', "'\\''"))
+    }
+}
+
+/// Quotes `value` for use as a single word in PowerShell, leaving it as-is if
+/// it's only made of characters which are always safe
+pub(crate) fn powershell_quote(value: &str) -> String {
+    let safe = |c: char| c.is_ascii_alphanumeric() || "-_./\\:=".contains(c);
+
+    if !value.is_empty() && value.chars().all(safe) {
+        value.to_string()
+    } else {
+        format!("'{}'", value.replace('\'', "''"))
+    }
+}
+
+/// Quotes `value` for the shell of the platform we're running on, so suggested
+/// commands can be copied and pasted as-is
+pub(crate) fn platform_quote(value: &str) -> String {
+    if cfg!(windows) {
+        powershell_quote(value)
+    } else {
+        shell_quote(value)
+    }
+}
+
+/// Splits a `line` into words like posix shells do, taking quotes and backslash
+/// escapes into account. Gives back the opening quote if one was never closed
+pub(crate) fn split_shell(line: &str) -> Result<Vec<String>, char> {
+    let mut words = vec![];
+    let mut word: Option<String> = None;
+    let mut chars = line.chars();
+
+    while let Some(c) = chars.next() {
+        match c {
+            c if c.is_whitespace() => words.extend(word.take()),
+            '\\' => {
+                let word = word.get_or_insert_with(String::new);
+                word.push(chars.next().unwrap_or('\\'));
+            }
+            '\'' => {
+                let word = word.get_or_insert_with(String::new);
+                loop {
+                    match chars.next() {
+                        Some('\'') => break,
+                        Some(c) => word.push(c),
+                        None => return Err('\''),
+                    }
+                }
+            }
+            '"' => {
+                let word = word.get_or_insert_with(String::new);
+                loop {
+                    match chars.next() {
+                        Some('"') => break,
+                        Some('\\') => match chars.next() {
+                            Some(c) if "\\\"$`".contains(c) => word.push(c),
+                            Some(c) => {
+                                word.push('\\');
+                                word.push(c);
+                            }
+                            None => return Err('"'),
+                        },
+                        Some(c) => word.push(c),
+                        None => return Err('"'),
+                    }
+                }
+            }
+            c => word.get_or_insert_with(String::new).push(c),
+        }
+    }
+
+    words.extend(word);
+    Ok(words)
+}
+
+/// Splits `value` by the given `delimiter`, with delimiters escaped using a
+/// backslash (e.g. `a\,b`) being kept inside of the value instead
+pub(crate) fn split_escaped(value: &str, delimiter: char) -> Vec<String> {
+    let mut values = vec![String::new()];
+    let mut chars = value.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if c == '\\' && chars.peek() == Some(&delimiter) {
+            values.last_mut().unwrap().push(delimiter);
+            chars.next();
+        } else if c == delimiter {
+            values.push(String::new());
+        } else {
+            values.last_mut().unwrap().push(c);
+        }
+    }
+
+    values
+}
+
+/// Converts rendered `text` into plain ascii, stripping any escape sequences and
+/// swapping out unicode such as em dashes for ascii lookalikes, with anything
+/// left over becoming `?`
+pub(crate) fn to_plain(text: &str) -> String {
+    let mut plain = String::with_capacity(text.len());
+    let mut chars = text.chars();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\x1b' => {
+                // skip escape sequences up to and including their final byte
+                if chars.next() == Some('[') {
+                    for c in chars.by_ref() {
+                        if ('\x40'..='\x7e').contains(&c) {
+                            break;
+                        }
+                    }
+                }
+            }
+            '\n' | '\t' => plain.push(c),
+            c if c.is_ascii_control() => (),
+            c if c.is_ascii() => plain.push(c),
+            '—' | '–' | '‐' | '−' => plain.push('-'),
+            '‘' | '’' => plain.push('\''),
+            '“' | '”' => plain.push('"'),
+            '…' => plain.push_str("..."),
+            '\u{a0}' => plain.push(' '),
+            _ => plain.push('?'),
+        }
+    }
+
+    plain
+}
+
+/// Calculates the levenshtein (edit) distance between two strings, i.e. the
+/// amount of single-character insertions, deletions or substitutions needed to
+/// get from `a` to `b`
+pub(crate) fn levenshtein(a: &str, b: &str) -> usize {
+    let b_chars: Vec<char> = b.chars().collect();
+    let mut prev_row: Vec<usize> = (0..=b_chars.len()).collect();
+
+    for (a_ind, a_char) in a.chars().enumerate() {
+        let mut cur_row = vec![a_ind + 1];
+
+        for (b_ind, b_char) in b_chars.iter().enumerate() {
+            let cost = if a_char == *b_char { 0 } else { 1 };
+
+            cur_row.push(
+                (prev_row[b_ind] + cost)
+                    .min(prev_row[b_ind + 1] + 1)
+                    .min(cur_row[b_ind] + 1),
+            );
+        }
+
+        prev_row = cur_row;
+    }
+
+    prev_row[b_chars.len()]
+}
+
+/// Finds the closest of the given `candidates` to `input` using [levenshtein],
+/// returning nothing if none are close enough to plausibly be a typo
+///
+/// This is the shared "did you mean" helper for anything user-facing which can
+/// be misspelt, such as possible values or argument calls.
+pub(crate) fn suggest<'b>(
+    input: &str,
+    candidates: impl IntoIterator<Item = &'b str>,
+) -> Option<&'b str> {
+    let max_distance = (input.chars().count() / 3).max(1);
+
+    candidates
+        .into_iter()
+        .map(|candidate| (levenshtein(input, candidate), candidate))
+        .filter(|(distance, _)| *distance <= max_distance)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, candidate)| candidate)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Checks that [Shared] items are only equal to clones of themselves
+    #[test]
+    fn shared_eq() {
+        let first: Shared<dyn Fn() -> u8> = Shared(Arc::new(|| 1));
+        let second: Shared<dyn Fn() -> u8> = Shared(Arc::new(|| 1));
+
+        assert_eq!(first, first.clone());
+        assert_ne!(first, second);
+    }
+
+    /// Checks at compile time that the cli and everything parsed from it may be
+    /// sent and shared across threads
+    #[test]
+    fn send_sync() {
+        fn assert_send_sync<T: Send + Sync>() {}
+
+        assert_send_sync::<crate::CliMake>();
+        assert_send_sync::<crate::Argument>();
+        assert_send_sync::<crate::Subcommand>();
+        assert_send_sync::<crate::Error>();
+        assert_send_sync::<crate::parsed::ParsedCli>();
+    }
+
+    /// Checks that each [DefinitionId] is unique but ignored by equality
+    #[test]
+    fn definition_id_unique() {
+        let (first, second) = (DefinitionId::new(), DefinitionId::new());
+
+        assert_ne!(first.0, second.0);
+        assert_eq!(first, second);
+    }
+
+    /// Checks that the [wrap] function wraps by word, even at pathological widths
+    #[test]
+    fn wrap_widths() {
+        assert_eq!(wrap("a few words here", 10), vec!["a few", "words here"]);
+        assert_eq!(wrap("a few words here", 100), vec!["a few words here"]);
+        assert_eq!(wrap("first\nsecond", 80), vec!["first", "second"]);
+        assert_eq!(wrap("", 80), vec![""]);
+        assert_eq!(wrap("añbñc de", 3), vec!["añb", "ñc", "de"]);
+        assert_eq!(wrap("ab c", 1), vec!["a", "b", "c"]);
+        assert_eq!(wrap("ab c", 0), vec!["a", "b", "c"]);
+    }
+
+    /// Checks that the [writeln_term] function tabs each wrapped line
+    #[test]
+    fn writeln_term_width() -> std::io::Result<()> {
+        let mut chk_vec: Vec<u8> = vec![];
+
+        writeln_term("-v — Verbose mode", 12, &mut chk_vec)?;
+        assert_eq!(
+            std::str::from_utf8(chk_vec.as_slice()).unwrap(),
+            "  -v —\n  Verbose\n  mode\n"
+        );
+        chk_vec = vec![];
+
+        writeln_term("abc", 0, &mut chk_vec)?;
+        assert_eq!(
+            std::str::from_utf8(chk_vec.as_slice()).unwrap(),
+            "  a\n  b\n  c\n"
+        );
+
+        Ok(())
+    }
+
+    /// Checks that the [fnv1a] function gives the standard FNV-1a hashes
+    #[test]
+    fn fnv1a_hashes() {
+        assert_eq!(fnv1a(b""), 0xcbf29ce484222325);
+        assert_eq!(fnv1a(b"a"), 0xaf63dc4c8601ec8c);
+        assert_eq!(fnv1a(b"foobar"), 0x85944171f73967e8);
+    }
+
+    /// Checks that the [shell_quote] function only quotes when needed
+    #[test]
+    fn shell_quoting() {
+        assert_eq!(shell_quote("always"), "always");
+        assert_eq!(shell_quote("./a/b.toml"), "./a/b.toml");
+        assert_eq!(shell_quote(""), "''");
+        assert_eq!(shell_quote("two words"), "'two words'");
+        assert_eq!(shell_quote("it's"), "'it'\\''s'");
+        assert_eq!(shell_quote("$HOME"), "'$HOME'");
+    }
+
+    /// Checks that the [powershell_quote] function only quotes when needed
+    #[test]
+    fn powershell_quoting() {
+        assert_eq!(powershell_quote("C:\\out\\a.txt"), "C:\\out\\a.txt");
+        assert_eq!(powershell_quote(""), "''");
+        assert_eq!(powershell_quote("two words"), "'two words'");
+        assert_eq!(powershell_quote("it's"), "'it''s'");
+        assert_eq!(powershell_quote("$env:HOME"), "'$env:HOME'");
+        assert_eq!(powershell_quote("a,b"), "'a,b'");
+    }
+
+    /// Checks that the [json_quote] function escapes everything json needs
+    #[test]
+    fn json_quoting() {
+        assert_eq!(json_quote("1.2.0"), "\"1.2.0\"");
+        assert_eq!(json_quote("say \"hi\"\\"), "\"say \\\"hi\\\"\\\\\"");
+        assert_eq!(json_quote("a\nb\u{7}"), "\"a\\nb\\u0007\"");
+    }
+
+    /// Checks that the [split_shell] function splits words like shells do
+    #[test]
+    fn split_shell_words() {
+        assert_eq!(
+            split_shell("--out 'my file.txt'  build"),
+            Ok(vec![
+                "--out".to_string(),
+                "my file.txt".to_string(),
+                "build".to_string()
+            ])
+        );
+        assert_eq!(
+            split_shell(r#"a\ b "c \"d\" \e" '' x'y'"z""#),
+            Ok(vec![
+                "a b".to_string(),
+                "c \"d\" \\e".to_string(),
+                String::new(),
+                "xyz".to_string()
+            ])
+        );
+        assert_eq!(split_shell("  "), Ok(vec![]));
+        assert_eq!(split_shell("'open"), Err('\''));
+        assert_eq!(split_shell("\"open\\"), Err('"'));
+    }
+
+    /// Checks that the [split_escaped] function keeps escaped delimiters
+    #[test]
+    fn split_escaped_delimiters() {
+        assert_eq!(split_escaped("a,b,c", ','), vec!["a", "b", "c"]);
+        assert_eq!(split_escaped("a\\,b", ','), vec!["a,b"]);
+        assert_eq!(split_escaped("a\\b,", ','), vec!["a\\b", ""]);
+        assert_eq!(split_escaped("", ','), vec![""]);
+    }
+
+    /// Checks that the [to_plain] function leaves only plain ascii
+    #[test]
+    fn plain_ascii() {
+        assert_eq!(to_plain("-v — Verbose\n"), "-v - Verbose\n");
+        assert_eq!(to_plain("\x1b[1;31mError\x1b[0m: bad"), "Error: bad");
+        assert_eq!(to_plain("“añb”…"), "\"a?b\"...");
+        assert_eq!(to_plain("plain\ttext"), "plain\ttext");
+    }
+
+    /// Checks that the [levenshtein] function calculates distances correctly
+    #[test]
+    fn levenshtein_distances() {
+        assert_eq!(levenshtein("", ""), 0);
+        assert_eq!(levenshtein("json", "json"), 0);
+        assert_eq!(levenshtein("jsn", "json"), 1);
+        assert_eq!(levenshtein("kitten", "sitting"), 3);
+        assert_eq!(levenshtein("", "yaml"), 4);
+        assert_eq!(levenshtein("añb", "ab"), 1);
+    }
+
+    /// Checks that the [suggest] function only suggests close candidates
+    #[test]
+    fn suggest_closest() {
+        let candidates = vec!["json", "yaml", "toml"];
+
+        assert_eq!(suggest("jsn", candidates.clone()), Some("json"));
+        assert_eq!(suggest("tml", candidates.clone()), Some("toml"));
+        assert_eq!(suggest("xml", candidates.clone()), None);
+        assert_eq!(suggest("json", vec![]), None);
+    }
+}
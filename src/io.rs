@@ -6,13 +6,18 @@
 //! This module is included in [crate::prelude] by default so no extra importing
 //! steps are required (unless you are importing explicit items).
 
+use std::cmp::Ordering;
+use std::collections::HashSet;
 use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::net::{IpAddr, SocketAddr};
 use std::path::PathBuf;
+use std::time::Duration;
 
 /// An input type, typically given for an [Argument](crate::Argument) to descibe
 /// what types are allowed to be passwed in. This is then transferred to [Data]
 /// once the cli has been executed
-#[derive(Debug, PartialEq, Clone)]
+#[derive(Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Clone)]
 pub enum Input {
     /// No input allowed, will error if any is given. Maps to [Data::None]
     None,
@@ -25,19 +30,53 @@ pub enum Input {
     /// and simply echo the user's input. Maps to [Data::Path]
     Path,
 
+    /// Similar to [Input::Path] but hinting that the path should be to a file
+    /// rather than a directory. Maps to [Data::Path]
+    File,
+
+    /// Similar to [Input::Path] but hinting that the path should be to a
+    /// directory rather than a file. Maps to [Data::Path]
+    Dir,
+
     /// Multiple [PathBuf]s given to the argument, these are not certain to exist
     /// and simply echo the user's input. Maps to [Data::Paths]
+    #[deprecated(note = "use `Input::Path` with `Argument::arity(1..)` instead")]
     Paths,
+
+    /// Text input which must be one of the given possible values, e.g. `json`
+    /// or `yaml` for a format argument. Invalid values will error with the
+    /// closest possible value suggested. Maps to [Data::Text]
+    Choice(Vec<String>),
+
+    /// A floating point number, e.g. `0.5`. Values which aren't a valid number
+    /// will error. Maps to [Data::Float]
+    Float,
+}
+
+impl Input {
+    /// Bare name of this input used for help generation, e.g. `text`, or nothing
+    /// for [Input::None] as no value is allowed
+    #[allow(deprecated)]
+    pub(crate) fn hint(&self) -> Option<String> {
+        match self {
+            Input::None => None,
+            Input::Text => Some("text".to_string()),
+            Input::Path => Some("path".to_string()),
+            Input::File => Some("file".to_string()),
+            Input::Dir => Some("dir".to_string()),
+            Input::Paths => Some("paths".to_string()),
+            Input::Choice(choices) => Some(choices.join("|")),
+            Input::Float => Some("number".to_string()),
+        }
+    }
 }
 
 impl fmt::Display for Input {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         // formatting has a space on existing words on purpouse for help generation
-        match self {
-            Input::None => write!(f, ""),
-            Input::Text => write!(f, "[text] "),
-            Input::Path => write!(f, "[path] "),
-            Input::Paths => write!(f, "[paths] "),
+        match self.hint() {
+            Some(hint) => write!(f, "[{}] ", hint),
+            None => write!(f, ""),
         }
     }
 }
@@ -52,7 +91,12 @@ impl fmt::Display for Input {
 /// once parsed this enumeration would be [Data::Path] (in corrospondance with
 /// the name).
 ///
-#[derive(Debug, PartialEq, Clone)]
+/// # Comparisons
+///
+/// Data may be compared, ordered and hashed, allowing it to be used in sets or
+/// maps. Numbers from [Data::Float] are compared by their total ordering, so
+/// unlike [f64] itself, `NaN` is equal to itself.
+#[derive(Debug, Clone)]
 pub enum Data {
     /// No data provided, from [Input::None]
     None,
@@ -69,29 +113,364 @@ pub enum Data {
     /// vector (i.e. length 0) if the user doesn't provide any paths or may be
     /// non-existant paths given from user input
     Paths(Vec<PathBuf>),
+
+    /// Argument set with [Argument::optional_value](crate::Argument::optional_value)
+    /// was present, with the `value` only being given if the user attached one,
+    /// e.g. `--color` compared to `--color=never`. The contained value maps from
+    /// the argument's [Input] like any other data
+    Flag {
+        /// Value attached to the argument, if any
+        value: Option<Box<Data>>,
+    },
+
+    /// Floating point number provided, from [Input::Float]
+    Float(f64),
+
+    /// Multiple textual inputs provided, from an argument set to
+    /// [RepeatPolicy::Append](crate::RepeatPolicy::Append) which was given more
+    /// than once
+    Texts(Vec<String>),
+
+    /// State of a flag set with [Argument::negatable](crate::Argument::negatable),
+    /// being `false` if it was given in its negated form, e.g. `--no-color`
+    Bool(bool),
+
+    /// Multiple floating point numbers provided, from an [Input::Float] argument
+    /// taking more than one value, e.g. using [Argument::arity](crate::Argument::arity)
+    Floats(Vec<f64>),
+}
+
+impl Data {
+    /// Position of this variant used for ordering data of different variants
+    fn rank(&self) -> u8 {
+        match self {
+            Data::None => 0,
+            Data::Text(_) => 1,
+            Data::Path(_) => 2,
+            Data::Paths(_) => 3,
+            Data::Flag { .. } => 4,
+            Data::Float(_) => 5,
+            Data::Bool(_) => 6,
+            Data::Texts(_) => 7,
+            Data::Floats(_) => 8,
+        }
+    }
+}
+
+impl PartialEq for Data {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Data {}
+
+impl PartialOrd for Data {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Data {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self, other) {
+            (Data::Text(text), Data::Text(other_text)) => text.cmp(other_text),
+            (Data::Path(path), Data::Path(other_path)) => path.cmp(other_path),
+            (Data::Paths(paths), Data::Paths(other_paths)) => paths.cmp(other_paths),
+            (Data::Flag { value }, Data::Flag { value: other_value }) => value.cmp(other_value),
+            (Data::Float(number), Data::Float(other_number)) => number.total_cmp(other_number),
+            (Data::Bool(state), Data::Bool(other_state)) => state.cmp(other_state),
+            (Data::Texts(texts), Data::Texts(other_texts)) => texts.cmp(other_texts),
+            (Data::Floats(numbers), Data::Floats(other_numbers)) => numbers
+                .iter()
+                .zip(other_numbers.iter())
+                .map(|(number, other_number)| number.total_cmp(other_number))
+                .find(|ordering| *ordering != Ordering::Equal)
+                .unwrap_or_else(|| numbers.len().cmp(&other_numbers.len())),
+            _ => self.rank().cmp(&other.rank()),
+        }
+    }
+}
+
+impl Hash for Data {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.rank().hash(state);
+
+        match self {
+            Data::None => (),
+            Data::Text(text) => text.hash(state),
+            Data::Path(path) => path.hash(state),
+            Data::Paths(paths) => paths.hash(state),
+            Data::Flag { value } => value.hash(state),
+            Data::Float(number) => number.to_bits().hash(state),
+            Data::Bool(flag_state) => flag_state.hash(state),
+            Data::Texts(texts) => texts.hash(state),
+            Data::Floats(numbers) => numbers
+                .iter()
+                .map(|number| number.to_bits())
+                .collect::<Vec<u64>>()
+                .hash(state),
+        }
+    }
+}
+
+impl fmt::Display for Data {
+    /// Formats the data as it was given by the user, meaning data with nothing
+    /// to show such as [Data::None] formats as an empty string and multiple paths
+    /// from [Data::Paths] or [Data::Texts] are separated with `, `
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Data::None | Data::Flag { value: None } => Ok(()),
+            Data::Text(text) => write!(f, "{}", text),
+            Data::Path(path) => write!(f, "{}", path.display()),
+            Data::Paths(paths) => write!(
+                f,
+                "{}",
+                paths
+                    .iter()
+                    .map(|path| path.display().to_string())
+                    .collect::<Vec<String>>()
+                    .join(", ")
+            ),
+            Data::Flag { value: Some(value) } => write!(f, "{}", value),
+            Data::Float(number) => write!(f, "{}", number),
+            Data::Bool(state) => write!(f, "{}", state),
+            Data::Texts(texts) => write!(f, "{}", texts.join(", ")),
+            Data::Floats(numbers) => write!(
+                f,
+                "{}",
+                numbers
+                    .iter()
+                    .map(|number| number.to_string())
+                    .collect::<Vec<String>>()
+                    .join(", ")
+            ),
+        }
+    }
 }
 
 impl Data {
     /// Creates a new [Data] from with types mapping from [Input] using passed
     /// `data`. This may map the `data` string vec into types such as `PathBuf`
-    pub(crate) fn new(input: Input, data: impl IntoIterator<Item = String>) -> Self {
-        match input {
+    ///
+    /// If a value given in `data` isn't allowed by the `input`, such as a value
+    /// not included in an [Input::Choice], the offending value will be returned
+    /// as an error for the parser to report.
+    #[allow(deprecated)]
+    pub(crate) fn new(
+        input: &Input,
+        data: impl IntoIterator<Item = String>,
+    ) -> Result<Self, String> {
+        Ok(match input {
             Input::None => Data::None, // ignore passed `data` (if any)
             Input::Text => match data.into_iter().next() {
                 Some(text) => Data::Text(text),
                 None => Data::Text(String::new()),
             },
-            Input::Path => match data.into_iter().next() {
+            Input::Path | Input::File | Input::Dir => match data.into_iter().next() {
                 Some(path_string) => Data::Path(PathBuf::from(path_string)),
                 None => Data::Path(PathBuf::new()),
             },
-            Input::Paths => Data::Paths(
-                data.into_iter()
-                    .map(|path_string| PathBuf::from(path_string))
+            Input::Paths => Data::Paths(data.into_iter().map(PathBuf::from).collect()),
+            Input::Choice(choices) => {
+                let text = data.into_iter().next().unwrap_or_default();
+
+                if !choices.contains(&text) {
+                    return Err(text);
+                }
+
+                Data::Text(text)
+            }
+            Input::Float => {
+                let text = data.into_iter().next().unwrap_or_default();
+
+                match text.parse() {
+                    Ok(number) => Data::Float(number),
+                    Err(_) => return Err(text),
+                }
+            }
+        })
+    }
+
+    /// Creates a new [Data::Flag] for arguments with an optional value, mapping
+    /// the `value` (if one was attached) from [Input] in the same way as [Data::new]
+    pub(crate) fn new_flag(input: &Input, value: Option<String>) -> Result<Self, String> {
+        Ok(Data::Flag {
+            value: match value {
+                Some(value) => Some(Box::new(Data::new(input, vec![value])?)),
+                None => None,
+            },
+        })
+    }
+
+    /// Removes repeated values from [Data::Paths], [Data::Texts] or
+    /// [Data::Floats], keeping the first of each in order, giving back the amount
+    /// of values which were removed. Any other data is left as-is
+    pub(crate) fn dedup(&mut self) -> usize {
+        fn dedup_vec<T: Clone + Eq + Hash>(values: &mut Vec<T>) -> usize {
+            let mut seen = HashSet::new();
+            let before = values.len();
+
+            values.retain(|value| seen.insert(value.clone()));
+            before - values.len()
+        }
+
+        match self {
+            Data::Paths(paths) => dedup_vec(paths),
+            Data::Texts(texts) => dedup_vec(texts),
+            Data::Floats(numbers) => {
+                let mut bits: Vec<u64> = numbers.iter().map(|number| number.to_bits()).collect();
+                let removed = dedup_vec(&mut bits);
+
+                *numbers = bits.into_iter().map(f64::from_bits).collect();
+                removed
+            }
+            _ => 0,
+        }
+    }
+
+    /// Combines the data of every occurrence of an argument set to
+    /// [RepeatPolicy::Append](crate::RepeatPolicy::Append) into a [Data::Paths]
+    /// for path inputs, [Data::Floats] for number inputs or [Data::Texts] for any
+    /// other input taking a value.
+    /// Occurrences of arguments without a value have nothing to combine, so the
+    /// last occurrence is used
+    #[allow(deprecated)]
+    pub(crate) fn append(input: &Input, occurrences: Vec<Data>) -> Self {
+        match input {
+            Input::None => occurrences.into_iter().last().unwrap_or(Data::None),
+            Input::Float => Data::Floats(
+                occurrences
+                    .into_iter()
+                    .filter_map(|data| match data.unflag() {
+                        Data::Float(number) => Some(number),
+                        _ => None,
+                    })
+                    .collect(),
+            ),
+            Input::Path | Input::File | Input::Dir | Input::Paths => Data::Paths(
+                occurrences
+                    .into_iter()
+                    .flat_map(|data| match data.unflag() {
+                        Data::Path(path) => vec![path],
+                        Data::Paths(paths) => paths,
+                        _ => vec![],
+                    })
+                    .collect(),
+            ),
+            _ => Data::Texts(
+                occurrences
+                    .into_iter()
+                    .map(Data::unflag)
+                    .filter(|data| *data != Data::None)
+                    .map(|data| data.to_string())
                     .collect(),
             ),
         }
     }
+
+    /// Takes the value out of a [Data::Flag], with flags given no value becoming
+    /// [Data::None] and any other data being left as-is
+    fn unflag(self) -> Self {
+        match self {
+            Data::Flag { value: Some(value) } => *value,
+            Data::Flag { value: None } => Data::None,
+            data => data,
+        }
+    }
+}
+
+/// Conversion from parsed [Data] into a typed value, used by
+/// [ParsedCli::get](crate::parsed::ParsedCli::get)
+///
+/// This is implemented for common std types, with most being parsed from the
+/// data as it was given (see the [Display](fmt::Display) implementation for
+/// [Data]). It may be implemented for your own types to extract them directly.
+///
+/// # Example
+///
+/// ```rust
+/// use climake::prelude::*;
+/// use std::net::SocketAddr;
+///
+/// let data = Data::Text("127.0.0.1:8080".to_string());
+/// let bind = SocketAddr::from_data(&data).unwrap();
+///
+/// assert_eq!(bind.port(), 8080);
+/// ```
+pub trait FromData: Sized {
+    /// Converts the given `data` into this type, giving nothing if it isn't
+    /// valid for it
+    fn from_data(data: &Data) -> Option<Self>;
+}
+
+/// Implements [FromData] for types which are parsed from the data as it was
+/// given using [FromStr]
+macro_rules! from_str_data {
+    ($($kind:ty),*) => {
+        $(
+            impl FromData for $kind {
+                fn from_data(data: &Data) -> Option<Self> {
+                    data.to_string().parse().ok()
+                }
+            }
+        )*
+    };
+}
+
+from_str_data!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+from_str_data!(f32, String, char, IpAddr, SocketAddr);
+
+impl FromData for f64 {
+    fn from_data(data: &Data) -> Option<Self> {
+        match data {
+            Data::Float(number) => Some(*number),
+            data => data.to_string().parse().ok(),
+        }
+    }
+}
+
+/// Flags without a value (i.e. [Data::None]) are `true` as they were given
+impl FromData for bool {
+    fn from_data(data: &Data) -> Option<Self> {
+        match data {
+            Data::Bool(state) => Some(*state),
+            Data::None | Data::Flag { value: None } => Some(true),
+            data => data.to_string().parse().ok(),
+        }
+    }
+}
+
+impl FromData for PathBuf {
+    fn from_data(data: &Data) -> Option<Self> {
+        match data {
+            Data::Path(path) => Some(path.clone()),
+            Data::None | Data::Paths(_) => None,
+            data => Some(PathBuf::from(data.to_string())),
+        }
+    }
+}
+
+/// Durations are given as a number with an optional unit of `ms`, `s`, `m` or
+/// `h`, e.g. `250ms` or `1.5h`, with numbers on their own being in seconds
+impl FromData for Duration {
+    fn from_data(data: &Data) -> Option<Self> {
+        let text = data.to_string();
+        let split = text
+            .find(|c: char| !c.is_ascii_digit() && c != '.')
+            .unwrap_or(text.len());
+        let (number, unit) = text.split_at(split);
+        let number: f64 = number.parse().ok()?;
+        let seconds = match unit.trim() {
+            "ms" => number / 1000.0,
+            "" | "s" => number,
+            "m" => number * 60.0,
+            "h" => number * 3600.0,
+            _ => return None,
+        };
+
+        Duration::try_from_secs_f64(seconds).ok()
+    }
 }
 
 #[cfg(test)]
@@ -100,44 +479,245 @@ mod tests {
 
     /// Checks that the [Data::new] method works correctly
     #[test]
+    #[allow(deprecated)]
     fn data_new() {
         let testval = String::from("Hi!");
 
         // Data::None
-        assert_eq!(Data::new(Input::None, vec![]), Data::None);
-        assert_eq!(Data::new(Input::None, vec![testval.clone()]), Data::None);
+        assert_eq!(Data::new(&Input::None, vec![]).unwrap(), Data::None);
+        assert_eq!(
+            Data::new(&Input::None, vec![testval.clone()]).unwrap(),
+            Data::None
+        );
 
         // Data::Text
-        assert_eq!(Data::new(Input::Text, vec![]), Data::Text(String::new()));
         assert_eq!(
-            Data::new(Input::Text, vec![testval.clone()]),
+            Data::new(&Input::Text, vec![]).unwrap(),
+            Data::Text(String::new())
+        );
+        assert_eq!(
+            Data::new(&Input::Text, vec![testval.clone()]).unwrap(),
             Data::Text(testval.clone())
         );
         assert_eq!(
-            Data::new(Input::Text, vec![testval.clone(), testval.clone()]),
+            Data::new(&Input::Text, vec![testval.clone(), testval.clone()]).unwrap(),
             Data::Text(testval.clone())
         );
 
         // Data::Path
-        assert_eq!(Data::new(Input::Path, vec![]), Data::Path(PathBuf::new()));
         assert_eq!(
-            Data::new(Input::Path, vec![testval.clone()]),
+            Data::new(&Input::Path, vec![]).unwrap(),
+            Data::Path(PathBuf::new())
+        );
+        assert_eq!(
+            Data::new(&Input::Path, vec![testval.clone()]).unwrap(),
             Data::Path(PathBuf::from(testval.clone()))
         );
         assert_eq!(
-            Data::new(Input::Path, vec![testval.clone(), testval.clone()]),
+            Data::new(&Input::Path, vec![testval.clone(), testval.clone()]).unwrap(),
             Data::Path(PathBuf::from(testval.clone()))
         );
 
         // Data::Paths
-        assert_eq!(Data::new(Input::Paths, vec![]), Data::Paths(vec![]));
         assert_eq!(
-            Data::new(Input::Paths, vec![testval.clone()]),
+            Data::new(&Input::Paths, vec![]).unwrap(),
+            Data::Paths(vec![])
+        );
+        assert_eq!(
+            Data::new(&Input::Paths, vec![testval.clone()]).unwrap(),
             Data::Paths(vec![PathBuf::from(testval.clone())])
         );
         assert_eq!(
-            Data::new(Input::Paths, vec![testval.clone(), testval.clone()]),
+            Data::new(&Input::Paths, vec![testval.clone(), testval.clone()]).unwrap(),
             Data::Paths(vec![PathBuf::from(testval.clone()), PathBuf::from(testval)])
         );
     }
+
+    /// Checks that the [Data::new] method maps [Input::File] and [Input::Dir] to
+    /// paths, with their own hints for help
+    #[test]
+    fn data_new_file_dir() {
+        for input in [Input::File, Input::Dir] {
+            assert_eq!(
+                Data::new(&input, vec!["a/b".to_string()]),
+                Ok(Data::Path(PathBuf::from("a/b")))
+            );
+        }
+
+        assert_eq!(Input::File.to_string(), "[file] ");
+        assert_eq!(Input::Dir.to_string(), "[dir] ");
+    }
+
+    /// Checks that the [Data::new] method only allows possible values for
+    /// [Input::Choice]
+    #[test]
+    fn data_new_choice() {
+        let input = Input::Choice(vec!["json".to_string(), "yaml".to_string()]);
+
+        assert_eq!(
+            Data::new(&input, vec!["json".to_string()]),
+            Ok(Data::Text("json".to_string()))
+        );
+        assert_eq!(
+            Data::new(&input, vec!["jsn".to_string()]),
+            Err("jsn".to_string())
+        );
+        assert_eq!(Data::new(&input, vec![]), Err(String::new()));
+        assert_eq!(format!("{}", input), "[json|yaml] ");
+    }
+
+    /// Checks that the [Data::new] method parses [Input::Float] numbers
+    #[test]
+    fn data_new_float() {
+        assert_eq!(
+            Data::new(&Input::Float, vec!["-0.5".to_string()]),
+            Ok(Data::Float(-0.5))
+        );
+        assert_eq!(
+            Data::new(&Input::Float, vec!["3".to_string()]),
+            Ok(Data::Float(3.0))
+        );
+        assert_eq!(
+            Data::new(&Input::Float, vec!["0,5".to_string()]),
+            Err("0,5".to_string())
+        );
+        assert_eq!(Data::new(&Input::Float, vec![]), Err(String::new()));
+    }
+
+    /// Checks that the [fmt::Display] implementation for [Data] works correctly
+    #[test]
+    fn data_display() {
+        assert_eq!(Data::None.to_string(), "");
+        assert_eq!(Data::Text("serde".to_string()).to_string(), "serde");
+        assert_eq!(Data::Path(PathBuf::from("a/b")).to_string(), "a/b");
+        assert_eq!(
+            Data::Paths(vec![PathBuf::from("a"), PathBuf::from("b")]).to_string(),
+            "a, b"
+        );
+        assert_eq!(Data::Flag { value: None }.to_string(), "");
+        assert_eq!(
+            Data::Flag {
+                value: Some(Box::new(Data::Float(0.5)))
+            }
+            .to_string(),
+            "0.5"
+        );
+    }
+
+    /// Checks that the [Data::append] method combines occurrences by input
+    #[test]
+    fn data_append() {
+        assert_eq!(
+            Data::append(
+                &Input::Path,
+                vec![Data::Paths(vec!["a".into()]), Data::Paths(vec!["b".into()])]
+            ),
+            Data::Paths(vec!["a".into(), "b".into()])
+        );
+        assert_eq!(
+            Data::append(
+                &Input::Float,
+                vec![
+                    Data::Float(0.5),
+                    Data::Flag { value: None },
+                    Data::Flag {
+                        value: Some(Box::new(Data::Float(2.0)))
+                    }
+                ]
+            ),
+            Data::Floats(vec![0.5, 2.0])
+        );
+        assert_eq!(
+            Data::append(&Input::None, vec![Data::None, Data::None]),
+            Data::None
+        );
+    }
+
+    /// Checks that the [Data::dedup] method keeps the first of each value
+    #[test]
+    fn data_dedup() {
+        let mut data = Data::Texts(vec!["b".into(), "a".into(), "b".into(), "b".into()]);
+        assert_eq!(data.dedup(), 2);
+        assert_eq!(data, Data::Texts(vec!["b".into(), "a".into()]));
+
+        let mut data = Data::Floats(vec![0.5, 2.0, 0.5]);
+        assert_eq!(data.dedup(), 1);
+        assert_eq!(data, Data::Floats(vec![0.5, 2.0]));
+
+        let mut data = Data::Text("a".into());
+        assert_eq!(data.dedup(), 0);
+    }
+
+    /// Checks that [Data] can be used inside of sets, including floats
+    #[test]
+    fn data_set() {
+        let mut set = std::collections::HashSet::new();
+
+        set.insert(Data::Path(PathBuf::from("a")));
+        set.insert(Data::Path(PathBuf::from("a")));
+        set.insert(Data::Float(f64::NAN));
+        set.insert(Data::Float(f64::NAN));
+        set.insert(Data::None);
+
+        assert_eq!(set.len(), 3);
+        assert!(Data::None < Data::Text(String::new()));
+        assert!(Data::Float(-1.0) < Data::Float(0.5));
+    }
+
+    /// Checks that the [Data::new_flag] method works correctly
+    #[test]
+    fn data_new_flag() {
+        assert_eq!(
+            Data::new_flag(&Input::Text, None),
+            Ok(Data::Flag { value: None })
+        );
+        assert_eq!(
+            Data::new_flag(&Input::Text, Some("never".to_string())),
+            Ok(Data::Flag {
+                value: Some(Box::new(Data::Text("never".to_string())))
+            })
+        );
+        assert_eq!(
+            Data::new_flag(
+                &Input::Choice(vec!["auto".to_string()]),
+                Some("nevr".to_string())
+            ),
+            Err("nevr".to_string())
+        );
+    }
+
+    /// Checks that [FromData] converts data into common std types
+    #[test]
+    fn data_from() {
+        let text = |text: &str| Data::Text(text.to_string());
+
+        assert_eq!(u16::from_data(&text("8080")), Some(8080));
+        assert_eq!(i8::from_data(&text("-3")), Some(-3));
+        assert_eq!(u8::from_data(&text("256")), None);
+        assert_eq!(f64::from_data(&Data::Float(0.5)), Some(0.5));
+        assert_eq!(bool::from_data(&Data::None), Some(true));
+        assert_eq!(bool::from_data(&Data::Bool(false)), Some(false));
+        assert_eq!(char::from_data(&text("x")), Some('x'));
+        assert_eq!(
+            PathBuf::from_data(&Data::Path("a/b".into())),
+            Some(PathBuf::from("a/b"))
+        );
+        assert_eq!(
+            IpAddr::from_data(&text("::1")),
+            Some(IpAddr::from([0, 0, 0, 0, 0, 0, 0, 1]))
+        );
+        assert_eq!(
+            Duration::from_data(&text("250ms")),
+            Some(Duration::from_millis(250))
+        );
+        assert_eq!(
+            Duration::from_data(&text("1.5m")),
+            Some(Duration::from_secs(90))
+        );
+        assert_eq!(
+            Duration::from_data(&text("3")),
+            Some(Duration::from_secs(3))
+        );
+        assert_eq!(Duration::from_data(&text("3 days")), None);
+    }
 }
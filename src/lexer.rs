@@ -0,0 +1,252 @@
+//! Low-level lexing of raw inputs into classified [Token]s, used internally
+//! whilst parsing
+//!
+//! This is independent of any cli definition, so it may be used to build custom
+//! parsing flows on top of climake's lexing rules, such as finding a config file
+//! in a first pass before the full cli is made.
+//!
+//! # Rules
+//!
+//! - `--` is a [Token::Terminator], with every input after it being a
+//!   [Token::Positional]
+//! - `--name` or `--name=value` is a [Token::Long]
+//! - `-abc` is a [Token::ShortCluster] of `abc`
+//! - Anything else, including a lone `-` (typically meaning stdin) is a
+//!   [Token::Positional]
+//!
+//! # Importing
+//!
+//! This module isn't included in [crate::prelude] as most clis won't need it, so
+//! it should be imported explicitly where needed.
+
+use std::fmt;
+use std::iter::Peekable;
+
+/// A single classified input, see the [module](self) documentation for the rules
+/// used to classify them
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+pub enum Token {
+    /// Cluster of one or more short calls after a single dash, e.g. `abc` from
+    /// `-abc`
+    ShortCluster(String),
+
+    /// Long call after a double dash, e.g. `--name` or `--name=value`
+    Long {
+        /// Name of the call, e.g. `name`
+        name: String,
+
+        /// Value attached to the call using `=`, if any
+        inline_value: Option<String>,
+    },
+
+    /// Input which isn't a call, such as a subcommand name or a value
+    Positional(String),
+
+    /// The `--` input which ends calls, making every input after it positional
+    Terminator,
+}
+
+impl Token {
+    /// Classifies a single raw `input`, not taking any previous
+    /// [Token::Terminator] into account
+    pub fn classify(input: impl Into<String>) -> Self {
+        let input = input.into();
+
+        if input == "--" {
+            Token::Terminator
+        } else if let Some(call) = input.strip_prefix("--") {
+            match call.split_once('=') {
+                Some((name, value)) => Token::Long {
+                    name: name.to_string(),
+                    inline_value: Some(value.to_string()),
+                },
+                None => Token::Long {
+                    name: call.to_string(),
+                    inline_value: None,
+                },
+            }
+        } else if input.len() > 1 && input.starts_with('-') {
+            Token::ShortCluster(input[1..].to_string())
+        } else {
+            Token::Positional(input)
+        }
+    }
+}
+
+/// Displays the token as it would've been input, e.g. `-abc` or `--name=value`
+impl fmt::Display for Token {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Token::ShortCluster(cluster) => write!(f, "-{}", cluster),
+            Token::Long {
+                name,
+                inline_value: Some(value),
+            } => write!(f, "--{}={}", name, value),
+            Token::Long {
+                name,
+                inline_value: None,
+            } => write!(f, "--{}", name),
+            Token::Positional(input) => write!(f, "{}", input),
+            Token::Terminator => write!(f, "--"),
+        }
+    }
+}
+
+/// Iterator over the [Token]s of some raw inputs, created using [lex]
+///
+/// As values for calls may look like calls themselves (e.g. `-5` for a number),
+/// the next input may also be taken raw using [Lexer::next_raw].
+#[derive(Debug, Clone)]
+pub struct Lexer<I: Iterator<Item = String>> {
+    /// Raw inputs left to lex
+    inputs: Peekable<I>,
+
+    /// If a [Token::Terminator] has been lexed
+    terminated: bool,
+
+    /// Amount of raw inputs taken so far, see [Lexer::taken]
+    taken: usize,
+}
+
+impl<I: Iterator<Item = String>> Lexer<I> {
+    /// Peeks at the next input without lexing it
+    pub fn peek_raw(&mut self) -> Option<&String> {
+        self.inputs.peek()
+    }
+
+    /// Takes the next input without lexing it, which is useful for values
+    pub fn next_raw(&mut self) -> Option<String> {
+        let input = self.inputs.next()?;
+        self.taken += 1;
+        Some(input)
+    }
+
+    /// Gets the amount of raw inputs taken so far, which is the position of the
+    /// last input taken counting from `1`
+    pub fn taken(&self) -> usize {
+        self.taken
+    }
+
+    /// Checks if a [Token::Terminator] has been lexed, meaning every token after
+    /// it is a [Token::Positional]
+    pub fn terminated(&self) -> bool {
+        self.terminated
+    }
+}
+
+impl<I: Iterator<Item = String>> Iterator for Lexer<I> {
+    type Item = Token;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let input = self.next_raw()?;
+
+        if self.terminated {
+            return Some(Token::Positional(input));
+        }
+
+        let token = Token::classify(input);
+        self.terminated = token == Token::Terminator;
+
+        Some(token)
+    }
+}
+
+/// Lexes the given raw `inputs` into [Token]s, see the [module](self)
+/// documentation for the rules used
+///
+/// # Example
+///
+/// ```rust
+/// use climake::lexer::{lex, Token};
+///
+/// let inputs = vec!["-v", "--config=my.toml", "add"];
+/// let tokens: Vec<Token> = lex(inputs.into_iter().map(String::from)).collect();
+///
+/// assert_eq!(tokens[0], Token::ShortCluster("v".to_string()));
+/// assert_eq!(tokens[2], Token::Positional("add".to_string()));
+/// ```
+pub fn lex<I: IntoIterator<Item = String>>(inputs: I) -> Lexer<I::IntoIter> {
+    Lexer {
+        inputs: inputs.into_iter().peekable(),
+        terminated: false,
+        taken: 0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Lexes string slices for easier testing
+    fn lex_strs(inputs: &[&str]) -> Vec<Token> {
+        lex(inputs.iter().map(|input| input.to_string())).collect()
+    }
+
+    /// Checks that the [Token::classify] method works correctly
+    #[test]
+    fn token_classify() {
+        assert_eq!(Token::classify("--"), Token::Terminator);
+        assert_eq!(Token::classify("-"), Token::Positional("-".to_string()));
+        assert_eq!(Token::classify("add"), Token::Positional("add".to_string()));
+        assert_eq!(
+            Token::classify("-ab"),
+            Token::ShortCluster("ab".to_string())
+        );
+        assert_eq!(
+            Token::classify("--name"),
+            Token::Long {
+                name: "name".to_string(),
+                inline_value: None
+            }
+        );
+        assert_eq!(
+            Token::classify("--name=a=b"),
+            Token::Long {
+                name: "name".to_string(),
+                inline_value: Some("a=b".to_string())
+            }
+        );
+        assert_eq!(
+            Token::classify("--name="),
+            Token::Long {
+                name: "name".to_string(),
+                inline_value: Some(String::new())
+            }
+        );
+    }
+
+    /// Checks that [lex] makes everything after a terminator positional
+    #[test]
+    fn lex_terminator() {
+        assert_eq!(
+            lex_strs(&["-v", "--", "--name", "--"]),
+            vec![
+                Token::ShortCluster("v".to_string()),
+                Token::Terminator,
+                Token::Positional("--name".to_string()),
+                Token::Positional("--".to_string())
+            ]
+        );
+    }
+
+    /// Checks that raw inputs can be taken between tokens
+    #[test]
+    fn lex_raw() {
+        let mut lexer = lex(vec!["--ratio", "-5", "-v"].into_iter().map(String::from));
+
+        assert!(matches!(lexer.next(), Some(Token::Long { .. })));
+        assert_eq!(lexer.peek_raw().map(|input| input.as_str()), Some("-5"));
+        assert_eq!(lexer.next_raw(), Some("-5".to_string()));
+        assert_eq!(lexer.next(), Some(Token::ShortCluster("v".to_string())));
+        assert!(!lexer.terminated());
+        assert_eq!(lexer.next(), None);
+    }
+
+    /// Checks that tokens are displayed as they were input
+    #[test]
+    fn token_display() {
+        for input in &["-ab", "--name", "--name=value", "add", "--"] {
+            assert_eq!(Token::classify(*input).to_string(), *input);
+        }
+    }
+}
@@ -32,10 +32,10 @@
 //!     let parsed = cli.parse();
 //!
 //!     for subcommand in parsed.subcommands {
-//!         if subcommand.inner == &add {
-//!             println!("Adding package {:?}..", subcommand.arguments[0]);
-//!         } else if subcommand.inner == &rem {
-//!             println!("Removing package {:?}..", subcommand.arguments[0]);
+//!         if subcommand == add {
+//!             println!("Adding package {}..", subcommand.arguments[0].data);
+//!         } else if subcommand == rem {
+//!             println!("Removing package {}..", subcommand.arguments[0].data);
 //!         }
 //!     }
 //! }
@@ -64,17 +64,44 @@
     html_favicon_url = "https://github.com/rust-cli/climake/raw/master/logo.png"
 )]
 
-/// Default help message for [Argument]s without help added
-const HELP_DEFAULT: &str = "No help provided";
-
 /// Tabs to render for cli arguments. This will be subtracted from 80 char width
 /// of terminals allowed so spaces are reccomended
 const CLI_TABBING: &str = "  ";
 
+/// Default maximum width of help messages, see [CliMake::max_width]
+const HELP_MAX_WIDTH: usize = 80;
+
+/// Hard minimum width of help messages which can't be lowered, see
+/// [CliMake::min_width]
+const HELP_MIN_WIDTH: usize = 20;
+
+/// Default maximum depth subcommands may be nested to whilst parsing, see
+/// [CliMake::max_depth]
+const MAX_DEPTH: usize = 64;
+
+/// Default amount of [Argument]s a cli or subcommand may have before help for
+/// arguments in named sections is collapsed, see [CliMake::section_threshold]
+const SECTION_THRESHOLD: usize = 20;
+
+/// Default maximum length of help messages before [CliMake::validate] flags them,
+/// see [CliMake::help_lint_width]
+const HELP_LINT_WIDTH: usize = 60;
+
+/// Default layout of help messages, see [CliMake::help_template]
+const HELP_TEMPLATE: &str = "{before-help}{usage}{about}{arguments}{subcommands}{after-help}";
+
+/// Default exit code used when parsing fails due to bad user input, see
+/// [CliMake::error_code]
+const ERROR_CODE: i32 = 2;
+
 mod core;
 
 pub mod io;
+pub mod lexer;
+pub mod messages;
 pub mod parsed;
 pub mod prelude;
+pub mod testing;
+pub mod text;
 
 pub use crate::core::*;
@@ -0,0 +1,241 @@
+//! Catalog of every user-facing sentence climake generates, each with a stable
+//! [Key] so messages can be referred to without matching english text which may
+//! change between releases
+//!
+//! # Importing
+//!
+//! This module isn't included in [crate::prelude] as most clis won't need it, so
+//! it should be imported explicitly where needed.
+//!
+//! # Example
+//!
+//! ```rust
+//! use climake::messages::Key;
+//! use climake::prelude::*;
+//!
+//! let cli = CliMake::new("tool", vec![], vec![], None, None);
+//! let inputs = vec!["tool", "--unknown"].into_iter().map(String::from);
+//! let error = cli.try_parse_custom(inputs).unwrap_err();
+//!
+//! assert_eq!(error.key(), Key::ArgumentNotFound);
+//! assert_eq!(error.key().id(), "argument-not-found");
+//! ```
+
+use std::fmt;
+
+/// Generates the [Key] enum from each message's key, stable identifier and
+/// english template, keeping all three next to each other
+macro_rules! catalog {
+    ($($(#[$doc:meta])* $key:ident = $id:literal => $template:literal,)*) => {
+        /// Stable key of a single message in the catalog, see [Key::id]
+        #[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+        #[non_exhaustive]
+        pub enum Key {
+            $($(#[$doc])* $key,)*
+        }
+
+        impl Key {
+            /// Every key in the catalog, in the order they're defined
+            pub const ALL: &'static [Key] = &[$(Key::$key,)*];
+
+            /// Gets the stable identifier of this message, e.g.
+            /// `argument-not-found`, which is never changed once released
+            pub fn id(self) -> &'static str {
+                match self {
+                    $(Key::$key => $id,)*
+                }
+            }
+
+            /// Gets the english template of this message, with `{}` placeholders
+            /// which are filled in order
+            pub fn template(self) -> &'static str {
+                match self {
+                    $(Key::$key => $template,)*
+                }
+            }
+        }
+    };
+}
+
+catalog! {
+    /// Subcommand which doesn't exist when no subcommands are available
+    SubcommandNotFoundNone = "subcommand-not-found-none"
+        => "No such subcommand '{}', no subcommands are available",
+    /// Subcommand which doesn't exist, listing those which are available
+    SubcommandNotFound = "subcommand-not-found"
+        => "No such subcommand '{}', available subcommands are {}",
+    /// Subcommand which doesn't exist, listing some of those which are available
+    SubcommandNotFoundMore = "subcommand-not-found-more"
+        => "No such subcommand '{}', available subcommands are {} and {} more",
+    /// Value which isn't one of the possible values, with the closest one
+    InvalidChoiceSuggestion = "invalid-choice-suggestion"
+        => "'{}' is not valid for {}; did you mean {}?",
+    /// Value which isn't one of the possible values
+    InvalidChoice = "invalid-choice" => "'{}' is not valid for {}; possible values are {}",
+    /// Value which wasn't attached using `=` when it must be
+    EqualsRequired = "equals-required" => "A value for {} must be attached using '=', e.g. {}=value",
+    /// Short call taking a value whose cluster could also be other calls
+    AmbiguousCluster = "ambiguous-cluster"
+        => "{} takes a value, so '{}' in '{}{}' would be taken as its value rather than as arguments; use '{}={}' or '{} {}' for a value, or '-{} {} <value>' for separate arguments",
+    /// Value which couldn't be parsed into the expected input
+    InvalidValue = "invalid-value" => "'{}' is not a valid {} for {}",
+    /// Value rejected by an argument's validator
+    Invalidated = "invalidated" => "Invalid value for {}: {}",
+    /// Error caused by a value read from an environment variable
+    FromEnv = "from-env" => "{} (from environment variable {})",
+    /// Value which is missing as another argument was found instead
+    MissingValueFound = "missing-value-found"
+        => "Missing value for {} [{}], found argument '{}' instead",
+    /// Value which is missing
+    MissingValue = "missing-value" => "Missing value for {} [{}]",
+    /// Wrong amount of values
+    ValueCount = "value-count" => "Expected {} values for {}, found {}",
+    /// Argument of a parent given after one of its subcommands
+    ParentArgument = "parent-argument"
+        => "{} isn't an argument of '{}' but exists for '{}', so must be given before '{}'",
+    /// Argument which doesn't exist, with the closest one
+    ArgumentNotFoundSuggestion = "argument-not-found-suggestion"
+        => "No such argument '{}'; did you mean '{}'?",
+    /// Argument which doesn't exist
+    ArgumentNotFound = "argument-not-found" => "No such argument '{}'",
+    /// Abbreviated call matching more than one argument
+    AmbiguousCall = "ambiguous-call" => "'{}' is ambiguous, it could mean {}",
+    /// Abbreviated name matching more than one subcommand
+    AmbiguousSubcommand = "ambiguous-subcommand"
+        => "'{}' is ambiguous, it could mean subcommands {}",
+    /// More than one required argument which is missing
+    MissingArguments = "missing-arguments" => "Missing required arguments {} and {}",
+    /// Required argument which is missing
+    MissingArgument = "missing-argument" => "Missing required argument {}",
+    /// Required argument of a subcommand which is missing, as listed by
+    /// [Key::MissingArguments] and [Key::MissingArgument]
+    MissingSubcommandArgument = "missing-subcommand-argument" => "{} for '{}'",
+    /// Value given to an argument which doesn't take one
+    UnexpectedValue = "unexpected-value" => "{} doesn't take a value, but was given '{}'",
+    /// Argument or subcommand which isn't available
+    Unavailable = "unavailable" => "{} isn't available on this platform or configuration",
+    /// Argument which may only be given once being repeated
+    Duplicate = "duplicate" => "Argument {} may only be given once",
+    /// Argument given more often than its limit
+    TooManyOccurrences = "too-many-occurrences"
+        => "Argument {} may be given at most {} times, found {}",
+    /// Quote which was never closed
+    UnclosedQuote = "unclosed-quote" => "Missing closing quote ({}) in input",
+    /// Subcommands nested past the limit
+    TooDeep = "too-deep" => "Subcommands are nested too deeply, the limit is {} levels",
+    /// Rendered help, which is shown as-is
+    Help = "help" => "{}",
+    /// Rendered version, which is shown as-is
+    Version = "version" => "{}",
+    /// Heading of many errors, which are listed below it
+    MultipleErrors = "multiple-errors" => "Found {} errors:",
+    /// Error shown after the usage of the cli
+    Error = "error" => "Error: {}",
+    /// Hint shown after errors
+    HelpHint = "help-hint" => "Try '--help' for more information",
+    /// Warning for an old spelling of a call being used
+    RenamedWarning = "renamed-warning" => "Warning: {} is deprecated, use {} instead",
+    /// Warning for a call which is defined by many arguments
+    DuplicateCallWarning = "duplicate-call-warning"
+        => "Warning: {} is defined by multiple arguments, using the first declared",
+    /// Usage line of the cli
+    Usage = "usage" => "Usage: ./{} [OPTIONS]",
+    /// Usage line of a subcommand
+    SubcommandUsage = "subcommand-usage" => "Usage: ./{} {} [OPTIONS]",
+    /// Heading of the about section of a subcommand
+    AboutHeading = "about-heading" => "About:",
+    /// Heading of the arguments section
+    ArgumentsHeading = "arguments-heading" => "Arguments:",
+    /// Heading of the arguments in a single section
+    SectionHeading = "section-heading" => "Arguments ({}):",
+    /// Heading of the subcommands section
+    SubcommandsHeading = "subcommands-heading" => "Subcommands:",
+    /// Heading of the examples section
+    ExamplesHeading = "examples-heading" => "Examples:",
+    /// Heading of collapsed argument sections
+    SectionsHeading = "sections-heading" => "Argument sections (see --help <section>):",
+    /// Summary of a collapsed argument section
+    SectionSummary = "section-summary" => "{} — {} arguments",
+    /// Placeholder for when there are no arguments
+    NoArguments = "no-arguments" => "No arguments found",
+    /// Placeholder for when there are no arguments in a section
+    NoSectionArguments = "no-section-arguments" => "No arguments found in section '{}'",
+    /// Placeholder for when there are no subcommands
+    NoSubcommands = "no-subcommands" => "No subcommands found",
+    /// Placeholder for arguments or subcommands without any help
+    NoHelp = "no-help" => "No help provided",
+    /// Marker for required arguments
+    Required = "required" => "[REQUIRED]",
+    /// Marker for arguments read from an environment variable
+    EnvMarker = "env-marker" => "[env: {}]",
+    /// Limit on how many times an argument may be given, shown in long help
+    MaxOccurrences = "max-occurrences" => "May be given at most {} times.",
+    /// Heading of help search results
+    SearchResults = "search-results" => "Results for '{}':",
+    /// Placeholder for when help searches find nothing
+    NoResults = "no-results" => "No results found",
+    /// Tip shown after a successful parse
+    Tip = "tip" => "tip: {}",
+    /// Argument given by the user when explaining parsed data
+    GivenByInput = "given-by-input" => "given by input {}",
+    /// Argument read from an environment variable when explaining parsed data
+    ReadFromEnv = "read-from-env" => "read from environment variable {}",
+    /// Argument filled in otherwise when explaining parsed data
+    FilledIn = "filled-in" => "filled in whilst parsing",
+}
+
+impl fmt::Display for Key {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.id())
+    }
+}
+
+/// Fills in the `{}` placeholders of the template for `key` with `args` in
+/// order, leaving any placeholders without an argument empty
+pub(crate) fn message(key: Key, args: &[&dyn fmt::Display]) -> String {
+    let mut args = args.iter();
+    let mut parts = key.template().split("{}");
+    let mut message = parts.next().unwrap_or_default().to_string();
+
+    for part in parts {
+        if let Some(arg) = args.next() {
+            message.push_str(&arg.to_string());
+        }
+
+        message.push_str(part);
+    }
+
+    message
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Checks that every [Key] has a unique, lowercase and hyphenated id
+    #[test]
+    fn key_ids() {
+        let mut ids: Vec<&str> = Key::ALL.iter().map(|key| key.id()).collect();
+        assert!(ids
+            .iter()
+            .all(|id| id.chars().all(|c| c.is_ascii_lowercase() || c == '-')));
+
+        ids.sort_unstable();
+        ids.dedup();
+        assert_eq!(ids.len(), Key::ALL.len());
+    }
+
+    /// Checks that the [message] function fills in placeholders in order
+    #[test]
+    fn message_placeholders() {
+        assert_eq!(
+            message(Key::ValueCount, &[&2, &"--size", &3]),
+            "Expected 2 values for --size, found 3"
+        );
+        assert_eq!(message(Key::NoResults, &[]), "No results found");
+        assert_eq!(
+            message(Key::MissingValue, &[&"-o"]),
+            "Missing value for -o []"
+        );
+    }
+}
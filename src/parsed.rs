@@ -5,9 +5,27 @@
 //! This module is included in [crate::prelude] by default so no extra importing
 //! steps are required (unless you are importing explicit items).
 
-use crate::io::Data;
+use crate::core::utils::Shared;
+use crate::io::{Data, FromData};
+use crate::messages::{message, Key};
 use crate::{Argument, Subcommand};
 
+use std::any::Any;
+use std::io::{self, Write};
+use std::str::FromStr;
+
+/// Finds the last of the given `arguments` called by `name`, see
+/// [ParsedCli::value_or]
+fn find_named<'b, 'a>(
+    arguments: &'b [ParsedArgument<'a>],
+    name: &str,
+) -> Option<&'b ParsedArgument<'a>> {
+    arguments
+        .iter()
+        .rev()
+        .find(|parsed_argument| parsed_argument.inner.is_named(name))
+}
+
 /// Used argument stemming from [CliMake::parse](crate::CliMake::parse)-related
 /// parsing
 ///
@@ -19,13 +37,87 @@ use crate::{Argument, Subcommand};
 /// This structure may be converted into a raw [Argument] with the use of the
 /// [From]<[ParsedArgument]> implementation or similarly to the [Data] used for
 /// this argument.
-#[derive(Debug, PartialEq, Clone)]
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
 pub struct ParsedArgument<'a> {
+    /// Identifier of the argument used, see [Argument::id]
+    pub id: usize,
+
     /// Reference to the argument used
     pub inner: &'a Argument<'a>,
 
     /// Passed data for this argument
     pub data: Data,
+
+    /// If this argument was given by the user, rather than being filled in from
+    /// elsewhere such as its [Argument::env] fallback
+    ///
+    /// This allows an argument passed with no value (where [ParsedArgument::data]
+    /// is [Data::None]) to be told apart from one which was never passed at all.
+    pub is_present: bool,
+
+    /// Amount of times this argument was given by the user at its level, e.g. `3`
+    /// for `-vvv` or `-v -v -v`, which is useful for verbosity levels
+    ///
+    /// Unless the argument's [Argument::repeats] policy says otherwise, each
+    /// occurrence is still parsed separately, with every one of them having the
+    /// same total here. Arguments which weren't given by the user (see
+    /// [ParsedArgument::is_present]) have no occurrences.
+    pub occurrences: usize,
+
+    /// Amount of repeated values removed from this argument's data as it was
+    /// set with [Argument::unique], which is useful for diagnostics
+    pub duplicates: usize,
+
+    /// Position of the input this argument was given by, counting from `1` for
+    /// the first input after the executable, or [None] if it wasn't given by the
+    /// user (see [ParsedArgument::is_present])
+    ///
+    /// Short calls given together such as `-abc` share the same position.
+    pub position: Option<usize>,
+
+    /// Custom item stashed by the argument's [Argument::validator], if any
+    pub(crate) custom: Option<Shared<dyn Any + Send + Sync>>,
+}
+
+impl<'a> ParsedArgument<'a> {
+    /// Writes a single line explaining how this argument's data was determined
+    /// to `buf`, prefixed by the `path` of the subcommand it was given for
+    fn explain(&self, path: &str, buf: &mut impl Write) -> io::Result<()> {
+        let value = self.data.to_string();
+        let source = match (self.position, self.inner.env) {
+            (Some(position), _) => message(Key::GivenByInput, &[&position]),
+            (None, Some(var)) => message(Key::ReadFromEnv, &[&var]),
+            (None, None) => message(Key::FilledIn, &[]),
+        };
+
+        if value.is_empty() {
+            writeln!(buf, "{}{} — {}", path, self.inner.display_call(), source)
+        } else {
+            writeln!(
+                buf,
+                "{}{} = {} — {}",
+                path,
+                self.inner.display_call(),
+                value,
+                source
+            )
+        }
+    }
+
+    /// Gets the custom item stashed by this argument's [Argument::validator]
+    /// whilst parsing, if it has one and it's of type `T`
+    pub fn get_custom<T: 'static>(&self) -> Option<&T> {
+        self.custom.as_ref()?.0.downcast_ref()
+    }
+}
+
+/// Compares this parsed argument to the argument it may have come from by
+/// [Argument::id], so `parsed_arg == my_arg` only holds for the same definition
+/// (or clones of it)
+impl<'a, 'b> PartialEq<Argument<'b>> for ParsedArgument<'a> {
+    fn eq(&self, other: &Argument<'b>) -> bool {
+        self.id == other.id()
+    }
 }
 
 impl<'a> From<ParsedArgument<'a>> for &'a Argument<'a> {
@@ -52,16 +144,24 @@ impl<'a> From<ParsedArgument<'a>> for Data {
 /// This structure may be converted into a raw [Subcommand] with the use of the
 /// [From]<[ParsedSubcommand]> implementation or similarly the [ParsedSubcommand::subcommands]
 /// and [ParsedSubcommand::arguments] vectors.
-#[derive(Debug, PartialEq, Clone)]
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
 pub struct ParsedSubcommand<'a> {
+    /// Identifier of the subcommand used, see [Subcommand::id]
+    pub id: usize,
+
     /// Reference to the subcommand used
     pub inner: &'a Subcommand<'a>,
 
     /// Used subcommands contained inside of this subcommand (if any)
     pub subcommands: Vec<ParsedSubcommand<'a>>,
 
-    /// Used arguments contained inside of this subcommand (if any)
+    /// Used arguments contained inside of this subcommand (if any), ordered as
+    /// set by [CliMake::argument_order](crate::CliMake::argument_order)
     pub arguments: Vec<ParsedArgument<'a>>,
+
+    /// Names of the parents this subcommand was used under, starting with the
+    /// name of the cli itself, see [ParsedSubcommand::parents]
+    pub(crate) parents: Vec<&'a str>,
 }
 
 impl<'a> ParsedSubcommand<'a> {
@@ -69,11 +169,51 @@ impl<'a> ParsedSubcommand<'a> {
     /// whilst parsing
     pub(crate) fn new_empty(subcommand: &'a Subcommand<'a>) -> Self {
         Self {
+            id: subcommand.id(),
             inner: subcommand,
             subcommands: vec![],
             arguments: vec![],
+            parents: vec![],
         }
     }
+
+    /// Gets the names of the parents this subcommand was used under, starting
+    /// with the name of the cli and ending with its direct parent, e.g.
+    /// `["mytool", "net"]` for `mytool net fetch`
+    pub fn parents(&self) -> &[&'a str] {
+        &self.parents
+    }
+
+    /// Gets how deeply nested this subcommand is, with subcommands given directly
+    /// to the cli being at a depth of `1`
+    pub fn depth(&self) -> usize {
+        self.parents.len()
+    }
+
+    /// Gets the fully-qualified path of this subcommand separated by spaces,
+    /// including the cli and any parents, e.g. `mytool net fetch`
+    ///
+    /// This is useful for logging which command was ran without having to walk
+    /// the parsed tree to find it.
+    pub fn path(&self) -> String {
+        let mut path = self.parents.join(" ");
+
+        if !path.is_empty() {
+            path.push(' ');
+        }
+
+        path.push_str(self.inner.name);
+        path
+    }
+}
+
+/// Compares this parsed subcommand to the subcommand it may have come from by
+/// [Subcommand::id], so `parsed_sub == my_sub` only holds for the same
+/// definition (or clones of it)
+impl<'a, 'b> PartialEq<Subcommand<'b>> for ParsedSubcommand<'a> {
+    fn eq(&self, other: &Subcommand<'b>) -> bool {
+        self.id == other.id()
+    }
 }
 
 impl<'a> From<ParsedSubcommand<'a>> for &'a Subcommand<'a> {
@@ -94,6 +234,27 @@ impl<'a> From<ParsedSubcommand<'a>> for Vec<ParsedArgument<'a>> {
     }
 }
 
+/// Notice of a value which was ignored as a higher-precedence source overrode it,
+/// e.g. an [Argument::env] variable being set whilst the argument was also given
+/// by the user. These are only recorded if
+/// [CliMake::record_overrides](crate::CliMake::record_overrides) is set, see
+/// [ParsedCli::overrides]
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+pub struct Override {
+    /// Call of the argument the ignored value was for, e.g. `--token`
+    pub call: String,
+
+    /// Name of the subcommand the argument belongs to, or [None] for the
+    /// top-level cli
+    pub subcommand: Option<String>,
+
+    /// Environment variable the ignored value was set in
+    pub var: String,
+
+    /// Ignored value exactly as it was set
+    pub value: String,
+}
+
 /// Similar to [ParsedSubcommand], contains the top-level parsed arguments from
 /// [CliMake::parse](crate::CliMake::parse)-related parsing
 ///
@@ -101,13 +262,202 @@ impl<'a> From<ParsedSubcommand<'a>> for Vec<ParsedArgument<'a>> {
 ///
 /// This structure may be converted into a vector of subcommands from [ParsedCli::subcommands]
 /// or arguments from [ParsedCli::arguments].
-#[derive(Debug, PartialEq, Clone)]
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
 pub struct ParsedCli<'a> {
     /// Used subcommands contained inside of top-level parsed
     pub subcommands: Vec<ParsedSubcommand<'a>>,
 
-    /// Used arguments contained inside of top-level parsed
+    /// Used arguments contained inside of top-level parsed, ordered as set by
+    /// [CliMake::argument_order](crate::CliMake::argument_order)
     pub arguments: Vec<ParsedArgument<'a>>,
+
+    /// Inputs left untouched once parsing was stopped, which is done after a
+    /// `--` terminator or at the first positional input which isn't a
+    /// subcommand if
+    /// [CliMake::stop_at_positional](crate::CliMake::stop_at_positional) is set
+    pub trailing: Vec<String>,
+
+    /// Inputs which weren't recognised, which are only collected instead of
+    /// erroring when [CliMake::lenient](crate::CliMake::lenient) is set
+    pub unknown: Vec<String>,
+
+    /// Values ignored as they were overridden, see [ParsedCli::overrides]
+    pub(crate) overrides: Vec<Override>,
+}
+
+impl<'a> ParsedCli<'a> {
+    /// Splits this parsed cli into its owned parts in one move, being the
+    /// top-level [ParsedCli::arguments], [ParsedCli::subcommands],
+    /// [ParsedCli::trailing] inputs and [ParsedCli::unknown] inputs
+    ///
+    /// This avoids cloning when several parts are needed, as the [From]
+    /// implementations each consume the whole parsed cli.
+    pub fn into_parts(
+        self,
+    ) -> (
+        Vec<ParsedArgument<'a>>,
+        Vec<ParsedSubcommand<'a>>,
+        Vec<String>,
+        Vec<String>,
+    ) {
+        (
+            self.arguments,
+            self.subcommands,
+            self.trailing,
+            self.unknown,
+        )
+    }
+
+    /// Gets the values which were ignored as a higher-precedence source overrode
+    /// them, such as environment variables for arguments also given by the user,
+    /// ordered from the top-level cli down to the deepest subcommand
+    ///
+    /// These are only recorded if
+    /// [CliMake::record_overrides](crate::CliMake::record_overrides) is set, and
+    /// are useful for diagnostics such as a `--warn-overridden` flag for clearing
+    /// up confusing deployments.
+    pub fn overrides(&self) -> &[Override] {
+        &self.overrides
+    }
+
+    /// Finds the argument called `name` (e.g. `timeout` for `--timeout`) which
+    /// is nearest to the subcommand used, searching from the deepest subcommand
+    /// given up to the top-level arguments
+    ///
+    /// This allows shared options which may be given at any level of the cli to
+    /// be used without knowing which level they were given at. If the argument
+    /// was given multiple times at the same level, the last is used.
+    pub fn find(&self, name: &str) -> Option<&ParsedArgument<'a>> {
+        let mut levels = vec![&self.arguments];
+        let mut subcommands = &self.subcommands;
+
+        while let Some(subcommand) = subcommands.first() {
+            levels.push(&subcommand.arguments);
+            subcommands = &subcommand.subcommands;
+        }
+
+        levels
+            .into_iter()
+            .rev()
+            .find_map(|arguments| find_named(arguments, name))
+    }
+
+    /// Gets the value given to the top-level argument called `name` (e.g.
+    /// `port` for `--port`) as text, or the `default` if it wasn't given
+    ///
+    /// If the argument was given multiple times, the last value is used.
+    pub fn value_or(&self, name: &str, default: impl Into<String>) -> String {
+        match find_named(&self.arguments, name) {
+            Some(parsed_argument) => parsed_argument.data.to_string(),
+            None => default.into(),
+        }
+    }
+
+    /// Gets the value given to the top-level argument called `name` (e.g.
+    /// `port` for `--port`) converted into `T`, or the `default` if it wasn't
+    /// given or couldn't be converted
+    ///
+    /// If the argument was given multiple times, the last value is used. To
+    /// reject values which can't be converted whilst parsing instead, see
+    /// [Argument::validator].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use climake::prelude::*;
+    ///
+    /// let port = Argument::new("Port to serve on", vec!['p'], vec!["port"], Input::Text);
+    /// let cli = CliMake::new("server", vec![&port], vec![], None, None);
+    ///
+    /// let parsed = cli.parse_custom(vec!["server".to_string()]);
+    /// assert_eq!(parsed.get_or::<u16>("port", 8080), 8080);
+    /// ```
+    pub fn get_or<T: FromStr>(&self, name: &str, default: T) -> T {
+        find_named(&self.arguments, name)
+            .and_then(|parsed_argument| parsed_argument.data.to_string().parse().ok())
+            .unwrap_or(default)
+    }
+
+    /// Gets the value given to the top-level argument called `name` (e.g.
+    /// `bind` for `--bind`) converted into `T` using [FromData], or nothing if it
+    /// wasn't given or couldn't be converted
+    ///
+    /// If the argument was given multiple times, the last value is used.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use climake::prelude::*;
+    /// use std::net::SocketAddr;
+    ///
+    /// let bind = Argument::new("Address to bind to", vec![], vec!["bind"], Input::Text);
+    /// let cli = CliMake::new("server", vec![&bind], vec![], None, None);
+    ///
+    /// let inputs = vec!["server", "--bind", "127.0.0.1:80"];
+    /// let parsed = cli.parse_custom(inputs.into_iter().map(String::from));
+    ///
+    /// assert_eq!(parsed.get::<SocketAddr>("bind").unwrap().port(), 80);
+    /// ```
+    pub fn get<T: FromData>(&self, name: &str) -> Option<T> {
+        find_named(&self.arguments, name)
+            .and_then(|parsed_argument| T::from_data(&parsed_argument.data))
+    }
+
+    /// Writes a human-readable account of how the data of every parsed argument
+    /// was determined to `buf`, one line per argument, which is useful for
+    /// backing a `mytool doctor` command for support
+    ///
+    /// Each line gives the argument (prefixed by the subcommands it was given
+    /// for), its value if any and where it came from: either the position of
+    /// the input which gave it or the [Argument::env] variable it was read from.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use climake::prelude::*;
+    ///
+    /// let name = Argument::new("Name", vec!['n'], vec!["name"], Input::Text);
+    /// let add = Subcommand::new("add", vec![&name], vec![], None);
+    /// let cli = CliMake::new("tool", vec![], vec![&add], None, None);
+    ///
+    /// let inputs = vec!["tool", "add", "--name", "serde"];
+    /// let parsed = cli.parse_custom(inputs.into_iter().map(String::from));
+    ///
+    /// let mut buf: Vec<u8> = vec![];
+    /// parsed.explain(&mut buf).unwrap();
+    ///
+    /// assert_eq!(
+    ///     String::from_utf8(buf).unwrap(),
+    ///     "add --name = serde — given by input 2\n"
+    /// );
+    /// ```
+    pub fn explain(&self, buf: &mut impl Write) -> io::Result<()> {
+        explain_level("", &self.arguments, &self.subcommands, buf)
+    }
+}
+
+/// Writes explanations for every argument of the `arguments` and `subcommands` of
+/// a single level prefixed by its `path`, see [ParsedCli::explain]
+fn explain_level(
+    path: &str,
+    arguments: &[ParsedArgument],
+    subcommands: &[ParsedSubcommand],
+    buf: &mut impl Write,
+) -> io::Result<()> {
+    for parsed_argument in arguments {
+        parsed_argument.explain(path, buf)?;
+    }
+
+    for parsed_subcommand in subcommands {
+        explain_level(
+            &format!("{}{} ", path, parsed_subcommand.inner.name),
+            &parsed_subcommand.arguments,
+            &parsed_subcommand.subcommands,
+            buf,
+        )?;
+    }
+
+    Ok(())
 }
 
 impl<'a> From<ParsedCli<'a>> for Vec<ParsedSubcommand<'a>> {
@@ -121,3 +471,162 @@ impl<'a> From<ParsedCli<'a>> for Vec<ParsedArgument<'a>> {
         used_cli.arguments
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::io::Input;
+    use crate::CliMake;
+
+    /// Checks that parsed items compare equal to only their own definitions
+    #[test]
+    fn parsed_eq_definition() {
+        let package = Argument::new("The package name", vec!['p'], vec![], Input::Text);
+        let other = Argument::new("The package name", vec!['p'], vec![], Input::Text);
+        let add = Subcommand::new("add", vec![&package], vec![], "Adds a package");
+        let rem = Subcommand::new("rem", vec![&package], vec![], "Removes a package");
+
+        let parsed_argument = ParsedArgument {
+            id: package.id(),
+            inner: &package,
+            data: Data::None,
+            is_present: true,
+            occurrences: 1,
+            duplicates: 0,
+            position: None,
+            custom: None,
+        };
+        assert!(parsed_argument == package);
+        assert!(parsed_argument == package.clone());
+        assert!(parsed_argument != other);
+
+        let parsed_subcommand = ParsedSubcommand::new_empty(&add);
+        assert!(parsed_subcommand == add);
+        assert!(parsed_subcommand != rem);
+    }
+
+    /// Checks that [ParsedSubcommand::path] and related methods show where the
+    /// subcommand was used
+    #[test]
+    fn parsed_subcommand_path() {
+        let fetch = Subcommand::new("fetch", vec![], vec![], None);
+        let net = Subcommand::new("net", vec![], vec![&fetch], None);
+        let cli = CliMake::new("tool", vec![], vec![&net], None, None);
+
+        let parsed = cli
+            .try_parse_custom(vec!["tool", "net", "fetch"].into_iter().map(String::from))
+            .ok()
+            .unwrap();
+        let (net_parsed, fetch_parsed) = (
+            &parsed.subcommands[0],
+            &parsed.subcommands[0].subcommands[0],
+        );
+
+        assert_eq!(net_parsed.depth(), 1);
+        assert_eq!(net_parsed.path(), "tool net");
+        assert_eq!(fetch_parsed.depth(), 2);
+        assert_eq!(fetch_parsed.parents(), &["tool", "net"]);
+        assert_eq!(fetch_parsed.path(), "tool net fetch");
+    }
+
+    /// Checks that [ParsedCli::into_parts] gives back every part
+    #[test]
+    fn parsed_into_parts() {
+        let verbose = Argument::new("Verbose", vec!['v'], vec![], Input::None);
+        let add = Subcommand::new("add", vec![], vec![], None);
+        let mut cli = CliMake::new("tool", vec![&verbose], vec![&add], None, None);
+        cli.lenient(true);
+
+        let given = ["tool", "-v", "-x", "add"];
+        let (arguments, subcommands, trailing, unknown) = cli
+            .try_parse_custom(given.iter().map(|input| input.to_string()))
+            .ok()
+            .unwrap()
+            .into_parts();
+
+        assert!(arguments[0] == verbose);
+        assert!(subcommands[0] == add);
+        assert!(trailing.is_empty());
+        assert_eq!(unknown, vec!["-x"]);
+    }
+
+    /// Checks that [ParsedCli::value_or] and [ParsedCli::get_or] fall back to
+    /// their defaults correctly
+    #[test]
+    fn parsed_value_defaults() {
+        let port = Argument::new("Port", vec!['p'], vec!["port"], Input::Text);
+        let host = Argument::new("Host", vec!['h'], vec!["host"], Input::Text);
+        let cli = CliMake::new("server", vec![&port, &host], vec![], None, None);
+        let given = ["server", "--port", "80", "-p", "90", "-h", "local"];
+
+        let parsed = cli
+            .try_parse_custom(given.iter().map(|input| input.to_string()))
+            .ok()
+            .unwrap();
+
+        assert_eq!(parsed.value_or("port", "8080"), "90");
+        assert_eq!(parsed.value_or("p", "8080"), "90");
+        assert_eq!(parsed.value_or("other", "8080"), "8080");
+        assert_eq!(parsed.get_or::<u16>("port", 8080), 90);
+        assert_eq!(parsed.get_or::<u16>("host", 8080), 8080);
+        assert_eq!(parsed.get_or::<u16>("other", 8080), 8080);
+    }
+
+    /// Checks that [ParsedCli::find] finds the argument nearest to the deepest
+    /// subcommand given
+    #[test]
+    fn parsed_find() {
+        let timeout = Argument::new("Timeout", vec!['t'], vec!["timeout"], Input::Text);
+        let verbose = Argument::new("Verbose", vec!['v'], vec!["verbose"], Input::None);
+        let fetch = Subcommand::new("fetch", vec![&timeout], vec![], None);
+        let net = Subcommand::new("net", vec![&timeout], vec![&fetch], None);
+        let cli = CliMake::new("tool", vec![&timeout, &verbose], vec![&net], None, None);
+        let parse = |given: &[&str]| {
+            cli.try_parse_custom(given.iter().map(|input| input.to_string()))
+                .ok()
+                .unwrap()
+        };
+
+        let parsed = parse(&["tool", "-t", "1", "-v", "net", "-t", "2", "fetch"]);
+        assert_eq!(
+            parsed.find("timeout").map(|found| found.data.to_string()),
+            Some("2".to_string())
+        );
+        assert!(parsed.find("verbose").is_some());
+        assert!(parsed.find("other").is_none());
+
+        let parsed = parse(&["tool", "net", "fetch", "--timeout", "3"]);
+        assert_eq!(
+            parsed.find("t").map(|found| found.data.to_string()),
+            Some("3".to_string())
+        );
+        assert!(parsed.find("verbose").is_none());
+    }
+
+    /// Checks that [ParsedCli::explain] gives where each argument came from
+    #[test]
+    fn parsed_explain() -> io::Result<()> {
+        std::env::set_var("CLIMAKE_TEST_EXPLAIN", "secret");
+        let mut token = Argument::new("Token", vec![], vec!["token"], Input::Text);
+        token.env("CLIMAKE_TEST_EXPLAIN");
+        let verbose = Argument::new("Verbose", vec!['v'], vec![], Input::None);
+        let name = Argument::new("Name", vec!['n'], vec!["name"], Input::Text);
+        let add = Subcommand::new("add", vec![&name], vec![], None);
+        let cli = CliMake::new("tool", vec![&token, &verbose], vec![&add], None, None);
+
+        let inputs = ["tool", "-v", "add", "-n", "serde"];
+        let parsed = cli
+            .try_parse_custom(inputs.iter().map(|input| input.to_string()))
+            .ok()
+            .unwrap();
+        let mut chk_vec: Vec<u8> = vec![];
+        parsed.explain(&mut chk_vec)?;
+
+        assert_eq!(
+            std::str::from_utf8(chk_vec.as_slice()).unwrap(),
+            "-v — given by input 1\n--token = secret — read from environment variable CLIMAKE_TEST_EXPLAIN\nadd --name = serde — given by input 3\n"
+        );
+
+        Ok(())
+    }
+}
@@ -17,6 +17,6 @@
 //!   - [climake::parsed::ParsedCli](ParsedCli)
 //!   - [climake::parsed::ParsedSubcommand](ParsedSubcommand)
 
-pub use crate::io::{Data, Input};
+pub use crate::io::{Data, FromData, Input};
 pub use crate::parsed::{ParsedArgument, ParsedCli, ParsedSubcommand};
 pub use crate::{Argument, CliMake, Subcommand};
@@ -0,0 +1,40 @@
+//! Helpers for testing clis made using climake inside of your own tests
+//!
+//! # Importing
+//!
+//! This module isn't included in [crate::prelude] as it's only useful for tests,
+//! so it should be imported explicitly where needed.
+
+use crate::core::utils::split_shell;
+use crate::{CliMake, Error};
+
+/// Parses each example added to the `cli` with [CliMake::add_example], panicking
+/// with the example and error if any of them would fail to parse
+///
+/// This is designed to be used inside of your own tests, guaranteeing that the
+/// examples shown in help never fall out of date with the actual definition of
+/// the cli. Examples are split into inputs like posix shells do, so quoted
+/// values with spaces are kept as a single input.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use climake::prelude::*;
+///
+/// let add = Subcommand::new("add", vec![], vec![], "Adds a package");
+///
+/// let mut cli = CliMake::new("MyPkg", vec![], vec![&add], "A package manager", None);
+/// cli.add_example("mypkg add");
+///
+/// climake::testing::verify_examples(&cli);
+/// ```
+pub fn verify_examples<'a>(cli: &'a CliMake<'a>) {
+    for example in cli.examples.iter() {
+        let inputs = split_shell(example).map_err(Error::UnclosedQuote);
+
+        match inputs.and_then(|inputs| cli.try_parse_custom(inputs)) {
+            Ok(_) | Err(Error::Help(_)) => (),
+            Err(err) => panic!("Example `{}` fails to parse: {}", example, err),
+        }
+    }
+}
@@ -0,0 +1,203 @@
+//! Plain text utilities for printing consistent output alongside help, such as
+//! the [Table] formatter
+//!
+//! Help itself doesn't use these, as each argument and subcommand is written as
+//! a single `calls — help` line wrapped to the terminal rather than in columns,
+//! so there's nothing for a table to align. They're for the application's own
+//! output instead, e.g. `mytool list`.
+//!
+//! # Importing
+//!
+//! This module isn't included in [crate::prelude] as most clis won't need it, so
+//! it should be imported explicitly where needed.
+
+use std::fmt;
+
+/// Alignment of text inside of a [Table] column
+#[derive(Debug, Default, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum Align {
+    /// Aligned to the left of the column, the default
+    #[default]
+    Left,
+
+    /// Aligned to the right of the column, typically used for numbers
+    Right,
+
+    /// Centred inside of the column, leaning left if it can't be exact
+    Center,
+}
+
+/// Minimal table formatter which sizes each column to its widest cell, for
+/// printing tabular output such as `mytool list` without a table crate
+///
+/// Columns are separated by two spaces and trailing whitespace is never written.
+/// Widths are measured in characters, with cells in a column set using
+/// [Table::max_width] being truncated with `…` to fit.
+///
+/// # Example
+///
+/// ```rust
+/// use climake::text::{Align, Table};
+///
+/// let mut table = Table::new();
+/// table
+///     .header(vec!["NAME", "SIZE"])
+///     .row(vec!["serde", "12"])
+///     .row(vec!["climake", "3"])
+///     .align(1, Align::Right);
+///
+/// assert_eq!(table.to_string(), "NAME     SIZE\nserde      12\nclimake     3\n");
+/// ```
+#[derive(Debug, Default, PartialEq, Eq, Hash, Clone)]
+pub struct Table {
+    /// Header cells shown as the first row, if any
+    headers: Option<Vec<String>>,
+
+    /// Rows of cells, which may be of different lengths
+    rows: Vec<Vec<String>>,
+
+    /// Alignments for each column by index, defaulting to [Align::Left]
+    aligns: Vec<Align>,
+
+    /// Maximum widths for each column by index, if any
+    max_widths: Vec<Option<usize>>,
+}
+
+impl Table {
+    /// Creates a new, empty table
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the header cells shown as the first row, chainable
+    pub fn header(&mut self, cells: impl IntoIterator<Item = impl Into<String>>) -> &mut Self {
+        self.headers = Some(cells.into_iter().map(|cell| cell.into()).collect());
+        self
+    }
+
+    /// Adds a single row of cells, chainable
+    pub fn row(&mut self, cells: impl IntoIterator<Item = impl Into<String>>) -> &mut Self {
+        self.rows
+            .push(cells.into_iter().map(|cell| cell.into()).collect());
+        self
+    }
+
+    /// Sets the alignment of the column at `index`, chainable
+    pub fn align(&mut self, index: usize, align: Align) -> &mut Self {
+        if self.aligns.len() <= index {
+            self.aligns.resize(index + 1, Align::default());
+        }
+
+        self.aligns[index] = align;
+        self
+    }
+
+    /// Sets the maximum width of the column at `index`, truncating any cells
+    /// which are wider using `…`, chainable
+    pub fn max_width(&mut self, index: usize, width: usize) -> &mut Self {
+        if self.max_widths.len() <= index {
+            self.max_widths.resize(index + 1, None);
+        }
+
+        self.max_widths[index] = Some(width);
+        self
+    }
+
+    /// Gets every row to render, including the header
+    fn all_rows(&self) -> impl Iterator<Item = &Vec<String>> {
+        self.headers.iter().chain(self.rows.iter())
+    }
+
+    /// Gets a cell to render in the column at `index`, truncated to its
+    /// [Table::max_width] if set
+    fn cell(&self, cell: &str, index: usize) -> String {
+        match self.max_widths.get(index).copied().flatten() {
+            Some(max) if cell.chars().count() > max => match max {
+                0 => String::new(),
+                _ => cell.chars().take(max - 1).chain(Some('…')).collect(),
+            },
+            _ => cell.to_string(),
+        }
+    }
+
+    /// Calculates the width of each column from its widest cell
+    fn widths(&self) -> Vec<usize> {
+        let mut widths: Vec<usize> = vec![];
+
+        for row in self.all_rows() {
+            for (index, cell) in row.iter().enumerate() {
+                let width = self.cell(cell, index).chars().count();
+
+                match widths.get_mut(index) {
+                    Some(existing) => *existing = (*existing).max(width),
+                    None => widths.push(width),
+                }
+            }
+        }
+
+        widths
+    }
+}
+
+impl fmt::Display for Table {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let widths = self.widths();
+
+        for row in self.all_rows() {
+            let mut line = String::new();
+
+            for (index, cell) in row.iter().enumerate() {
+                let cell = self.cell(cell, index);
+                let padding = widths[index] - cell.chars().count();
+                let left = match self.aligns.get(index).copied().unwrap_or_default() {
+                    Align::Left => 0,
+                    Align::Right => padding,
+                    Align::Center => padding / 2,
+                };
+
+                if index != 0 {
+                    line.push_str("  ");
+                }
+
+                line.push_str(&" ".repeat(left));
+                line.push_str(&cell);
+                line.push_str(&" ".repeat(padding - left));
+            }
+
+            writeln!(f, "{}", line.trim_end())?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Checks that columns are sized to their widest cell and aligned
+    #[test]
+    fn table_align() {
+        let mut table = Table::new();
+        table
+            .header(vec!["A", "B", "C"])
+            .row(vec!["one", "two", "three"])
+            .row(vec!["x"])
+            .align(1, Align::Center)
+            .align(2, Align::Right);
+
+        assert_eq!(table.to_string(), "A     B       C\none  two  three\nx\n");
+    }
+
+    /// Checks that cells are truncated to [Table::max_width], even at
+    /// pathological widths
+    #[test]
+    fn table_truncate() {
+        let mut table = Table::new();
+        table.row(vec!["añbñc", "de"]).max_width(0, 3);
+        assert_eq!(table.to_string(), "añ…  de\n");
+
+        table.max_width(0, 0).max_width(1, 1);
+        assert_eq!(table.to_string(), "  …\n");
+    }
+}